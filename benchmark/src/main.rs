@@ -1,6 +1,7 @@
-use std::time::Instant;
+use std::{fs::File, io::BufReader, time::Instant};
 
 use sudoku_solver_lib::{
+    formats::lines::GridReader,
     generators::generators::Generator,
     grid::grid::Grid,
     solvers::{fast_solver::FastSolver, validator::is_valid},
@@ -29,10 +30,15 @@ impl GridSet {
 
 fn main() {
     let mut points = Vec::with_capacity(20);
+    // A file with one puzzle per line can be given to benchmark on real data
+    let file = std::env::args().nth(1);
 
     for i in 0..10 {
         println!("Running test {}", i);
-        let point = run_random_test(SIZE);
+        let point = match &file {
+            Some(path) => run_file_test(path),
+            None => run_random_test(SIZE),
+        };
         points.push(point);
     }
 
@@ -129,6 +135,50 @@ fn run_test(size: usize, remove_cells: usize) -> DataPoint {
     point
 }
 
+/// Solves every puzzle in the file, reading them one line at a time so large datasets fit in memory
+fn run_file_test(path: &str) -> DataPoint {
+    println!("Running test with the grids from {}", path);
+
+    let file = File::open(path).expect("Unable to open file");
+    let reader = GridReader::new(BufReader::new(file));
+    let mut solver = FastSolver::new_with_seed(SOLVE_SEED);
+
+    let mut point = DataPoint::empty();
+    let mut solve_time = 0;
+    let start_time = Instant::now();
+
+    for grid in reader {
+        let grid = match grid {
+            Ok(grid) => grid,
+            Err(e) => {
+                println!("Skipping grid: {}", e);
+                continue;
+            }
+        };
+
+        let solve_start = Instant::now();
+        let r = solver.solve(&grid);
+        solve_time += solve_start.elapsed().as_nanos();
+
+        match is_valid(&r) {
+            true => point.solved += 1,
+            false => point.error += 1,
+        }
+        point.size += 1;
+    }
+
+    // Reading the file takes the place of generating the grids
+    let size128 = point.size.max(1) as u128;
+    point.generation_time = start_time.elapsed().as_nanos() - solve_time;
+    point.generation_time_per = point.generation_time / size128;
+    point.solve_time = solve_time;
+    point.solve_time_per = solve_time / size128;
+
+    println!("Done! ");
+
+    point
+}
+
 fn solve(grids: Vec<GridSet>, point: DataPoint) -> DataPoint {
     let mut solver = FastSolver::new_with_seed(SOLVE_SEED);
     let size = grids.len();
@@ -0,0 +1,142 @@
+use crate::{
+    constraints::constraint_set::ConstraintSet,
+    grid::{cell_collection::CellCollection, coords::Coord, grid::Grid, mark::Mark},
+    solvers::solution_counter::SolutionCounter,
+};
+
+/// The values each cell takes over the solutions of a puzzle.
+///
+/// The backbone are the cells that hold the same value in every solution, the other cells are where
+/// a clue can be added to rule out solutions of an ambiguous puzzle.
+#[derive(Debug, Clone)]
+pub struct Backbone {
+    /// The candidates of each cell are the values found in the solutions
+    values: Grid,
+    solutions: usize,
+    complete: bool,
+}
+
+impl Backbone {
+    /// Analyzes the solutions of the grid, at most the given limit
+    pub fn analyze(grid: &Grid, limit: usize) -> Self {
+        Backbone::analyze_with(grid, &ConstraintSet::new(), limit)
+    }
+
+    /// Analyzes the solutions of the grid under the constraints, at most the given limit
+    pub fn analyze_with(grid: &Grid, constraints: &ConstraintSet, limit: usize) -> Self {
+        // One more than the limit tells if there are more solutions than those looked at
+        let mut solutions = SolutionCounter::find_with(grid, constraints, limit.saturating_add(1));
+        let complete = solutions.len() <= limit;
+        solutions.truncate(limit);
+
+        Backbone::from_solutions(&solutions, complete)
+    }
+
+    /// Collects the values of the solutions, complete tells if these are all the solutions there are
+    pub fn from_solutions(solutions: &[Grid], complete: bool) -> Self {
+        let mut values = Grid::empty();
+
+        for solution in solutions {
+            for index in solution.iter() {
+                if let Some(value) = solution.get_cell(index).value() {
+                    values.set_possible(index, Mark::from_value(value));
+                }
+            }
+        }
+
+        Self {
+            values,
+            solutions: solutions.len(),
+            complete,
+        }
+    }
+
+    /// Returns the amount of solutions that were looked at
+    pub fn solutions(&self) -> usize {
+        self.solutions
+    }
+
+    /// Returns true if every solution of the puzzle was looked at, false if the limit was reached
+    pub fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    /// Returns a grid where the candidates of each cell are the values found in the solutions
+    pub fn values(&self) -> &Grid {
+        &self.values
+    }
+
+    /// Returns the values the cell holds over the solutions
+    pub fn values_at(&self, coord: Coord) -> Vec<u16> {
+        let cell = self.values.get_cell_at(coord);
+
+        cell.iter_possible().map(|m| m.to_value()).collect()
+    }
+
+    /// Returns true if the cell holds the same value in every solution
+    pub fn is_backbone(&self, coord: Coord) -> bool {
+        self.values.get_cell_at(coord).possible_count() == 1
+    }
+
+    /// Returns the cells that hold the same value in every solution, givens included
+    pub fn backbone(&self) -> Vec<Coord> {
+        self.iter_coords()
+            .filter(|c| self.is_backbone(*c))
+            .collect()
+    }
+
+    /// Returns the cells that differ between solutions
+    pub fn open_cells(&self) -> Vec<Coord> {
+        let open = |c: &Coord| self.values.get_cell_at(*c).possible_count() > 1;
+
+        self.iter_coords().filter(open).collect()
+    }
+
+    fn iter_coords(&self) -> impl Iterator<Item = Coord> + '_ {
+        self.values.iter().map(Coord::from_index)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Backbone;
+    use crate::{
+        grid::{coords::Coord, grid::Grid},
+        test::util::general_tests,
+    };
+
+    #[test]
+    fn test_swapped_values() {
+        // Swapping the 1s and 2s of the filled sudoku is the only other solution
+        let filled = general_tests::filled_sudoku();
+        let mut grid = filled;
+        general_tests::remove_number(&mut grid, 1);
+        general_tests::remove_number(&mut grid, 2);
+
+        let backbone = Backbone::analyze(&grid, 10);
+        assert_eq!(backbone.solutions(), 2);
+        assert!(backbone.is_complete());
+        assert_eq!(backbone.open_cells().len(), 18);
+        assert_eq!(backbone.backbone().len(), 63);
+
+        let open = backbone.open_cells()[0];
+        assert_eq!(backbone.values_at(open), vec![1, 2]);
+        assert!(backbone.is_backbone(Coord::new(0, 0)));
+        assert_eq!(
+            backbone.values_at(Coord::new(0, 0)),
+            vec![filled.get_cell_at(Coord::new(0, 0)).get_value()]
+        );
+    }
+
+    #[test]
+    fn test_limit() {
+        let backbone = Backbone::analyze(&Grid::new(), 5);
+
+        assert_eq!(backbone.solutions(), 5);
+        assert!(!backbone.is_complete());
+        assert!(!backbone.open_cells().is_empty());
+
+        let none = Backbone::from_solutions(&[], true);
+        assert!(none.backbone().is_empty() && none.open_cells().is_empty());
+    }
+}
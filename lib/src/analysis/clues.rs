@@ -0,0 +1,335 @@
+use crate::{
+    constraints::constraint_set::ConstraintSet,
+    grid::{cell::Cell, cell_collection::CellCollection, coords::Coord, grid::Grid},
+    solvers::solution_counter::SolutionCounter,
+};
+
+/// The amount of solutions compared at once when suggesting clues, one bit for each
+const SOLUTION_LIMIT: usize = 128;
+
+/// Sets of up to this many clues are searched exhaustively, beyond that clues are added one at a time
+const EXACT_CLUES: usize = 3;
+
+/// An analysis of the givens of a puzzle.
+///
+/// For a unique puzzle it lists the givens that can be removed while keeping it unique,
+/// for a puzzle with several solutions it suggests clues that make it unique.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClueAnalysis {
+    solutions: usize,
+    redundant: Vec<Coord>,
+    suggested: Vec<(Coord, u16)>,
+    /// Whether no fewer clues make the puzzle unique
+    fewest: bool,
+}
+
+impl ClueAnalysis {
+    pub fn analyze(grid: &Grid) -> Self {
+        ClueAnalysis::analyze_with(grid, &ConstraintSet::new())
+    }
+
+    pub fn analyze_with(grid: &Grid, constraints: &ConstraintSet) -> Self {
+        let solutions = SolutionCounter::count_with(grid, constraints, 2);
+
+        let (redundant, (suggested, fewest)) = match solutions {
+            1 => (find_redundant(grid, constraints), (Vec::new(), true)),
+            _ => (Vec::new(), suggest_clues(grid, constraints)),
+        };
+
+        Self {
+            solutions,
+            redundant,
+            suggested,
+            fewest,
+        }
+    }
+
+    /// Returns true if the puzzle has exactly one solution
+    pub fn is_unique(&self) -> bool {
+        self.solutions == 1
+    }
+
+    /// Returns true if the puzzle is unique and every given is needed to keep it that way
+    pub fn is_minimal(&self) -> bool {
+        self.is_unique() && self.redundant.is_empty()
+    }
+
+    /// Returns the givens that can each be removed on their own while the puzzle stays unique.
+    ///
+    /// Removing one can make others needed, so they can not all be removed at once.
+    pub fn redundant(&self) -> &[Coord] {
+        &self.redundant
+    }
+
+    /// Returns the clues that together make the puzzle unique, empty if it is unique already or has no solution.
+    ///
+    /// The clues are not always the fewest possible, see [`ClueAnalysis::is_fewest_suggested`].
+    pub fn suggested(&self) -> &[(Coord, u16)] {
+        &self.suggested
+    }
+
+    /// Returns true if the suggested clues are proven to be the fewest that make the puzzle unique.
+    ///
+    /// This is only searched for when the puzzle has few solutions and a few clues are enough,
+    /// then the clues that single out each of the solutions are compared and the smallest set is kept.
+    /// Otherwise the clues are picked greedily and there may be a smaller set.
+    pub fn is_fewest_suggested(&self) -> bool {
+        self.fewest
+    }
+}
+
+/// Removes each given in turn and keeps the ones the puzzle stays unique without
+fn find_redundant(grid: &Grid, constraints: &ConstraintSet) -> Vec<Coord> {
+    let puzzle = &mut grid.clone();
+    let mut redundant = Vec::new();
+
+    for index in grid.iter() {
+        let cell = *grid.get_cell(index);
+        if !cell.is_determined() {
+            continue;
+        }
+
+        puzzle.set_cell(index, &Cell::new());
+        if SolutionCounter::is_unique_with(puzzle, constraints) {
+            redundant.push(Coord::from_index(index));
+        }
+        puzzle.set_cell(index, &cell);
+    }
+
+    redundant
+}
+
+/// Suggests clues from one of the solutions that rule out all the others, and whether they are proven to be the fewest.
+///
+/// Each solution is a bit, and each open cell rules out the solutions that differ from the chosen one in it.
+/// While all solutions fit in the limit, the smallest set of cells covering them is searched for with
+/// every solution as the chosen one, otherwise the cell of the first solution that rules out the most
+/// is added and the solutions are looked at again.
+/// Once a clue has been added that way the result is a heuristic, the smallest set is not proven.
+fn suggest_clues(grid: &Grid, constraints: &ConstraintSet) -> (Vec<(Coord, u16)>, bool) {
+    let puzzle = &mut grid.clone();
+    let mut clues = Vec::new();
+    let mut greedy = false;
+
+    loop {
+        let solutions = SolutionCounter::find_with(puzzle, constraints, SOLUTION_LIMIT);
+        if solutions.len() <= 1 {
+            return (clues, !greedy);
+        }
+
+        if solutions.len() < SOLUTION_LIMIT {
+            if let Some((chosen, cover)) = fewest_cover(&solutions) {
+                let value_at = |index: usize| solutions[chosen].get_cell(index).get_value();
+                clues.extend(
+                    cover
+                        .into_iter()
+                        .map(|i| (Coord::from_index(i), value_at(i))),
+                );
+                return (clues, !greedy);
+            }
+        }
+
+        let (index, _) = *rules_out(&solutions, 0)
+            .iter()
+            .max_by_key(|(_, bits)| bits.count_ones())
+            .expect("different solutions differ in a cell");
+        let clue = (
+            Coord::from_index(index),
+            solutions[0].get_cell(index).get_value(),
+        );
+        constraints.place_value_at(puzzle, clue.0, clue.1);
+        clues.push(clue);
+        greedy = true;
+    }
+}
+
+/// Returns for each cell the solutions that differ from the chosen one in it, leaving out the cells where none do
+fn rules_out(solutions: &[Grid], chosen: usize) -> Vec<(usize, u128)> {
+    let value_at = |index: usize| solutions[chosen].get_cell(index).get_value();
+
+    solutions[chosen]
+        .iter()
+        .map(|index| {
+            let differ = solutions
+                .iter()
+                .enumerate()
+                .filter(|(_, s)| s.get_cell(index).get_value() != value_at(index));
+            (index, differ.fold(0u128, |bits, (i, _)| bits | 1 << i))
+        })
+        .filter(|(_, bits)| *bits != 0)
+        .collect()
+}
+
+/// Returns the solution that can be singled out with the fewest cells, with those cells, up to the exact limit
+fn fewest_cover(solutions: &[Grid]) -> Option<(usize, Vec<usize>)> {
+    let all = (0..solutions.len()).fold(0u128, |bits, i| bits | 1 << i);
+    let mut best: Option<(usize, Vec<usize>)> = None;
+
+    for chosen in 0..solutions.len() {
+        // Only a smaller cover than the best so far is worth searching for
+        let limit = best
+            .as_ref()
+            .map_or(EXACT_CLUES, |(_, cover)| cover.len() - 1);
+        if limit == 0 {
+            break;
+        }
+
+        if let Some(cover) =
+            smallest_cover(&rules_out(solutions, chosen), all & !(1 << chosen), limit)
+        {
+            best = Some((chosen, cover));
+        }
+    }
+
+    best
+}
+
+/// Returns the fewest cells that together rule out every solution in the target, up to the given amount
+fn smallest_cover(rules_out: &[(usize, u128)], target: u128, limit: usize) -> Option<Vec<usize>> {
+    (1..=limit).find_map(|size| {
+        let mut chosen = Vec::with_capacity(size);
+        find_cover(rules_out, target, 0, size, &mut chosen).then_some(chosen)
+    })
+}
+
+fn find_cover(
+    rules_out: &[(usize, u128)],
+    remaining: u128,
+    start: usize,
+    size: usize,
+    chosen: &mut Vec<usize>,
+) -> bool {
+    if remaining == 0 {
+        return true;
+    }
+    if size == 0 {
+        return false;
+    }
+
+    for (offset, (index, bits)) in rules_out[start..].iter().enumerate() {
+        if remaining & bits == 0 {
+            continue;
+        }
+
+        chosen.push(*index);
+        if find_cover(
+            rules_out,
+            remaining & !bits,
+            start + offset + 1,
+            size - 1,
+            chosen,
+        ) {
+            return true;
+        }
+        chosen.pop();
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod test {
+    use super::{rules_out, smallest_cover, ClueAnalysis};
+    use crate::{
+        constraints::constraint_set::ConstraintSet,
+        grid::{grid::Grid, utility::utility},
+        solvers::solution_counter::SolutionCounter,
+        test::util::general_tests,
+    };
+
+    #[test]
+    fn test_redundant() {
+        let puzzle = utility::from_digit(
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300",
+        )
+        .unwrap();
+
+        let analysis = ClueAnalysis::analyze(&puzzle);
+        assert!(analysis.is_unique());
+        assert!(analysis.suggested().is_empty());
+        assert!(!analysis.is_minimal());
+
+        for coord in analysis.redundant() {
+            let mut grid = puzzle;
+            grid.set_cell_at(*coord, &Default::default());
+            assert!(SolutionCounter::is_unique(&grid));
+        }
+
+        // The filled sudoku is unique with every cell given, and any one of them can go
+        let filled = ClueAnalysis::analyze(&general_tests::filled_sudoku());
+        assert_eq!(filled.redundant().len(), 81);
+    }
+
+    #[test]
+    fn test_suggest_for_swapped_values() {
+        let mut puzzle = general_tests::filled_sudoku();
+        general_tests::remove_number(&mut puzzle, 1);
+        general_tests::remove_number(&mut puzzle, 2);
+
+        // Swapping the 1s and 2s is the only other solution, so a single clue does it
+        let analysis = ClueAnalysis::analyze(&puzzle);
+        assert!(!analysis.is_unique() && !analysis.is_minimal());
+        assert!(analysis.redundant().is_empty());
+        assert_eq!(analysis.suggested().len(), 1);
+        assert!(analysis.is_fewest_suggested());
+
+        let (coord, value) = analysis.suggested()[0];
+        puzzle.place_value_at(coord, value);
+        assert!(SolutionCounter::is_unique(&puzzle));
+    }
+
+    #[test]
+    fn test_suggest_for_open_puzzle() {
+        let mut puzzle = general_tests::filled_sudoku();
+        general_tests::remove_cells_amount(&mut puzzle, 60);
+
+        let analysis = ClueAnalysis::analyze(&puzzle);
+        for (coord, value) in analysis.suggested() {
+            puzzle.place_value_at(*coord, *value);
+        }
+        assert!(SolutionCounter::is_unique(&puzzle));
+
+        let empty = ClueAnalysis::analyze(&Grid::new());
+        assert!(!empty.is_unique());
+        assert!(!empty.suggested().is_empty());
+    }
+
+    #[test]
+    fn test_suggest_greedy() {
+        // The empty grid has too many solutions to compare at once, so clues are added greedily first
+        let mut puzzle = Grid::new();
+        let analysis = ClueAnalysis::analyze(&puzzle);
+        assert!(!analysis.is_fewest_suggested());
+        assert!(analysis.suggested().len() > 3);
+
+        for (coord, value) in analysis.suggested() {
+            puzzle.place_value_at(*coord, *value);
+        }
+        assert!(SolutionCounter::is_unique(&puzzle));
+
+        let unique = ClueAnalysis::analyze(&general_tests::filled_sudoku());
+        assert!(unique.suggested().is_empty());
+        assert!(unique.is_fewest_suggested());
+    }
+
+    #[test]
+    fn test_suggest_for_other_solution() {
+        let mut puzzle = utility::from_digit(
+            "040000850751200040000354070092070060005006010010000407000095730007000000008061024",
+        )
+        .unwrap();
+
+        // The first solution found needs two clues to rule out the others, another one needs a single clue
+        let solutions = SolutionCounter::find_with(&puzzle, &ConstraintSet::new(), 128);
+        let others = (1..solutions.len()).fold(0u128, |bits, i| bits | 1 << i);
+        assert!(smallest_cover(&rules_out(&solutions, 0), others, 1).is_none());
+
+        let analysis = ClueAnalysis::analyze(&puzzle);
+        assert!(analysis.is_fewest_suggested());
+        assert_eq!(analysis.suggested().len(), 1);
+
+        let (coord, value) = analysis.suggested()[0];
+        puzzle.place_value_at(coord, value);
+        assert!(SolutionCounter::is_unique(&puzzle));
+    }
+}
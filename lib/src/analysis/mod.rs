@@ -0,0 +1,2 @@
+pub mod backbone;
+pub mod clues;
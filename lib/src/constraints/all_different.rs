@@ -0,0 +1,197 @@
+use std::error::Error;
+
+use crate::{
+    grid::{
+        cell_collection::CellCollection, coords::Coord, dimensions::Nine, grid::Grid, mark::Mark,
+    },
+    solvers::solver::SolveResult,
+};
+
+use super::constraint::Constraint;
+
+/// An extra region where every value can be placed at most once, such as a diagonal or an extra box
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AllDifferent {
+    cells: Vec<Coord>,
+}
+
+impl AllDifferent {
+    pub fn new(cells: Vec<Coord>) -> Self {
+        debug_assert!(
+            cells.len() <= 9,
+            "a region can hold at most 9 different values"
+        );
+
+        Self { cells }
+    }
+
+    /// Creates a region from the cells of a collection
+    pub fn from_collection<T: CellCollection<Dimensions = Nine>>(area: &T) -> Self {
+        Self::new(area.iter().map(|i| area.get_coord(i)).collect())
+    }
+
+    pub fn cells(&self) -> &[Coord] {
+        &self.cells
+    }
+
+    pub fn contains(&self, coord: Coord) -> bool {
+        self.cells.contains(&coord)
+    }
+}
+
+impl CellCollection for AllDifferent {
+    type Dimensions = Nine;
+
+    fn get_coord(&self, index: usize) -> Coord {
+        self.cells[index]
+    }
+
+    fn iter(&self) -> std::ops::Range<usize> {
+        0..self.cells.len()
+    }
+
+    fn max(&self) -> usize {
+        self.cells.len()
+    }
+}
+
+impl Constraint for AllDifferent {
+    fn name(&self) -> &'static str {
+        "All Different"
+    }
+
+    fn mark_off(&self, grid: &mut Grid, coord: Coord, value: u16) {
+        if self.contains(coord) {
+            mark_off_area(self, grid, coord, value);
+        }
+    }
+
+    /// A region of 9 cells holds every value once, so a value that fits in only one cell goes there
+    fn propagate(&self, grid: &mut Grid) -> SolveResult {
+        if self.cells.len() != 9 {
+            return SolveResult::Nothing;
+        }
+
+        place_hidden_singles(self, grid)
+    }
+
+    fn validate(&self, grid: &Grid) -> Result<(), Box<dyn Error>> {
+        validate_distinct(self, grid)
+    }
+}
+
+/// Unsets the value from the other cells of the area, the coord must be in the area
+pub fn mark_off_area<T: CellCollection<Dimensions = Nine>>(
+    area: &T,
+    grid: &mut Grid,
+    coord: Coord,
+    value: u16,
+) {
+    let mark = Mark::from_value(value);
+
+    for other in area.iter().map(|i| area.get_coord(i)) {
+        if other != coord {
+            grid.unset_possible_at(other, mark);
+        }
+    }
+}
+
+/// Places every value that fits in only one cell of the area, the area must hold every value once
+pub fn place_hidden_singles<T: CellCollection<Dimensions = Nine>>(
+    area: &T,
+    grid: &mut Grid,
+) -> SolveResult {
+    let mut changed = false;
+
+    for mark in Mark::iter() {
+        if area.count_determined_value(grid, mark.to_value()) > 0 {
+            continue;
+        }
+
+        let mut possible = area
+            .iter()
+            .map(|i| area.get_coord(i))
+            .filter(|c| grid.is_possible_at(*c, mark));
+
+        match (possible.next(), possible.next()) {
+            (None, _) => return SolveResult::Error,
+            (Some(coord), None) => {
+                grid.place_value_at(coord, mark.to_value());
+                mark_off_area(area, grid, coord, mark.to_value());
+                changed = true;
+            }
+            _ => {}
+        }
+    }
+
+    SolveResult::from_changed(changed)
+}
+
+/// Returns an error if a value is placed more than once in the area
+pub fn validate_distinct<T: CellCollection<Dimensions = Nine>>(
+    area: &T,
+    grid: &Grid,
+) -> Result<(), Box<dyn Error>> {
+    for mark in Mark::iter() {
+        if area.count_determined_value(grid, mark.to_value()) > 1 {
+            let msg = format!(
+                "More than one {} in region, from {} to {}",
+                mark,
+                area.get_coord(0),
+                area.get_coord(area.max() - 1)
+            );
+            return Err(msg.into());
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::AllDifferent;
+    use crate::{
+        constraints::constraint::Constraint,
+        grid::{coords::Coord, grid::Grid, mark::Mark},
+        solvers::solver::SolveResult,
+    };
+
+    fn diagonal() -> AllDifferent {
+        AllDifferent::new((0..9).map(|i| Coord::new(i, i)).collect())
+    }
+
+    #[test]
+    fn test_mark_off_and_validate() {
+        let region = diagonal();
+        let grid = &mut Grid::new();
+
+        grid.place_value_at(Coord::new(0, 0), 5);
+        region.mark_off(grid, Coord::new(0, 0), 5);
+
+        assert!(!grid.is_possible_at(Coord::new(8, 8), Mark::N5));
+        assert!(grid.is_possible_at(Coord::new(8, 7), Mark::N5));
+        assert!(region.validate(grid).is_ok());
+
+        grid.place_value_at(Coord::new(4, 4), 5);
+        assert!(region.validate(grid).is_err());
+    }
+
+    #[test]
+    fn test_propagate_hidden_single() {
+        let region = diagonal();
+        let grid = &mut Grid::new();
+
+        for i in 0..8 {
+            grid.unset_possible_at(Coord::new(i, i), Mark::N7);
+        }
+
+        assert_eq!(region.propagate(grid), SolveResult::Updated);
+        assert_eq!(grid.get_cell_at(Coord::new(8, 8)).value(), Some(7));
+
+        grid.unset_possible_at(Coord::new(3, 3), Mark::N2);
+        for i in 0..9 {
+            grid.unset_possible_at(Coord::new(i, i), Mark::N2);
+        }
+        assert_eq!(region.propagate(grid), SolveResult::Error);
+    }
+}
@@ -0,0 +1,178 @@
+use std::error::Error;
+
+use crate::{
+    grid::{cell::Cell, coords::Coord, grid::Grid},
+    solvers::solver::SolveResult,
+};
+
+use super::{
+    constraint::Constraint,
+    util::{candidates, highest_value, keep_candidates, lowest_value, value_range},
+};
+
+/// An arrow, the values along the arrow add up to the value in the circle.
+///
+/// Values along the arrow can repeat, unless the cells see each other.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "ArrowDefinition")
+)]
+pub struct Arrow {
+    circle: Coord,
+    cells: Vec<Coord>,
+}
+
+/// The fields of an arrow as they are read, checked by [`Arrow::new`]
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct ArrowDefinition {
+    circle: Coord,
+    cells: Vec<Coord>,
+}
+
+impl Arrow {
+    /// Creates an arrow, returns an error if the arrow is empty or the circle is on the arrow
+    pub fn new(circle: Coord, cells: Vec<Coord>) -> Result<Self, Box<dyn Error>> {
+        if cells.is_empty() {
+            return Err("An arrow holds at least one cell")?;
+        }
+        if cells.contains(&circle) {
+            let msg = format!("The circle at {} is on the arrow", circle);
+            return Err(msg)?;
+        }
+
+        Ok(Self { circle, cells })
+    }
+
+    pub fn circle(&self) -> Coord {
+        self.circle
+    }
+
+    pub fn cells(&self) -> &[Coord] {
+        &self.cells
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<ArrowDefinition> for Arrow {
+    type Error = Box<dyn Error>;
+
+    fn try_from(definition: ArrowDefinition) -> Result<Self, Self::Error> {
+        Arrow::new(definition.circle, definition.cells)
+    }
+}
+
+impl Constraint for Arrow {
+    fn name(&self) -> &'static str {
+        "Arrow"
+    }
+
+    fn propagate(&self, grid: &mut Grid) -> SolveResult {
+        let masks: Vec<Cell> = self.cells.iter().map(|c| candidates(grid, *c)).collect();
+        let sum_min: i32 = masks.iter().map(|m| lowest_value(*m)).sum();
+        let sum_max: i32 = masks.iter().map(|m| highest_value(*m)).sum();
+
+        if masks.iter().any(|m| m.is_empty()) {
+            return SolveResult::Error;
+        }
+
+        // The circle lies between the lowest and highest sum of the arrow
+        let circle = candidates(grid, self.circle) & value_range(sum_min, sum_max);
+        if circle.is_empty() {
+            return SolveResult::Error;
+        }
+        let mut changed = keep_candidates(grid, self.circle, circle);
+
+        // Each cell makes up what the other cells can not reach
+        let (circle_min, circle_max) = (lowest_value(circle), highest_value(circle));
+        for (coord, cell) in self.cells.iter().zip(masks) {
+            let others_min = sum_min - lowest_value(cell);
+            let others_max = sum_max - highest_value(cell);
+            let allowed = value_range(circle_min - others_max, circle_max - others_min);
+
+            if (cell & allowed).is_empty() {
+                return SolveResult::Error;
+            }
+            changed |= keep_candidates(grid, *coord, allowed);
+        }
+
+        SolveResult::from_changed(changed)
+    }
+
+    fn validate(&self, grid: &Grid) -> Result<(), Box<dyn Error>> {
+        let values: Vec<u16> = self
+            .cells
+            .iter()
+            .filter_map(|c| grid.get_cell_at(*c).value())
+            .collect();
+        let total: u16 = values.iter().sum();
+        let filled = values.len() == self.cells.len();
+
+        let broken = match grid.get_cell_at(self.circle).value() {
+            Some(circle) => total > circle || (filled && total != circle),
+            None => total > 9,
+        };
+
+        if broken {
+            let msg = format!(
+                "The arrow of the circle at {} adds up to {}",
+                self.circle, total
+            );
+            return Err(msg)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Arrow;
+    use crate::{
+        constraints::constraint::Constraint,
+        grid::{coords::Coord, grid::Grid, mark::Mark},
+        solvers::solver::SolveResult,
+        test::util::general_tests,
+    };
+
+    #[test]
+    fn test_propagate() {
+        let arrow = Arrow::new(Coord::new(0, 0), vec![Coord::new(1, 1), Coord::new(2, 2)]).unwrap();
+        let grid = &mut Grid::new();
+
+        assert_eq!(arrow.propagate(grid), SolveResult::Updated);
+        assert!(!grid.is_possible_at(Coord::new(0, 0), Mark::N1));
+        assert!(!grid.is_possible_at(Coord::new(1, 1), Mark::N9));
+
+        grid.place_value_at(Coord::new(0, 0), 4);
+        grid.place_value_at(Coord::new(1, 1), 1);
+        assert_eq!(arrow.propagate(grid), SolveResult::Updated);
+        let marks: Vec<Mark> = grid.get_cell_at(Coord::new(2, 2)).iter_possible().collect();
+        assert_eq!(marks, vec![Mark::N3]);
+
+        grid.place_value_at(Coord::new(1, 1), 5);
+        assert_eq!(arrow.propagate(grid), SolveResult::Error);
+    }
+
+    #[test]
+    fn test_validate() {
+        // The first row is 4 3 5 2 6 9 7 8 1
+        let grid = general_tests::filled_sudoku();
+        let arrow = Arrow::new(Coord::new(0, 2), vec![Coord::new(0, 1), Coord::new(0, 3)]).unwrap();
+
+        assert!(arrow.validate(&grid).is_ok());
+        assert!(Arrow::new(Coord::new(0, 2), vec![Coord::new(0, 0)])
+            .unwrap()
+            .validate(&grid)
+            .is_err());
+        assert!(arrow.validate(&Grid::new()).is_ok());
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(Arrow::new(Coord::new(0, 0), vec![]).is_err());
+        assert!(Arrow::new(Coord::new(0, 0), vec![Coord::new(0, 1), Coord::new(0, 0)]).is_err());
+    }
+}
@@ -0,0 +1,286 @@
+use std::error::Error;
+
+use crate::{
+    grid::{
+        cell::Cell, cell_collection::CellCollection, coords::Coord, dimensions::Nine, grid::Grid,
+        mark::Mark,
+    },
+    solvers::solver::SolveResult,
+};
+
+use super::{
+    constraint::Constraint,
+    util::{candidates, keep_candidates},
+};
+
+/// A killer cage, the values of the cells add up to the sum and can not repeat inside of the cage
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cage {
+    sum: u16,
+    cells: Vec<Coord>,
+}
+
+impl Cage {
+    /// Creates a cage, returns an error if it does not hold 1 to 9 cells
+    pub fn new(sum: u16, cells: Vec<Coord>) -> Result<Self, Box<dyn Error>> {
+        if cells.is_empty() || cells.len() > 9 {
+            let msg = format!("A cage holds 1 to 9 cells, not {}", cells.len());
+            return Err(msg)?;
+        }
+
+        Ok(Self { sum, cells })
+    }
+
+    pub fn sum(&self) -> u16 {
+        self.sum
+    }
+
+    pub fn cells(&self) -> &[Coord] {
+        &self.cells
+    }
+
+    pub fn contains(&self, coord: Coord) -> bool {
+        self.cells.contains(&coord)
+    }
+}
+
+impl CellCollection for Cage {
+    type Dimensions = Nine;
+
+    fn get_coord(&self, index: usize) -> Coord {
+        self.cells[index]
+    }
+
+    fn iter(&self) -> std::ops::Range<usize> {
+        0..self.cells.len()
+    }
+
+    fn max(&self) -> usize {
+        self.cells.len()
+    }
+}
+
+impl Constraint for Cage {
+    fn name(&self) -> &'static str {
+        "Cage"
+    }
+
+    fn mark_off(&self, grid: &mut Grid, coord: Coord, value: u16) {
+        if !self.contains(coord) {
+            return;
+        }
+
+        let mark = Mark::from_value(value);
+        for other in self.cells.iter() {
+            if *other != coord {
+                grid.unset_possible_at(*other, mark);
+            }
+        }
+    }
+
+    fn propagate(&self, grid: &mut Grid) -> SolveResult {
+        eliminate_combinations(grid, &self.cells, self.sum)
+    }
+
+    fn validate(&self, grid: &Grid) -> Result<(), Box<dyn Error>> {
+        let mut seen = 0u16;
+        let mut total = 0;
+
+        for coord in self.cells.iter() {
+            let value = match grid.get_cell_at(*coord).value() {
+                Some(value) => value,
+                None => continue,
+            };
+
+            if seen & (1 << value) != 0 {
+                let msg = format!("More than one {} in the cage at {}", value, self.cells[0]);
+                return Err(msg)?;
+            }
+            seen |= 1 << value;
+            total += value;
+        }
+
+        let filled = self.count_determined(grid) == self.cells.len();
+        if total > self.sum || (filled && total != self.sum) {
+            let msg = format!(
+                "The cage at {} adds up to {} instead of {}",
+                self.cells[0], total, self.sum
+            );
+            return Err(msg)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Removes the candidates that are not part of any combination of different values adding up to the sum.
+///
+/// The cells must all hold different values, such as the cells of a cage or of a single row.
+/// Returns an error if no combination fits the candidates.
+pub fn eliminate_combinations(grid: &mut Grid, cells: &[Coord], sum: u16) -> SolveResult {
+    let masks: Vec<Cell> = cells.iter().map(|c| candidates(grid, *c)).collect();
+    let supported = match supported_combinations(&masks, sum) {
+        Some(supported) => supported,
+        None => return SolveResult::Error,
+    };
+
+    let mut changed = false;
+    for (index, coord) in cells.iter().enumerate() {
+        changed |= keep_candidates(grid, *coord, supported[index]);
+    }
+
+    SolveResult::from_changed(changed)
+}
+
+/// Returns per cell the mask of the candidates that are part of a combination of different values adding up to the sum.
+///
+/// Returns none if no combination fits the candidates.
+pub fn supported_combinations(candidates: &[Cell], sum: u16) -> Option<Vec<Cell>> {
+    let mut search = Combinations {
+        candidates,
+        sum,
+        feasible: vec![None; (candidates.len() + 1) << 9],
+        supported: vec![Cell::new_empty(); candidates.len()],
+        visited: vec![false; (candidates.len() + 1) << 9],
+    };
+
+    if !search.is_feasible(0, 0) {
+        return None;
+    }
+    search.collect_support(0, 0);
+
+    Some(search.supported)
+}
+
+/// Searches the assignments of different values to the cells, one cell at a time.
+///
+/// The state is the index of the next cell and the values used so far, which is small enough to remember.
+/// The used values are kept by the index of their mark, one bit each.
+struct Combinations<'a> {
+    candidates: &'a [Cell],
+    sum: u16,
+    feasible: Vec<Option<bool>>,
+    supported: Vec<Cell>,
+    visited: Vec<bool>,
+}
+
+impl Combinations<'_> {
+    fn state(index: usize, used: u16) -> usize {
+        (index << 9) | used as usize
+    }
+
+    fn total(used: u16) -> u16 {
+        Mark::iter()
+            .filter(|m| used & (1 << m.to_index()) != 0)
+            .map(|m| m.to_value())
+            .sum()
+    }
+
+    /// Returns the candidates of the cell that are not used yet
+    fn options(&self, index: usize, used: u16) -> impl Iterator<Item = Mark> {
+        let candidates = self.candidates[index];
+
+        candidates
+            .iter_possible()
+            .filter(move |m| used & (1 << m.to_index()) == 0)
+    }
+
+    /// Returns true if the remaining cells can be filled in to reach the sum
+    fn is_feasible(&mut self, index: usize, used: u16) -> bool {
+        let state = Self::state(index, used);
+        if let Some(result) = self.feasible[state] {
+            return result;
+        }
+
+        let total = Self::total(used);
+        let result = match index == self.candidates.len() {
+            true => total == self.sum,
+            false if total >= self.sum => false,
+            false => {
+                let options: Vec<Mark> = self.options(index, used).collect();
+                options
+                    .into_iter()
+                    .any(|m| self.is_feasible(index + 1, used | 1 << m.to_index()))
+            }
+        };
+
+        self.feasible[state] = Some(result);
+        result
+    }
+
+    /// Marks the values that are part of a feasible assignment, for every reachable state
+    fn collect_support(&mut self, index: usize, used: u16) {
+        let state = Self::state(index, used);
+        if index == self.candidates.len() || self.visited[state] {
+            return;
+        }
+        self.visited[state] = true;
+
+        let options: Vec<Mark> = self.options(index, used).collect();
+        for mark in options {
+            let used = used | 1 << mark.to_index();
+            if self.is_feasible(index + 1, used) {
+                self.supported[index].set_possible(mark);
+                self.collect_support(index + 1, used);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Cage;
+    use crate::{
+        constraints::constraint::Constraint,
+        grid::{coords::Coord, grid::Grid, mark::Mark},
+        solvers::solver::SolveResult,
+        test::util::general_tests,
+    };
+
+    #[test]
+    fn test_combinations() {
+        let grid = &mut Grid::new();
+        let small = Cage::new(3, vec![Coord::new(0, 0), Coord::new(0, 1)]).unwrap();
+        let large = Cage::new(
+            24,
+            vec![Coord::new(1, 0), Coord::new(1, 1), Coord::new(1, 2)],
+        )
+        .unwrap();
+
+        assert_eq!(small.propagate(grid), SolveResult::Updated);
+        assert_eq!(large.propagate(grid), SolveResult::Updated);
+
+        let marks: Vec<Mark> = grid.get_cell_at(Coord::new(0, 1)).iter_possible().collect();
+        assert_eq!(marks, vec![Mark::N1, Mark::N2]);
+        let marks: Vec<Mark> = grid.get_cell_at(Coord::new(1, 2)).iter_possible().collect();
+        assert_eq!(marks, vec![Mark::N7, Mark::N8, Mark::N9]);
+
+        // A placed 3 leaves a 7 for the other cell, which is in a different row
+        let pair = Cage::new(10, vec![Coord::new(2, 0), Coord::new(3, 0)]).unwrap();
+        grid.place_value_at(Coord::new(2, 0), 3);
+        assert_eq!(pair.propagate(grid), SolveResult::Updated);
+        assert_eq!(grid.get_cell_at(Coord::new(3, 0)).possible_count(), 1);
+        assert_eq!(pair.propagate(grid), SolveResult::Nothing);
+
+        grid.place_value_at(Coord::new(0, 0), 4);
+        assert_eq!(small.propagate(grid), SolveResult::Error);
+    }
+
+    #[test]
+    fn test_validate() {
+        let grid = general_tests::filled_sudoku();
+        let cells = vec![Coord::new(0, 0), Coord::new(0, 1), Coord::new(1, 0)];
+
+        assert!(Cage::new(13, cells.clone())
+            .unwrap()
+            .validate(&grid)
+            .is_ok());
+        assert!(Cage::new(14, cells.clone())
+            .unwrap()
+            .validate(&grid)
+            .is_err());
+        assert!(Cage::new(14, cells).unwrap().validate(&Grid::new()).is_ok());
+        assert!(Cage::new(5, vec![]).is_err());
+        assert!(Cage::new(5, vec![Coord::new(0, 0); 10]).is_err());
+    }
+}
@@ -0,0 +1,261 @@
+use std::error::Error;
+
+use crate::{
+    grid::{
+        cell_collection::CellCollection, constants::GRID_SIZE, coords::Coord, grid::Grid,
+        mark::Mark,
+    },
+    solvers::validation_report::{Conflict, Unit, ValidationReport},
+};
+
+use super::constraint::Constraint;
+
+/// The cells of each coord that are a knight's move away, computed once
+static KNIGHT_PEERS: [MovePeers; GRID_SIZE] = generate_move_peers(&KNIGHT_MOVES);
+/// The cells of each coord that are a king's move away, computed once
+static KING_PEERS: [MovePeers; GRID_SIZE] = generate_move_peers(&KING_MOVES);
+
+/// The row and column offsets of a chess knight's move
+const KNIGHT_MOVES: [(isize, isize); 8] = [
+    (-2, -1),
+    (-2, 1),
+    (-1, -2),
+    (-1, 2),
+    (1, -2),
+    (1, 2),
+    (2, -1),
+    (2, 1),
+];
+
+/// The row and column offsets of a chess king's move
+const KING_MOVES: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// Anti-knight, the same value can not be a chess knight's move apart
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AntiKnight;
+
+/// Anti-king, the same value can not be a chess king's move apart, so not even diagonally touching
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AntiKing;
+
+impl AntiKnight {
+    /// Returns the cells a knight's move away from the coord
+    pub fn peers(coord: Coord) -> &'static [Coord] {
+        KNIGHT_PEERS[coord.get_index()].as_slice()
+    }
+}
+
+impl AntiKing {
+    /// Returns the cells a king's move away from the coord
+    pub fn peers(coord: Coord) -> &'static [Coord] {
+        KING_PEERS[coord.get_index()].as_slice()
+    }
+}
+
+impl Constraint for AntiKnight {
+    fn name(&self) -> &'static str {
+        "Anti-Knight"
+    }
+
+    fn mark_off(&self, grid: &mut Grid, coord: Coord, value: u16) {
+        mark_off_peers(AntiKnight::peers(coord), grid, value);
+    }
+
+    fn validate(&self, grid: &Grid) -> Result<(), Box<dyn Error>> {
+        validate_peers(grid, AntiKnight::peers, "knight")
+    }
+
+    fn report(&self, grid: &Grid, report: &mut ValidationReport) {
+        report_peers(grid, AntiKnight::peers, Unit::KnightMove, report);
+    }
+}
+
+impl Constraint for AntiKing {
+    fn name(&self) -> &'static str {
+        "Anti-King"
+    }
+
+    fn mark_off(&self, grid: &mut Grid, coord: Coord, value: u16) {
+        mark_off_peers(AntiKing::peers(coord), grid, value);
+    }
+
+    fn validate(&self, grid: &Grid) -> Result<(), Box<dyn Error>> {
+        validate_peers(grid, AntiKing::peers, "king")
+    }
+
+    fn report(&self, grid: &Grid, report: &mut ValidationReport) {
+        report_peers(grid, AntiKing::peers, Unit::KingMove, report);
+    }
+}
+
+/// The cells a move away from one coord, only the first `len` are used
+#[derive(Debug, Clone, Copy)]
+struct MovePeers {
+    coords: [Coord; 8],
+    len: usize,
+}
+
+impl MovePeers {
+    fn as_slice(&self) -> &[Coord] {
+        &self.coords[..self.len]
+    }
+}
+
+fn mark_off_peers(peers: &[Coord], grid: &mut Grid, value: u16) {
+    let mark = Mark::from_value(value);
+
+    for peer in peers {
+        grid.unset_possible_at(*peer, mark);
+    }
+}
+
+fn validate_peers(
+    grid: &Grid,
+    peers: fn(Coord) -> &'static [Coord],
+    piece: &str,
+) -> Result<(), Box<dyn Error>> {
+    for index in grid.iter() {
+        let coord = Coord::from_index(index);
+        let value = match grid.get_cell(index).value() {
+            Some(v) => v,
+            None => continue,
+        };
+
+        for peer in peers(coord) {
+            if grid.get_cell_at(*peer).value() == Some(value) {
+                let msg = format!(
+                    "Same value {} at {} and {}, a {}'s move apart",
+                    value, coord, peer, piece
+                );
+                return Err(msg.into());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn report_peers(
+    grid: &Grid,
+    peers: fn(Coord) -> &'static [Coord],
+    unit: Unit,
+    report: &mut ValidationReport,
+) {
+    for index in grid.iter() {
+        let coord = Coord::from_index(index);
+        let value = match grid.get_cell(index).value() {
+            Some(v) => v,
+            None => continue,
+        };
+
+        // Each pair once, from the cell that comes first
+        for peer in peers(coord).iter().filter(|p| p.get_index() > index) {
+            if grid.get_cell_at(*peer).value() == Some(value) {
+                report.add(Conflict::Duplicate {
+                    value,
+                    first: coord,
+                    second: *peer,
+                    unit,
+                });
+            }
+        }
+    }
+}
+
+const fn generate_move_peers(moves: &[(isize, isize); 8]) -> [MovePeers; GRID_SIZE] {
+    let empty = MovePeers {
+        coords: [Coord::new(0, 0); 8],
+        len: 0,
+    };
+    let mut peers = [empty; GRID_SIZE];
+    let mut index = 0;
+
+    while index < GRID_SIZE {
+        let (row, col) = Coord::from_index(index).get_row_col();
+        let mut i = 0;
+
+        while i < moves.len() {
+            let (row_offset, col_offset) = moves[i];
+            let r = row as isize + row_offset;
+            let c = col as isize + col_offset;
+
+            if r >= 0 && r < 9 && c >= 0 && c < 9 {
+                let len = peers[index].len;
+                peers[index].coords[len] = Coord::new(r as usize, c as usize);
+                peers[index].len += 1;
+            }
+            i += 1;
+        }
+        index += 1;
+    }
+
+    peers
+}
+
+#[cfg(test)]
+mod test {
+    use super::{AntiKing, AntiKnight};
+    use crate::{
+        constraints::{constraint::Constraint, constraint_set::ConstraintSet},
+        grid::{cell::Cell, coords::Coord, grid::Grid, mark::Mark},
+        solvers::validation_report::{Conflict, Unit, ValidationReport},
+    };
+
+    #[test]
+    fn test_peers() {
+        assert_eq!(AntiKnight::peers(Coord::new(0, 0)).len(), 2);
+        assert_eq!(AntiKnight::peers(Coord::new(4, 4)).len(), 8);
+        assert_eq!(AntiKing::peers(Coord::new(0, 8)).len(), 3);
+        assert!(AntiKing::peers(Coord::new(4, 4)).contains(&Coord::new(5, 5)));
+        assert!(AntiKnight::peers(Coord::new(4, 4)).contains(&Coord::new(2, 3)));
+    }
+
+    #[test]
+    fn test_mark_off() {
+        let constraints = ConstraintSet::new().with(AntiKnight).with(AntiKing);
+        let grid = &mut Grid::new();
+
+        constraints.place_value_at(grid, Coord::new(4, 4), 7);
+
+        assert!(!grid.is_possible_at(Coord::new(2, 3), Mark::N7));
+        assert!(!grid.is_possible_at(Coord::new(6, 5), Mark::N7));
+        assert!(!grid.is_possible_at(Coord::new(5, 6), Mark::N7));
+        assert!(grid.is_possible_at(Coord::new(6, 6), Mark::N7));
+        assert!(grid.is_possible_at(Coord::new(2, 2), Mark::N7));
+    }
+
+    #[test]
+    fn test_validate() {
+        let grid = &mut Grid::new();
+        grid.set_cell_at(Coord::new(2, 2), &Cell::new_with_value(1));
+        grid.set_cell_at(Coord::new(3, 3), &Cell::new_with_value(1));
+        assert!(AntiKnight.validate(grid).is_ok());
+        assert!(AntiKing.validate(grid).is_err());
+
+        grid.set_cell_at(Coord::new(3, 3), &Cell::new());
+        grid.set_cell_at(Coord::new(3, 4), &Cell::new_with_value(1));
+        assert!(AntiKing.validate(grid).is_ok());
+        assert!(AntiKnight.validate(grid).is_err());
+
+        let report = &mut ValidationReport::new(Vec::new());
+        AntiKnight.report(grid, report);
+        assert_eq!(
+            report.conflicts(),
+            &[Conflict::Duplicate {
+                value: 1,
+                first: Coord::new(2, 2),
+                second: Coord::new(3, 4),
+                unit: Unit::KnightMove,
+            }]
+        );
+    }
+}
@@ -0,0 +1,35 @@
+use std::error::Error;
+
+use crate::{
+    grid::{board::Board, coords::Coord, grid::Grid},
+    solvers::{
+        solver::SolveResult,
+        validation_report::{Conflict, ValidationReport},
+    },
+};
+
+/// A rule that applies to a grid in addition to the rows, columns and squares, the standard grid unless specified
+pub trait Constraint<G: Board = Grid> {
+    fn name(&self) -> &'static str;
+
+    /// Removes the candidates that conflict with the value placed at the given coord
+    fn mark_off(&self, _grid: &mut G, _coord: Coord<G::Dimensions>, _value: u16) {}
+
+    /// Removes candidates or places values that follow from the constraint
+    fn propagate(&self, _grid: &mut G) -> SolveResult {
+        SolveResult::Nothing
+    }
+
+    /// Returns an error if the placed values break the constraint
+    fn validate(&self, grid: &G) -> Result<(), Box<dyn Error>>;
+
+    /// Adds the conflicts of the placed values to the report, by default the error of `validate`
+    fn report(&self, grid: &G, report: &mut ValidationReport<G::Dimensions>) {
+        if let Err(e) = self.validate(grid) {
+            report.add(Conflict::Constraint {
+                name: self.name().to_string(),
+                message: e.to_string(),
+            });
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use std::error::Error;
+
+use crate::{
+    grid::{
+        board::{Board, BoardCell},
+        coords::Coord,
+        grid::Grid,
+    },
+    solvers::solver::SolveResult,
+};
+
+use super::constraint::Constraint;
+
+/// The constraints that apply to a grid in addition to the rows, columns and squares
+pub struct ConstraintSet<G: Board = Grid> {
+    constraints: Vec<Box<dyn Constraint<G>>>,
+}
+
+impl ConstraintSet {
+    /// Creates a set without any constraints, which only leaves the standard rules
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<G: Board> Default for ConstraintSet<G> {
+    fn default() -> Self {
+        Self {
+            constraints: Vec::new(),
+        }
+    }
+}
+
+impl<G: Board> ConstraintSet<G> {
+    /// Adds a constraint to the set
+    pub fn add<C: Constraint<G> + 'static>(&mut self, constraint: C) {
+        self.constraints.push(Box::new(constraint));
+    }
+
+    /// Adds a constraint to the set and returns the set
+    pub fn with<C: Constraint<G> + 'static>(mut self, constraint: C) -> Self {
+        self.add(constraint);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.constraints.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.constraints.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &dyn Constraint<G>> {
+        self.constraints.iter().map(|c| c.as_ref())
+    }
+
+    /// Removes the candidates that conflict with the value placed at the given coord
+    pub fn mark_off(&self, grid: &mut G, coord: Coord<G::Dimensions>, value: u16) {
+        for constraint in self.constraints.iter() {
+            constraint.mark_off(grid, coord, value);
+        }
+    }
+
+    /// Places the value and removes the candidates that conflict with it, for both the standard rules and the constraints
+    pub fn place_value_at(&self, grid: &mut G, coord: Coord<G::Dimensions>, value: u16) {
+        grid.place_value_at(coord, value);
+        self.mark_off(grid, coord, value);
+    }
+
+    /// Marks off the candidates for every determined cell in the grid
+    pub fn mark_off_all(&self, grid: &mut G) {
+        if self.is_empty() {
+            return;
+        }
+
+        for index in grid.iter() {
+            if let Some(value) = grid.get_cell(index).value() {
+                self.mark_off(grid, Coord::at_index(index), value);
+            }
+        }
+    }
+
+    /// Marks off the determined cells and lets every constraint remove candidates or place values
+    pub fn propagate(&self, grid: &mut G) -> SolveResult {
+        if self.is_empty() {
+            return SolveResult::Nothing;
+        }
+
+        let before = grid.clone();
+        let mut result = SolveResult::Nothing;
+        self.mark_off_all(grid);
+
+        for constraint in self.constraints.iter() {
+            result |= constraint.propagate(grid);
+            if result == SolveResult::Error {
+                return result;
+            }
+        }
+
+        // Values placed by the constraints still have to be marked off
+        self.mark_off_all(grid);
+
+        result | SolveResult::from_changed(before != *grid)
+    }
+
+    /// Returns the first error of a constraint that is broken by the placed values
+    pub fn validate(&self, grid: &G) -> Result<(), Box<dyn Error>> {
+        for constraint in self.constraints.iter() {
+            constraint.validate(grid)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns true if none of the constraints are broken
+    pub fn is_valid(&self, grid: &G) -> bool {
+        self.validate(grid).is_ok()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ConstraintSet;
+    use crate::{
+        constraints::{all_different::AllDifferent, relation::Relation},
+        grid::{cell::Cell, coords::Coord, grid::Grid, mark::Mark},
+        solvers::{solver::SolveResult, solver_manager::SolverManager},
+        test::util::general_tests,
+    };
+
+    #[test]
+    fn test_propagate() {
+        let diagonal = AllDifferent::new((0..9).map(|i| Coord::new(i, i)).collect());
+        let pair = Relation::new("Differ", Coord::new(0, 1), Coord::new(8, 8), |a, b| a != b);
+        let set = ConstraintSet::new().with(diagonal).with(pair);
+        let grid = &mut Grid::new();
+
+        assert_eq!(set.len(), 2);
+        assert_eq!(set.propagate(grid), SolveResult::Nothing);
+
+        set.place_value_at(grid, Coord::new(0, 0), 4);
+        set.place_value_at(grid, Coord::new(0, 1), 6);
+        assert_eq!(set.propagate(grid), SolveResult::Nothing);
+
+        assert!(!grid.is_possible_at(Coord::new(5, 5), Mark::N4));
+        assert!(!grid.is_possible_at(Coord::new(8, 8), Mark::N6));
+        assert!(set.is_valid(grid));
+
+        grid.place_value_at(Coord::new(8, 8), 6);
+        assert!(set.validate(grid).is_err());
+    }
+
+    #[test]
+    fn test_solver_manager_follows_constraints() {
+        let grid = &mut general_tests::filled_sudoku();
+        grid.set_cell(0, &Cell::new());
+
+        let solver = SolverManager::new();
+        assert_eq!(solver.solve(*grid).result, SolveResult::Solved);
+
+        // The only value that fits is a 4, next to the 3 that is not allowed anymore
+        let pair = Relation::new("Sum", Coord::new(0, 0), Coord::new(0, 1), |a, b| a + b != 7);
+        let solver = SolverManager::new_with_constraints(ConstraintSet::new().with(pair));
+        let result = solver.solve(*grid);
+
+        assert_ne!(result.result, SolveResult::Solved);
+        assert!(!solver.is_valid(&general_tests::filled_sudoku()));
+    }
+}
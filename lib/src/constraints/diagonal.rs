@@ -0,0 +1,99 @@
+use std::error::Error;
+
+use crate::{
+    grid::{coords::Coord, diagonal::Diagonal, grid::Grid},
+    solvers::{
+        solver::SolveResult,
+        validation_report::{Unit, ValidationReport},
+        validator::report_area,
+    },
+};
+
+use super::{
+    all_different::{mark_off_area, place_hidden_singles, validate_distinct},
+    constraint::Constraint,
+    constraint_set::ConstraintSet,
+};
+
+/// Returns the constraints of Sudoku-X, where both main diagonals must contain 1 to 9
+pub fn sudoku_x() -> ConstraintSet {
+    ConstraintSet::new()
+        .with(Diagonal::main())
+        .with(Diagonal::anti())
+}
+
+impl Constraint for Diagonal {
+    fn name(&self) -> &'static str {
+        match self.is_anti() {
+            true => "Anti Diagonal",
+            false => "Diagonal",
+        }
+    }
+
+    fn mark_off(&self, grid: &mut Grid, coord: Coord, value: u16) {
+        if self.is_coord_in_diagonal(coord) {
+            mark_off_area(self, grid, coord, value);
+        }
+    }
+
+    fn propagate(&self, grid: &mut Grid) -> SolveResult {
+        place_hidden_singles(self, grid)
+    }
+
+    fn validate(&self, grid: &Grid) -> Result<(), Box<dyn Error>> {
+        validate_distinct(self, grid)
+    }
+
+    fn report(&self, grid: &Grid, report: &mut ValidationReport) {
+        let unit = match self.is_anti() {
+            true => Unit::AntiDiagonal,
+            false => Unit::Diagonal,
+        };
+        report_area(grid, *self, unit, report);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::sudoku_x;
+    use crate::{
+        grid::{
+            cell::Cell, cell_collection::CellCollection, coords::Coord, diagonal::Diagonal,
+            grid::Grid, mark::Mark,
+        },
+        solvers::{
+            validation_report::{Conflict, Unit},
+            validator::{is_valid_with, validation_report_with},
+        },
+    };
+
+    #[test]
+    fn test_mark_off() {
+        let constraints = sudoku_x();
+        let grid = &mut Grid::new();
+        grid.set_cell_at(Coord::new(2, 2), &Cell::new_with_value(5));
+
+        constraints.mark_off_all(grid);
+
+        assert_eq!(Diagonal::main().count_possible(grid, Mark::N5), 0);
+        assert!(Diagonal::anti().count_possible(grid, Mark::N5) > 0);
+        assert!(grid.is_possible_at(Coord::new(3, 4), Mark::N5));
+    }
+
+    #[test]
+    fn test_report() {
+        let constraints = sudoku_x();
+        let grid = &mut Grid::new();
+        grid.place_value_at(Coord::new(0, 8), 1);
+        grid.place_value_at(Coord::new(4, 4), 1);
+
+        assert!(!is_valid_with(grid, &constraints));
+        let report = validation_report_with(grid, &constraints);
+        assert!(report.conflicts().contains(&Conflict::Duplicate {
+            value: 1,
+            first: Coord::new(0, 8),
+            second: Coord::new(4, 4),
+            unit: Unit::AntiDiagonal,
+        }));
+    }
+}
@@ -0,0 +1,102 @@
+use std::error::Error;
+
+use crate::{
+    grid::{coords::Coord, grid::Grid},
+    solvers::solver::SolveResult,
+};
+
+use super::{
+    constraint::Constraint,
+    util::{mark_off_pair, reduce_pair, validate_pair},
+};
+
+/// A greater than sign between two neighboring cells, as used in futoshiki
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GreaterThan {
+    greater: Coord,
+    smaller: Coord,
+}
+
+impl GreaterThan {
+    pub fn new(greater: Coord, smaller: Coord) -> Self {
+        debug_assert!(
+            super::util::is_orthogonal(greater, smaller),
+            "a sign sits between two neighboring cells"
+        );
+
+        Self { greater, smaller }
+    }
+
+    pub fn greater(&self) -> Coord {
+        self.greater
+    }
+
+    pub fn smaller(&self) -> Coord {
+        self.smaller
+    }
+}
+
+impl Constraint for GreaterThan {
+    fn name(&self) -> &'static str {
+        "Greater Than"
+    }
+
+    fn mark_off(&self, grid: &mut Grid, coord: Coord, value: u16) {
+        mark_off_pair(grid, coord, value, self.greater, self.smaller, is_greater);
+    }
+
+    fn propagate(&self, grid: &mut Grid) -> SolveResult {
+        reduce_pair(grid, self.greater, self.smaller, is_greater)
+    }
+
+    fn validate(&self, grid: &Grid) -> Result<(), Box<dyn Error>> {
+        validate_pair(grid, self.greater, self.smaller, is_greater, self.name())
+    }
+}
+
+fn is_greater(a: u16, b: u16) -> bool {
+    a > b
+}
+
+#[cfg(test)]
+mod test {
+    use super::GreaterThan;
+    use crate::{
+        constraints::constraint::Constraint,
+        grid::{coords::Coord, grid::Grid, mark::Mark},
+        solvers::solver::SolveResult,
+        test::util::general_tests,
+    };
+
+    #[test]
+    fn test_propagate() {
+        let sign = GreaterThan::new(Coord::new(0, 0), Coord::new(0, 1));
+        let grid = &mut Grid::new();
+
+        assert_eq!(sign.propagate(grid), SolveResult::Updated);
+        assert!(!grid.is_possible_at(Coord::new(0, 0), Mark::N1));
+        assert!(!grid.is_possible_at(Coord::new(0, 1), Mark::N9));
+
+        sign.mark_off(grid, Coord::new(0, 0), 3);
+        let marks: Vec<Mark> = grid.get_cell_at(Coord::new(0, 1)).iter_possible().collect();
+        assert_eq!(marks, vec![Mark::N1, Mark::N2]);
+
+        grid.place_value_at(Coord::new(0, 1), 8);
+        grid.place_value_at(Coord::new(0, 0), 2);
+        assert_eq!(sign.propagate(grid), SolveResult::Error);
+    }
+
+    #[test]
+    fn test_validate() {
+        // The first row is 4 3 5 2 6 9 7 8 1
+        let grid = general_tests::filled_sudoku();
+
+        assert!(GreaterThan::new(Coord::new(0, 0), Coord::new(0, 1))
+            .validate(&grid)
+            .is_ok());
+        assert!(GreaterThan::new(Coord::new(0, 1), Coord::new(0, 2))
+            .validate(&grid)
+            .is_err());
+    }
+}
@@ -0,0 +1,282 @@
+use std::{error::Error, str::FromStr};
+
+use crate::{
+    grid::{
+        constants::{GRID_HEIGHT, GRID_WIDTH},
+        coords::Coord,
+        grid::Grid,
+        parse_error::{ParseError, ParseErrorKind},
+    },
+    solvers::solver::SolveResult,
+};
+
+use super::{
+    cage::{eliminate_combinations, Cage},
+    constraint::Constraint,
+};
+
+/// The sum of the values of every row, column and square
+const HOUSE_SUM: i32 = 45;
+
+/// The cages of a killer sudoku.
+///
+/// Next to the combinations of each cage, the rule of 45 is used on the rows, columns and squares:
+/// the cells of a house that are not covered by cages inside of it (innies) add up to what is left of 45,
+/// and the cells of the cages sticking out of a house (outies) add up to what the cages have too much.
+///
+/// Cages are parsed from lines of a sum followed by the cells in rXcY notation, lines starting with `#` are skipped:
+///
+/// ```text
+/// 3: r1c1 r1c2
+/// 15: r1c3 r2c3 r3c3
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Killer {
+    cages: Vec<Cage>,
+}
+
+impl Killer {
+    pub fn new(cages: Vec<Cage>) -> Self {
+        Self { cages }
+    }
+
+    pub fn cages(&self) -> &[Cage] {
+        &self.cages
+    }
+
+    /// Returns the cage the coord is in
+    pub fn cage_at(&self, coord: Coord) -> Option<&Cage> {
+        self.cages.iter().find(|c| c.contains(coord))
+    }
+
+    /// Applies the rule of 45 to a single house
+    fn innies_outies(&self, grid: &mut Grid, house: &[Coord]) -> SolveResult {
+        let mut inside_sum = 0;
+        let mut touching_sum = 0;
+        let mut innies: Vec<Coord> = house.to_vec();
+        let mut outies: Vec<Coord> = Vec::new();
+        let mut covered = 0;
+
+        for cage in self.cages.iter() {
+            let overlap = cage.cells().iter().filter(|c| house.contains(c)).count();
+            if overlap == 0 {
+                continue;
+            }
+
+            covered += overlap;
+            touching_sum += cage.sum() as i32;
+            if overlap == cage.cells().len() {
+                inside_sum += cage.sum() as i32;
+                innies.retain(|c| !cage.contains(*c));
+            } else {
+                outies.extend(cage.cells().iter().filter(|c| !house.contains(c)));
+            }
+        }
+
+        let mut result = SolveResult::Nothing;
+        if !innies.is_empty() && innies.len() < house.len() {
+            result |= eliminate_sum(grid, &innies, HOUSE_SUM - inside_sum);
+        }
+
+        // The outies only add up when every cell of the house is in a cage
+        if covered == house.len() && !outies.is_empty() && is_one_house(grid, &outies) {
+            result |= eliminate_sum(grid, &outies, touching_sum - HOUSE_SUM);
+        }
+
+        result
+    }
+
+    /// Returns the rows, columns and regions of the grid
+    fn houses(grid: &Grid) -> Vec<Vec<Coord>> {
+        let rows = (0..GRID_HEIGHT).map(|row| (0..GRID_WIDTH).map(move |col| Coord::new(row, col)));
+        let columns =
+            (0..GRID_WIDTH).map(|col| (0..GRID_HEIGHT).map(move |row| Coord::new(row, col)));
+        let mut result: Vec<Vec<Coord>> = rows.map(|r| r.collect()).collect();
+
+        result.extend(columns.map(|c| c.collect()));
+        result.extend(grid.regions().iter().map(|r| r.coords().to_vec()));
+        result
+    }
+}
+
+impl Constraint for Killer {
+    fn name(&self) -> &'static str {
+        "Killer"
+    }
+
+    fn mark_off(&self, grid: &mut Grid, coord: Coord, value: u16) {
+        if let Some(cage) = self.cage_at(coord) {
+            cage.mark_off(grid, coord, value);
+        }
+    }
+
+    fn propagate(&self, grid: &mut Grid) -> SolveResult {
+        let mut result = SolveResult::Nothing;
+
+        for cage in self.cages.iter() {
+            result |= cage.propagate(grid);
+            if result == SolveResult::Error {
+                return result;
+            }
+        }
+
+        for house in Killer::houses(grid) {
+            result |= self.innies_outies(grid, &house);
+            if result == SolveResult::Error {
+                return result;
+            }
+        }
+
+        result
+    }
+
+    fn validate(&self, grid: &Grid) -> Result<(), Box<dyn Error>> {
+        for cage in self.cages.iter() {
+            cage.validate(grid)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Killer {
+    type Err = ParseError;
+
+    /// Parses the cages, one per line as a sum followed by the cells
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cages: Vec<Cage> = Vec::new();
+
+        for (line_index, line) in s.lines().enumerate() {
+            let line_number = line_index + 1;
+            let content = line.trim();
+            if content.is_empty() || content.starts_with('#') {
+                continue;
+            }
+
+            let column_of = |part: &str| part.as_ptr() as usize - line.as_ptr() as usize + 1;
+            let malformed = |part: &str, reason: &str| {
+                ParseError::new(
+                    line_number,
+                    column_of(part),
+                    ParseErrorKind::Malformed(String::from(reason)),
+                )
+            };
+
+            let (sum_text, cells_text) = match content.split_once(':') {
+                Some(parts) => parts,
+                None => return Err(malformed(content, "expected a sum followed by ':'")),
+            };
+            let sum: u16 = match sum_text.trim().parse() {
+                Ok(sum) if sum > 0 && sum <= 45 => sum,
+                _ => return Err(malformed(sum_text, "expected a sum from 1 to 45")),
+            };
+
+            let mut cells = Vec::new();
+            for part in cells_text.split_whitespace() {
+                let coord =
+                    parse_cell(part).ok_or_else(|| malformed(part, "expected a cell like r1c1"))?;
+                if cells.contains(&coord) || cages.iter().any(|c| c.contains(coord)) {
+                    return Err(malformed(part, "cell is already in a cage"));
+                }
+                cells.push(coord);
+            }
+            let cage = Cage::new(sum, cells).map_err(|e| malformed(content, &e.to_string()))?;
+
+            cages.push(cage);
+        }
+
+        Ok(Killer::new(cages))
+    }
+}
+
+/// Parses a cell in rXcY notation, rows and columns starting at 1
+fn parse_cell(text: &str) -> Option<Coord> {
+    let lower = text.to_ascii_lowercase();
+    let (row, col) = lower.strip_prefix('r')?.split_once('c')?;
+    let (row, col): (usize, usize) = (row.parse().ok()?, col.parse().ok()?);
+
+    let valid = (1..=GRID_HEIGHT).contains(&row) && (1..=GRID_WIDTH).contains(&col);
+    valid.then(|| Coord::new(row - 1, col - 1))
+}
+
+/// Restricts the cells to the combinations that add up to the sum, an impossible sum is an error
+fn eliminate_sum(grid: &mut Grid, cells: &[Coord], sum: i32) -> SolveResult {
+    if sum <= 0 || sum > HOUSE_SUM {
+        return SolveResult::Error;
+    }
+
+    eliminate_combinations(grid, cells, sum as u16)
+}
+
+/// Returns true if the cells share a row, column or region, so they all hold different values
+fn is_one_house(grid: &Grid, cells: &[Coord]) -> bool {
+    let first = cells[0];
+    let region = grid.get_region_at(first);
+
+    cells.iter().all(|c| c.get_row() == first.get_row())
+        || cells.iter().all(|c| c.get_col() == first.get_col())
+        || cells.iter().all(|c| region.contains(*c))
+}
+
+#[cfg(test)]
+mod test {
+    use super::Killer;
+    use crate::{
+        constraints::{constraint::Constraint, constraint_set::ConstraintSet},
+        grid::{coords::Coord, grid::Grid, mark::Mark, parse_error::ParseErrorKind},
+        solvers::{solution_counter::SolutionCounter, validator::validate_grid_with},
+        test::util::general_tests,
+    };
+
+    /// The first row of the filled sudoku is 4 3 5 2 6 9 7 8 1, the last column ends with 1 3
+    const CAGES: &str = "# First row
+        7: r1c1 r1c2
+        7: r1c3 r1c4
+        15: r1c5 r1c6
+        15: r1c7 r1c8
+        4: r1c9 r2c9";
+
+    #[test]
+    fn test_parse() {
+        let killer: Killer = CAGES.parse().unwrap();
+
+        assert_eq!(killer.cages().len(), 5);
+        assert_eq!(killer.cages()[4].sum(), 4);
+        assert_eq!(killer.cage_at(Coord::new(1, 8)), Some(&killer.cages()[4]));
+        assert!(killer.validate(&general_tests::filled_sudoku()).is_ok());
+
+        let error = "7: r1c1 r1x2".parse::<Killer>().unwrap_err();
+        assert_eq!((error.line, error.column), (1, 9));
+        assert!(matches!(error.kind, ParseErrorKind::Malformed(_)));
+
+        let error = "7: r1c1 r1c2\n3: r1c2 r1c3".parse::<Killer>().unwrap_err();
+        assert_eq!((error.line, error.column), (2, 4));
+        assert!("r1c1 r1c2".parse::<Killer>().is_err());
+    }
+
+    #[test]
+    fn test_innies_outies() {
+        let killer: Killer = CAGES.parse().unwrap();
+        let grid = &mut Grid::new();
+
+        killer.propagate(grid);
+
+        // Innie: the first row has 44 in cages, leaving a 1 for the last cell
+        let marks: Vec<Mark> = grid.get_cell_at(Coord::new(0, 8)).iter_possible().collect();
+        assert_eq!(marks, vec![Mark::N1]);
+
+        // Outie: the cages touching the first row add up to 48, 3 more than the row
+        let marks: Vec<Mark> = grid.get_cell_at(Coord::new(1, 8)).iter_possible().collect();
+        assert_eq!(marks, vec![Mark::N3]);
+    }
+
+    #[test]
+    fn test_solve_with_cages() {
+        let killer: Killer = CAGES.parse().unwrap();
+        let constraints = ConstraintSet::new().with(killer);
+        let solution = &SolutionCounter::find_with(&Grid::new(), &constraints, 1)[0];
+
+        assert!(validate_grid_with(solution, &constraints).is_ok());
+        assert_eq!(solution.get_cell_at(Coord::new(0, 8)).value(), Some(1));
+    }
+}
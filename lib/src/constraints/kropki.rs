@@ -0,0 +1,241 @@
+use std::error::Error;
+
+use crate::{
+    grid::{coords::Coord, grid::Grid},
+    solvers::solver::SolveResult,
+};
+
+use super::{
+    constraint::Constraint,
+    util::{
+        edge_index, iter_orthogonal_pairs, mark_off_pair, orthogonal_neighbors, reduce_pair,
+        validate_pair, EDGE_COUNT,
+    },
+};
+
+/// The kind of a Kropki dot between two neighboring cells
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DotKind {
+    /// The values are consecutive, such as 4 and 5
+    White,
+    /// One value is double the other, such as 3 and 6
+    Black,
+}
+
+impl DotKind {
+    /// Returns the rule between the two values
+    pub fn rule(self) -> fn(u16, u16) -> bool {
+        match self {
+            DotKind::White => is_consecutive,
+            DotKind::Black => is_double,
+        }
+    }
+
+    pub fn allows(self, a: u16, b: u16) -> bool {
+        (self.rule())(a, b)
+    }
+}
+
+/// A dot on the edge between two orthogonally neighboring cells
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Dot {
+    pub a: Coord,
+    pub b: Coord,
+    pub kind: DotKind,
+}
+
+impl Dot {
+    pub fn new(kind: DotKind, a: Coord, b: Coord) -> Self {
+        debug_assert!(
+            super::util::is_orthogonal(a, b),
+            "a dot sits between two neighboring cells"
+        );
+
+        Self { a, b, kind }
+    }
+
+    pub fn is_between(&self, a: Coord, b: Coord) -> bool {
+        (self.a == a && self.b == b) || (self.a == b && self.b == a)
+    }
+}
+
+/// The Kropki dots of a puzzle.
+///
+/// With the negative constraint every pair of neighbors without a dot is neither consecutive nor double.
+/// Dots between cells that are not neighbors are ignored.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Kropki {
+    dots: Vec<Dot>,
+    /// The kinds of the dots on each edge, by the index of the edge
+    edges: Vec<Vec<DotKind>>,
+    negative: bool,
+}
+
+impl Kropki {
+    pub fn new(dots: Vec<Dot>) -> Self {
+        let mut edges = vec![Vec::new(); EDGE_COUNT];
+        for dot in dots.iter() {
+            if let Some(index) = edge_index(dot.a, dot.b) {
+                edges[index].push(dot.kind);
+            }
+        }
+
+        Self {
+            dots,
+            edges,
+            negative: false,
+        }
+    }
+
+    /// Enables the negative constraint, all dots are given
+    pub fn with_negative(mut self) -> Self {
+        self.negative = true;
+        self
+    }
+
+    pub fn dots(&self) -> &[Dot] {
+        &self.dots
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// Returns the rules that apply between the two cells, from their dots or the negative constraint
+    fn rules_between(&self, a: Coord, b: Coord) -> impl Iterator<Item = fn(u16, u16) -> bool> + '_ {
+        let kinds = edge_index(a, b)
+            .and_then(|index| self.edges.get(index))
+            .map_or(&[][..], |kinds| kinds.as_slice());
+        let neither =
+            (kinds.is_empty() && self.negative).then_some(is_neither as fn(u16, u16) -> bool);
+
+        kinds.iter().map(|kind| kind.rule()).chain(neither)
+    }
+
+    /// Returns the pairs of cells with the rule that applies between them
+    fn iter_rules(&self) -> impl Iterator<Item = (Coord, Coord, fn(u16, u16) -> bool)> + '_ {
+        iter_orthogonal_pairs()
+            .flat_map(move |(a, b)| self.rules_between(a, b).map(move |rule| (a, b, rule)))
+    }
+}
+
+impl Constraint for Kropki {
+    fn name(&self) -> &'static str {
+        "Kropki"
+    }
+
+    fn mark_off(&self, grid: &mut Grid, coord: Coord, value: u16) {
+        for other in orthogonal_neighbors(coord) {
+            for rule in self.rules_between(coord, other) {
+                mark_off_pair(grid, coord, value, coord, other, rule);
+            }
+        }
+    }
+
+    fn propagate(&self, grid: &mut Grid) -> SolveResult {
+        let mut result = SolveResult::Nothing;
+
+        for (a, b, rule) in self.iter_rules() {
+            result |= reduce_pair(grid, a, b, rule);
+            if result == SolveResult::Error {
+                return result;
+            }
+        }
+
+        result
+    }
+
+    fn validate(&self, grid: &Grid) -> Result<(), Box<dyn Error>> {
+        for (a, b, rule) in self.iter_rules() {
+            validate_pair(grid, a, b, rule, "Kropki dot")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns true if the values differ by one
+pub fn is_consecutive(a: u16, b: u16) -> bool {
+    a.abs_diff(b) == 1
+}
+
+fn is_double(a: u16, b: u16) -> bool {
+    a == b * 2 || b == a * 2
+}
+
+fn is_neither(a: u16, b: u16) -> bool {
+    !is_consecutive(a, b) && !is_double(a, b)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Dot, DotKind, Kropki};
+    use crate::{
+        constraints::{
+            constraint::Constraint,
+            constraint_set::ConstraintSet,
+            util::{edge_index, iter_orthogonal_pairs, orthogonal_neighbors, EDGE_COUNT},
+        },
+        grid::{coords::Coord, grid::Grid, mark::Mark},
+        solvers::solver::SolveResult,
+    };
+
+    fn marks(grid: &Grid, coord: Coord) -> Vec<Mark> {
+        grid.get_cell_at(coord).iter_possible().collect()
+    }
+
+    #[test]
+    fn test_dots() {
+        let kropki = Kropki::new(vec![
+            Dot::new(DotKind::White, Coord::new(0, 0), Coord::new(0, 1)),
+            Dot::new(DotKind::Black, Coord::new(1, 0), Coord::new(1, 1)),
+        ]);
+        let set = ConstraintSet::new().with(kropki);
+        let grid = &mut Grid::new();
+
+        // A black dot can never hold a 5, 7 or 9
+        assert_eq!(set.propagate(grid), SolveResult::Updated);
+        assert!(!grid.is_possible_at(Coord::new(1, 1), Mark::N7));
+
+        set.place_value_at(grid, Coord::new(0, 0), 5);
+        set.place_value_at(grid, Coord::new(1, 0), 3);
+
+        assert_eq!(marks(grid, Coord::new(0, 1)), vec![Mark::N4, Mark::N6]);
+        assert_eq!(marks(grid, Coord::new(1, 1)), vec![Mark::N6]);
+
+        grid.place_value_at(Coord::new(0, 1), 7);
+        assert!(set.validate(grid).is_err());
+    }
+
+    #[test]
+    fn test_negative() {
+        let dot = Dot::new(DotKind::White, Coord::new(0, 0), Coord::new(0, 1));
+        let kropki = Kropki::new(vec![dot]).with_negative();
+        let grid = &mut Grid::new();
+
+        kropki.mark_off(grid, Coord::new(0, 1), 4);
+
+        // No dot between the 4 and the cell below it, so no 2, 3, 5 or 8
+        let below = marks(grid, Coord::new(1, 1));
+        assert_eq!(
+            below,
+            vec![Mark::N1, Mark::N4, Mark::N6, Mark::N7, Mark::N9]
+        );
+        assert_eq!(marks(grid, Coord::new(0, 0)), vec![Mark::N3, Mark::N5]);
+    }
+
+    #[test]
+    fn test_edges() {
+        for (index, (a, b)) in iter_orthogonal_pairs().enumerate() {
+            assert_eq!(edge_index(a, b), Some(index));
+            assert_eq!(edge_index(b, a), Some(index));
+            assert!(orthogonal_neighbors(a).any(|n| n == b));
+        }
+
+        assert_eq!(iter_orthogonal_pairs().count(), EDGE_COUNT);
+        assert_eq!(edge_index(Coord::new(0, 0), Coord::new(1, 1)), None);
+        assert_eq!(orthogonal_neighbors(Coord::new(0, 0)).count(), 2);
+        assert_eq!(orthogonal_neighbors(Coord::new(4, 8)).count(), 3);
+        assert_eq!(orthogonal_neighbors(Coord::new(4, 4)).count(), 4);
+    }
+}
@@ -0,0 +1,17 @@
+pub mod all_different;
+pub mod arrow;
+pub mod cage;
+pub mod chess_move;
+pub mod constraint;
+pub mod constraint_set;
+pub mod diagonal;
+pub mod inequality;
+pub mod killer;
+pub mod kropki;
+pub mod non_consecutive;
+pub mod parity;
+pub mod relation;
+pub mod sandwich;
+pub mod thermometer;
+pub mod util;
+pub mod window;
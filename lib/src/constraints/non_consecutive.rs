@@ -0,0 +1,85 @@
+use std::error::Error;
+
+use crate::{
+    grid::{coords::Coord, grid::Grid},
+    solvers::solver::SolveResult,
+};
+
+use super::{
+    constraint::Constraint,
+    kropki::is_consecutive,
+    util::{
+        iter_orthogonal_pairs, mark_off_pair, orthogonal_neighbors, reduce_pair, validate_pair,
+    },
+};
+
+/// No two orthogonally neighboring cells can hold consecutive values
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct NonConsecutive {}
+
+impl NonConsecutive {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Constraint for NonConsecutive {
+    fn name(&self) -> &'static str {
+        "Non Consecutive"
+    }
+
+    fn mark_off(&self, grid: &mut Grid, coord: Coord, value: u16) {
+        for other in orthogonal_neighbors(coord) {
+            mark_off_pair(grid, coord, value, coord, other, is_not_consecutive);
+        }
+    }
+
+    fn propagate(&self, grid: &mut Grid) -> SolveResult {
+        let mut result = SolveResult::Nothing;
+
+        for (a, b) in iter_orthogonal_pairs() {
+            result |= reduce_pair(grid, a, b, is_not_consecutive);
+            if result == SolveResult::Error {
+                return result;
+            }
+        }
+
+        result
+    }
+
+    fn validate(&self, grid: &Grid) -> Result<(), Box<dyn Error>> {
+        for (a, b) in iter_orthogonal_pairs() {
+            validate_pair(grid, a, b, is_not_consecutive, "Non consecutive")?;
+        }
+
+        Ok(())
+    }
+}
+
+fn is_not_consecutive(a: u16, b: u16) -> bool {
+    !is_consecutive(a, b)
+}
+
+#[cfg(test)]
+mod test {
+    use super::NonConsecutive;
+    use crate::{
+        constraints::constraint_set::ConstraintSet,
+        grid::{coords::Coord, grid::Grid, mark::Mark},
+        solvers::{solution_counter::SolutionCounter, validator::validate_grid_with},
+    };
+
+    #[test]
+    fn test_non_consecutive() {
+        let constraints = ConstraintSet::new().with(NonConsecutive::new());
+        let grid = &mut Grid::new();
+
+        constraints.place_value_at(grid, Coord::new(4, 4), 5);
+        assert!(!grid.is_possible_at(Coord::new(4, 5), Mark::N6));
+        assert!(!grid.is_possible_at(Coord::new(3, 4), Mark::N4));
+        assert!(grid.is_possible_at(Coord::new(3, 3), Mark::N4));
+
+        let solution = &SolutionCounter::find_with(&Grid::new(), &constraints, 1)[0];
+        assert!(validate_grid_with(solution, &constraints).is_ok());
+    }
+}
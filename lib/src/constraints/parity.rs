@@ -0,0 +1,165 @@
+use std::error::Error;
+
+use crate::{
+    grid::{cell::Cell, coords::Coord, grid::Grid},
+    solvers::solver::SolveResult,
+};
+
+use super::{
+    constraint::Constraint,
+    util::{candidates, keep_candidates},
+};
+
+/// Whether a cell must hold an even or an odd value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Parity {
+    Even,
+    Odd,
+}
+
+impl Parity {
+    /// Returns the candidates that match the parity
+    pub const fn mask(self) -> Cell {
+        match self {
+            Parity::Even => Cell::new_even(),
+            Parity::Odd => Cell::new_odd(),
+        }
+    }
+
+    pub fn allows(self, value: u16) -> bool {
+        match self {
+            Parity::Even => value.is_multiple_of(2),
+            Parity::Odd => !value.is_multiple_of(2),
+        }
+    }
+}
+
+/// Cells that are marked as even or odd.
+///
+/// The marks are masks over the candidates of the cells, applied before and during solving.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EvenOdd {
+    cells: Vec<(Coord, Parity)>,
+}
+
+impl EvenOdd {
+    pub fn new(cells: Vec<(Coord, Parity)>) -> Self {
+        Self { cells }
+    }
+
+    pub fn cells(&self) -> &[(Coord, Parity)] {
+        &self.cells
+    }
+
+    /// Returns the parity the cell is marked with
+    pub fn parity_at(&self, coord: Coord) -> Option<Parity> {
+        self.cells
+            .iter()
+            .find(|(c, _)| *c == coord)
+            .map(|(_, p)| *p)
+    }
+
+    /// Masks the candidates of the marked cells, returns an error if a cell has no candidates left
+    pub fn apply(&self, grid: &mut Grid) -> SolveResult {
+        let mut changed = false;
+
+        for (coord, parity) in self.cells.iter() {
+            changed |= keep_candidates(grid, *coord, parity.mask());
+            if candidates(grid, *coord).is_empty() {
+                return SolveResult::Error;
+            }
+        }
+
+        SolveResult::from_changed(changed)
+    }
+}
+
+impl Constraint for EvenOdd {
+    fn name(&self) -> &'static str {
+        "Even Odd"
+    }
+
+    fn propagate(&self, grid: &mut Grid) -> SolveResult {
+        self.apply(grid)
+    }
+
+    fn validate(&self, grid: &Grid) -> Result<(), Box<dyn Error>> {
+        for (coord, parity) in self.cells.iter() {
+            match grid.get_cell_at(*coord).value() {
+                Some(value) if !parity.allows(value) => {
+                    let msg = format!("{} at {} is not {:?}", value, coord, parity);
+                    return Err(msg)?;
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{EvenOdd, Parity};
+    use crate::{
+        constraints::{constraint::Constraint, constraint_set::ConstraintSet},
+        grid::{coords::Coord, grid::Grid, mark::Mark},
+        solvers::{solution_counter::SolutionCounter, solver::SolveResult},
+        test::util::general_tests,
+    };
+
+    #[test]
+    fn test_apply() {
+        let even_odd = EvenOdd::new(vec![
+            (Coord::new(0, 0), Parity::Even),
+            (Coord::new(0, 1), Parity::Odd),
+        ]);
+        let grid = &mut Grid::new();
+
+        assert_eq!(even_odd.apply(grid), SolveResult::Updated);
+        assert_eq!(even_odd.apply(grid), SolveResult::Nothing);
+        let marks: Vec<Mark> = grid.get_cell_at(Coord::new(0, 0)).iter_possible().collect();
+        assert_eq!(marks, vec![Mark::N2, Mark::N4, Mark::N6, Mark::N8]);
+        assert_eq!(even_odd.parity_at(Coord::new(0, 1)), Some(Parity::Odd));
+
+        for value in [2, 4, 6, 8] {
+            grid.unset_possible_at(Coord::new(0, 0), Mark::from_value(value));
+        }
+        assert_eq!(even_odd.apply(grid), SolveResult::Error);
+    }
+
+    #[test]
+    fn test_validate() {
+        // The first row is 4 3 5 2 6 9 7 8 1
+        let grid = general_tests::filled_sudoku();
+        let matching = EvenOdd::new(vec![
+            (Coord::new(0, 0), Parity::Even),
+            (Coord::new(0, 1), Parity::Odd),
+        ]);
+
+        assert!(matching.validate(&grid).is_ok());
+        assert!(EvenOdd::new(vec![(Coord::new(0, 2), Parity::Even)])
+            .validate(&grid)
+            .is_err());
+    }
+
+    #[test]
+    fn test_solve_with_parity() {
+        // Every cell of the first row odd, except for the last four
+        let cells = (0..9)
+            .map(|col| {
+                (
+                    Coord::new(0, col),
+                    if col < 5 { Parity::Odd } else { Parity::Even },
+                )
+            })
+            .collect();
+        let constraints = ConstraintSet::new().with(EvenOdd::new(cells));
+        let solution = &SolutionCounter::find_with(&Grid::new(), &constraints, 1)[0];
+
+        assert!(constraints.is_valid(solution));
+        assert_eq!(solution.get_cell_at(Coord::new(0, 8)).get_value() % 2, 0);
+    }
+}
@@ -0,0 +1,102 @@
+use std::error::Error;
+
+use crate::{
+    grid::{coords::Coord, grid::Grid},
+    solvers::solver::SolveResult,
+};
+
+use super::{
+    constraint::Constraint,
+    util::{mark_off_pair, reduce_pair, validate_pair},
+};
+
+/// A rule between the values of two cells, such as "differ by one" or "the first is larger"
+#[derive(Debug, Clone, Copy)]
+pub struct Relation {
+    pub a: Coord,
+    pub b: Coord,
+    name: &'static str,
+    /// Returns true if the value of `a` and the value of `b` can be placed together
+    allowed: fn(u16, u16) -> bool,
+}
+
+impl Relation {
+    pub fn new(name: &'static str, a: Coord, b: Coord, allowed: fn(u16, u16) -> bool) -> Self {
+        Self {
+            a,
+            b,
+            name,
+            allowed,
+        }
+    }
+
+    /// Returns true if the values can be placed in `a` and `b`
+    pub fn allows(&self, a: u16, b: u16) -> bool {
+        (self.allowed)(a, b)
+    }
+}
+
+impl Constraint for Relation {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn mark_off(&self, grid: &mut Grid, coord: Coord, value: u16) {
+        mark_off_pair(grid, coord, value, self.a, self.b, self.allowed);
+    }
+
+    fn propagate(&self, grid: &mut Grid) -> SolveResult {
+        reduce_pair(grid, self.a, self.b, self.allowed)
+    }
+
+    fn validate(&self, grid: &Grid) -> Result<(), Box<dyn Error>> {
+        validate_pair(grid, self.a, self.b, self.allowed, self.name)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Relation;
+    use crate::{
+        constraints::constraint::Constraint,
+        grid::{coords::Coord, grid::Grid, mark::Mark},
+        solvers::solver::SolveResult,
+    };
+
+    #[test]
+    fn test_greater_than() {
+        let a = Coord::new(0, 0);
+        let b = Coord::new(0, 1);
+        let relation = Relation::new("Greater Than", a, b, |a, b| a > b);
+        let grid = &mut Grid::new();
+
+        assert_eq!(relation.propagate(grid), SolveResult::Updated);
+        assert!(!grid.is_possible_at(a, Mark::N1));
+        assert!(!grid.is_possible_at(b, Mark::N9));
+        assert_eq!(relation.propagate(grid), SolveResult::Nothing);
+
+        grid.place_value_at(b, 7);
+        relation.mark_off(grid, b, 7);
+        assert_eq!(grid.get_cell_at(a).possible_count(), 2);
+
+        grid.place_value_at(a, 3);
+        assert!(relation.validate(grid).is_err());
+    }
+
+    #[test]
+    fn test_no_candidates_left() {
+        let a = Coord::new(0, 0);
+        let b = Coord::new(0, 1);
+        let relation = Relation::new("Greater Than", a, b, |a, b| a > b);
+        let grid = &mut Grid::new();
+
+        grid.place_value_at(b, 9);
+        assert_eq!(relation.propagate(grid), SolveResult::Error);
+
+        let grid = &mut Grid::new();
+        grid.place_value_at(a, 2);
+        relation.mark_off(grid, a, 2);
+        assert_eq!(grid.get_cell_at(b).possible_count(), 1);
+        assert!(grid.is_possible_at(b, Mark::N1));
+    }
+}
@@ -0,0 +1,254 @@
+use std::error::Error;
+
+use crate::{
+    grid::{
+        cell::Cell,
+        constants::{GRID_HEIGHT, GRID_WIDTH},
+        coords::Coord,
+        grid::Grid,
+        mark::Mark,
+    },
+    solvers::solver::SolveResult,
+};
+
+use super::{
+    cage::supported_combinations,
+    constraint::Constraint,
+    util::{candidates, keep_candidates},
+};
+
+/// The values that can be between the crusts of a sandwich, all but the 1 and the 9
+const FILLING: Cell =
+    Cell::from_data(Cell::new().get_value() ^ Mark::N1.to_data() ^ Mark::N9.to_data());
+
+/// A row or a column of the grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Line {
+    Row(usize),
+    Column(usize),
+}
+
+impl Line {
+    pub fn coords(self) -> [Coord; 9] {
+        match self {
+            Line::Row(row) => core::array::from_fn(|col| Coord::new(row, col)),
+            Line::Column(col) => core::array::from_fn(|row| Coord::new(row, col)),
+        }
+    }
+}
+
+/// A sandwich clue, the values between the 1 and the 9 of the line add up to the sum
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "SandwichDefinition")
+)]
+pub struct Sandwich {
+    line: Line,
+    sum: u16,
+}
+
+/// The fields of a sandwich as they are read, checked by [`Sandwich::new`]
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct SandwichDefinition {
+    line: Line,
+    sum: u16,
+}
+
+impl Sandwich {
+    /// Creates a sandwich clue, returns an error if the line is outside of the grid
+    pub fn new(line: Line, sum: u16) -> Result<Self, Box<dyn Error>> {
+        let inside = match line {
+            Line::Row(row) => row < GRID_HEIGHT,
+            Line::Column(col) => col < GRID_WIDTH,
+        };
+        if !inside {
+            let msg = format!("{:?} is outside of the grid", line);
+            return Err(msg)?;
+        }
+
+        Ok(Self { line, sum })
+    }
+
+    pub fn line(&self) -> Line {
+        self.line
+    }
+
+    pub fn sum(&self) -> u16 {
+        self.sum
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<SandwichDefinition> for Sandwich {
+    type Error = Box<dyn Error>;
+
+    fn try_from(definition: SandwichDefinition) -> Result<Self, Self::Error> {
+        Sandwich::new(definition.line, definition.sum)
+    }
+}
+
+impl Constraint for Sandwich {
+    fn name(&self) -> &'static str {
+        "Sandwich"
+    }
+
+    /// Tries every position of the 1 and the 9, and keeps the candidates of the positions that can add up to the sum
+    fn propagate(&self, grid: &mut Grid) -> SolveResult {
+        let coords = self.line.coords();
+        let masks = coords.map(|c| candidates(grid, c));
+        let mut supported = [Cell::new_empty(); 9];
+        let mut fits = false;
+
+        for one in (0..9).filter(|i| masks[*i].is_possible(Mark::N1)) {
+            for nine in (0..9).filter(|i| *i != one && masks[*i].is_possible(Mark::N9)) {
+                let (start, end) = (one.min(nine), one.max(nine));
+                let filling: Vec<Cell> =
+                    masks[start + 1..end].iter().map(|m| *m & FILLING).collect();
+                let outside = (0..9).filter(|i| *i != one && *i != nine);
+
+                if outside.clone().any(|i| (masks[i] & FILLING).is_empty()) {
+                    continue;
+                }
+                let filling = match supported_combinations(&filling, self.sum) {
+                    Some(filling) => filling,
+                    None => continue,
+                };
+
+                fits = true;
+                supported[one].set_possible(Mark::N1);
+                supported[nine].set_possible(Mark::N9);
+                for i in outside.filter(|i| *i <= start || *i >= end) {
+                    supported[i] = supported[i] | (masks[i] & FILLING);
+                }
+                for (i, filled) in filling.into_iter().enumerate() {
+                    supported[start + 1 + i] = supported[start + 1 + i] | filled;
+                }
+            }
+        }
+
+        if !fits {
+            return SolveResult::Error;
+        }
+
+        let mut changed = false;
+        for (coord, allowed) in coords.into_iter().zip(supported) {
+            changed |= keep_candidates(grid, coord, allowed);
+        }
+
+        SolveResult::from_changed(changed)
+    }
+
+    fn validate(&self, grid: &Grid) -> Result<(), Box<dyn Error>> {
+        let values = self.line.coords().map(|c| grid.get_cell_at(c).value());
+        let one = values.iter().position(|v| *v == Some(1));
+        let nine = values.iter().position(|v| *v == Some(9));
+
+        let (start, end) = match (one, nine) {
+            (Some(one), Some(nine)) => (one.min(nine), one.max(nine)),
+            _ => return Ok(()),
+        };
+
+        let filling = &values[start + 1..end];
+        let total: u16 = filling.iter().flatten().sum();
+        let filled = filling.iter().all(|v| v.is_some());
+
+        if total > self.sum || (filled && total != self.sum) {
+            let msg = format!(
+                "The sandwich of {:?} adds up to {} instead of {}",
+                self.line, total, self.sum
+            );
+            return Err(msg)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Line, Sandwich};
+    use crate::{
+        constraints::{
+            constraint::Constraint, constraint_set::ConstraintSet, thermometer::Thermometer,
+        },
+        grid::{coords::Coord, grid::Grid, mark::Mark},
+        solvers::{
+            solution_counter::SolutionCounter, solver::SolveResult, validator::validate_grid_with,
+        },
+        test::util::general_tests,
+    };
+
+    #[test]
+    fn test_propagate() {
+        let grid = &mut Grid::new();
+
+        // Only 2 to 8 add up to 35, so the crusts are at the ends
+        let full = Sandwich::new(Line::Row(0), 35).unwrap();
+        assert_eq!(full.propagate(grid), SolveResult::Updated);
+        let marks: Vec<Mark> = grid.get_cell_at(Coord::new(0, 8)).iter_possible().collect();
+        assert_eq!(marks, vec![Mark::N1, Mark::N9]);
+        assert!(!grid.is_possible_at(Coord::new(0, 4), Mark::N1));
+
+        // An empty sandwich keeps the 1 and the 9 together
+        let empty = Sandwich::new(Line::Column(4), 0).unwrap();
+        grid.place_value_at(Coord::new(4, 4), 9);
+        assert_eq!(empty.propagate(grid), SolveResult::Updated);
+        assert!(grid.is_possible_at(Coord::new(5, 4), Mark::N1));
+        assert!(!grid.is_possible_at(Coord::new(6, 4), Mark::N1));
+
+        grid.place_value_at(Coord::new(8, 4), 1);
+        assert_eq!(empty.propagate(grid), SolveResult::Error);
+    }
+
+    #[test]
+    fn test_validate() {
+        // The first row is 4 3 5 2 6 9 7 8 1
+        let grid = general_tests::filled_sudoku();
+
+        assert!(Sandwich::new(Line::Row(9), 15).is_err());
+        assert!(Sandwich::new(Line::Column(9), 15).is_err());
+        assert!(Sandwich::new(Line::Row(0), 15)
+            .unwrap()
+            .validate(&grid)
+            .is_ok());
+        assert!(Sandwich::new(Line::Row(0), 14)
+            .unwrap()
+            .validate(&grid)
+            .is_err());
+        assert!(Sandwich::new(Line::Row(0), 14)
+            .unwrap()
+            .validate(&Grid::new())
+            .is_ok());
+    }
+
+    #[test]
+    fn test_solve_with_lines() {
+        let thermometer =
+            Thermometer::new(vec![Coord::new(0, 0), Coord::new(1, 0), Coord::new(2, 0)]).unwrap();
+        let constraints = ConstraintSet::new()
+            .with(Sandwich::new(Line::Row(0), 35).unwrap())
+            .with(Sandwich::new(Line::Column(8), 0).unwrap())
+            .with(thermometer);
+        let solution = &SolutionCounter::find_with(&Grid::new(), &constraints, 1)[0];
+
+        assert!(validate_grid_with(solution, &constraints).is_ok());
+        assert_eq!(solution.get_cell_at(Coord::new(0, 0)).value(), Some(1));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serialize() {
+        let sandwich = Sandwich::new(Line::Column(3), 12).unwrap();
+        let json = serde_json::to_string(&sandwich).unwrap();
+
+        assert_eq!(json, r#"{"line":{"Column":3},"sum":12}"#);
+        assert_eq!(serde_json::from_str::<Sandwich>(&json).unwrap(), sandwich);
+
+        let outside = r#"{"line":{"Row":12},"sum":5}"#;
+        assert!(serde_json::from_str::<Sandwich>(outside).is_err());
+    }
+}
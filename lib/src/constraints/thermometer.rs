@@ -0,0 +1,209 @@
+use std::error::Error;
+
+use crate::{
+    grid::{cell::Cell, coords::Coord, grid::Grid},
+    solvers::solver::SolveResult,
+};
+
+use super::{
+    constraint::Constraint,
+    util::{candidates, highest_value, keep_candidates, lowest_value, value_range},
+};
+
+/// A thermometer, the values strictly increase from the bulb along the path
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "ThermometerDefinition")
+)]
+pub struct Thermometer {
+    cells: Vec<Coord>,
+}
+
+/// The fields of a thermometer as they are read, checked by [`Thermometer::new`]
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct ThermometerDefinition {
+    cells: Vec<Coord>,
+}
+
+impl Thermometer {
+    /// Creates a thermometer, the first cell is the bulb. Returns an error if it does not hold 1 to 9 cells
+    pub fn new(cells: Vec<Coord>) -> Result<Self, Box<dyn Error>> {
+        if cells.is_empty() || cells.len() > 9 {
+            let msg = format!("A thermometer holds 1 to 9 cells, not {}", cells.len());
+            return Err(msg)?;
+        }
+
+        Ok(Self { cells })
+    }
+
+    pub fn cells(&self) -> &[Coord] {
+        &self.cells
+    }
+
+    pub fn index_of(&self, coord: Coord) -> Option<usize> {
+        self.cells.iter().position(|c| *c == coord)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<ThermometerDefinition> for Thermometer {
+    type Error = Box<dyn Error>;
+
+    fn try_from(definition: ThermometerDefinition) -> Result<Self, Self::Error> {
+        Thermometer::new(definition.cells)
+    }
+}
+
+impl Constraint for Thermometer {
+    fn name(&self) -> &'static str {
+        "Thermometer"
+    }
+
+    fn mark_off(&self, grid: &mut Grid, coord: Coord, value: u16) {
+        let index = match self.index_of(coord) {
+            Some(index) => index as i32,
+            None => return,
+        };
+
+        let value = value as i32;
+        for (other, cell) in self.cells.iter().enumerate() {
+            let distance = other as i32 - index;
+            let allowed = match distance {
+                0 => continue,
+                d if d < 0 => value_range(1, value + d),
+                d => value_range(value + d, 9),
+            };
+
+            keep_candidates(grid, *cell, allowed);
+        }
+    }
+
+    fn propagate(&self, grid: &mut Grid) -> SolveResult {
+        let mut masks: Vec<Cell> = self.cells.iter().map(|c| candidates(grid, *c)).collect();
+
+        // Each cell is higher than the lowest option before it, and lower than the highest option after it
+        let mut low = 0;
+        for cell in masks.iter_mut() {
+            *cell = *cell & value_range(low + 1, 9);
+            if cell.is_empty() {
+                return SolveResult::Error;
+            }
+            low = lowest_value(*cell);
+        }
+
+        let mut high = 10;
+        for cell in masks.iter_mut().rev() {
+            *cell = *cell & value_range(1, high - 1);
+            if cell.is_empty() {
+                return SolveResult::Error;
+            }
+            high = highest_value(*cell);
+        }
+
+        let mut changed = false;
+        for (coord, cell) in self.cells.iter().zip(masks) {
+            changed |= keep_candidates(grid, *coord, cell);
+        }
+
+        SolveResult::from_changed(changed)
+    }
+
+    fn validate(&self, grid: &Grid) -> Result<(), Box<dyn Error>> {
+        let mut previous: Option<(u16, Coord)> = None;
+
+        for coord in self.cells.iter() {
+            let value = match grid.get_cell_at(*coord).value() {
+                Some(value) => value,
+                None => continue,
+            };
+
+            if let Some((before, at)) = previous {
+                if before >= value {
+                    let msg = format!(
+                        "The thermometer at {} does not increase from {} at {} to {} at {}",
+                        self.cells[0], before, at, value, coord
+                    );
+                    return Err(msg)?;
+                }
+            }
+            previous = Some((value, *coord));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Thermometer;
+    use crate::{
+        constraints::constraint::Constraint,
+        grid::{coords::Coord, grid::Grid, mark::Mark},
+        solvers::solver::SolveResult,
+        test::util::general_tests,
+    };
+
+    fn thermometer() -> Thermometer {
+        let cells = vec![
+            Coord::new(0, 0),
+            Coord::new(1, 1),
+            Coord::new(2, 2),
+            Coord::new(3, 3),
+        ];
+
+        Thermometer::new(cells).unwrap()
+    }
+
+    #[test]
+    fn test_propagate() {
+        let thermometer = thermometer();
+        let grid = &mut Grid::new();
+
+        assert_eq!(thermometer.propagate(grid), SolveResult::Updated);
+        assert_eq!(thermometer.propagate(grid), SolveResult::Nothing);
+        let marks: Vec<Mark> = grid.get_cell_at(Coord::new(3, 3)).iter_possible().collect();
+        assert_eq!(
+            marks,
+            vec![Mark::N4, Mark::N5, Mark::N6, Mark::N7, Mark::N8, Mark::N9]
+        );
+
+        thermometer.mark_off(grid, Coord::new(1, 1), 5);
+        let marks: Vec<Mark> = grid.get_cell_at(Coord::new(0, 0)).iter_possible().collect();
+        assert_eq!(marks, vec![Mark::N1, Mark::N2, Mark::N3, Mark::N4]);
+        let marks: Vec<Mark> = grid.get_cell_at(Coord::new(3, 3)).iter_possible().collect();
+        assert_eq!(marks, vec![Mark::N7, Mark::N8, Mark::N9]);
+
+        grid.place_value_at(Coord::new(2, 2), 9);
+        assert_eq!(thermometer.propagate(grid), SolveResult::Error);
+    }
+
+    #[test]
+    fn test_validate() {
+        let grid = general_tests::filled_sudoku();
+        let increasing =
+            Thermometer::new(vec![Coord::new(0, 3), Coord::new(0, 1), Coord::new(0, 0)]).unwrap();
+
+        assert!(Thermometer::new(vec![]).is_err());
+        assert!(Thermometer::new(vec![Coord::new(0, 0); 10]).is_err());
+        assert!(increasing.validate(&grid).is_ok());
+        assert!(thermometer().validate(&Grid::new()).is_ok());
+        assert!(Thermometer::new(vec![Coord::new(0, 0), Coord::new(0, 1)])
+            .unwrap()
+            .validate(&grid)
+            .is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serialize() {
+        let json = serde_json::to_string(&thermometer()).unwrap();
+        let parsed: Thermometer = serde_json::from_str(&json).unwrap();
+
+        assert!(json.starts_with(r#"{"cells":[{"row":0,"col":0}"#));
+        assert_eq!(parsed, thermometer());
+        assert!(serde_json::from_str::<Thermometer>(r#"{"cells":[]}"#).is_err());
+    }
+}
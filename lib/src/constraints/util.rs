@@ -0,0 +1,185 @@
+use std::error::Error;
+
+use crate::{
+    grid::{
+        cell::Cell,
+        constants::{GRID_HEIGHT, GRID_WIDTH},
+        coords::Coord,
+        grid::Grid,
+        mark::Mark,
+    },
+    solvers::solver::SolveResult,
+};
+
+/// Returns the values a cell can hold as a mask, a placed value is its only candidate
+pub fn candidates(grid: &Grid, coord: Coord) -> Cell {
+    let cell = *grid.get_cell_at(coord);
+
+    match cell.value() {
+        Some(value) => Cell::new_with_possible(Mark::from_value(value)),
+        None => cell,
+    }
+}
+
+/// Masks the candidates of the cell, returns true if any were removed
+pub fn keep_candidates(grid: &mut Grid, coord: Coord, mask: Cell) -> bool {
+    let cell = *grid.get_cell_at(coord);
+    if cell.is_determined() {
+        return false;
+    }
+
+    let masked = cell & mask;
+    if masked == cell {
+        return false;
+    }
+
+    grid.set_cell_at(coord, &masked);
+    true
+}
+
+/// Returns the values that can be placed next to any of the candidates, as a mask.
+///
+/// The candidate is the first value given to the rule, the partner the second.
+pub fn partners(candidates: Cell, allowed: impl Fn(u16, u16) -> bool) -> Cell {
+    let mut result = Cell::new_empty();
+
+    for mark in candidates.iter_possible() {
+        for partner in Mark::iter() {
+            if allowed(mark.to_value(), partner.to_value()) {
+                result.set_possible(partner);
+            }
+        }
+    }
+
+    result
+}
+
+/// Removes the candidates of both cells that do not fit any candidate of the other cell.
+///
+/// The rule is given the value of `a` first, returns an error if one of the cells has nothing left that fits.
+pub fn reduce_pair(
+    grid: &mut Grid,
+    a: Coord,
+    b: Coord,
+    allowed: fn(u16, u16) -> bool,
+) -> SolveResult {
+    let candidates_a = candidates(grid, a);
+    let candidates_b = candidates(grid, b);
+    let fit_a = candidates_a & partners(candidates_b, |b, a| allowed(a, b));
+    let fit_b = candidates_b & partners(candidates_a, allowed);
+
+    if fit_a.is_empty() || fit_b.is_empty() {
+        return SolveResult::Error;
+    }
+
+    let ch1 = keep_candidates(grid, a, fit_a);
+    let ch2 = keep_candidates(grid, b, fit_b);
+
+    SolveResult::from_changed(ch1 | ch2)
+}
+
+/// The amount of edges between orthogonally neighboring cells, the pairs of [`iter_orthogonal_pairs`]
+pub const EDGE_COUNT: usize = GRID_HEIGHT * (GRID_WIDTH - 1) + (GRID_HEIGHT - 1) * GRID_WIDTH;
+
+/// Iterates over every pair of cells that share an edge, each pair once
+pub fn iter_orthogonal_pairs() -> impl Iterator<Item = (Coord, Coord)> {
+    let right = (0..GRID_HEIGHT).flat_map(|row| {
+        (0..GRID_WIDTH - 1).map(move |col| (Coord::new(row, col), Coord::new(row, col + 1)))
+    });
+    let down = (0..GRID_HEIGHT - 1).flat_map(|row| {
+        (0..GRID_WIDTH).map(move |col| (Coord::new(row, col), Coord::new(row + 1, col)))
+    });
+
+    right.chain(down)
+}
+
+/// Returns true if the two cells share an edge
+pub fn is_orthogonal(a: Coord, b: Coord) -> bool {
+    a.get_row().abs_diff(b.get_row()) + a.get_col().abs_diff(b.get_col()) == 1
+}
+
+/// Returns the index of the edge between the two cells in the order of [`iter_orthogonal_pairs`], none if they do not share one
+pub fn edge_index(a: Coord, b: Coord) -> Option<usize> {
+    if !is_orthogonal(a, b) {
+        return None;
+    }
+
+    let (row, col) = (a.get_row().min(b.get_row()), a.get_col().min(b.get_col()));
+    match a.get_row() == b.get_row() {
+        true => Some(row * (GRID_WIDTH - 1) + col),
+        false => Some(GRID_HEIGHT * (GRID_WIDTH - 1) + row * GRID_WIDTH + col),
+    }
+}
+
+/// Iterates over the cells that share an edge with the cell, at most 4
+pub fn orthogonal_neighbors(coord: Coord) -> impl Iterator<Item = Coord> {
+    let (row, col) = coord.get_row_col();
+    let up = row.checked_sub(1).map(|r| Coord::new(r, col));
+    let left = col.checked_sub(1).map(|c| Coord::new(row, c));
+    let right = (col + 1 < GRID_WIDTH).then(|| Coord::new(row, col + 1));
+    let down = (row + 1 < GRID_HEIGHT).then(|| Coord::new(row + 1, col));
+
+    [up, left, right, down].into_iter().flatten()
+}
+
+/// Removes the values from the neighbor that do not fit the value placed in one of the cells, the rule is given the value of `a` first
+pub fn mark_off_pair(
+    grid: &mut Grid,
+    coord: Coord,
+    value: u16,
+    a: Coord,
+    b: Coord,
+    rule: fn(u16, u16) -> bool,
+) {
+    let placed = Cell::new_with_possible(Mark::from_value(value));
+    let (other, allowed) = match coord {
+        c if c == a => (b, partners(placed, rule)),
+        c if c == b => (a, partners(placed, |b, a| rule(a, b))),
+        _ => return,
+    };
+
+    keep_candidates(grid, other, allowed);
+}
+
+pub fn validate_pair(
+    grid: &Grid,
+    a: Coord,
+    b: Coord,
+    rule: fn(u16, u16) -> bool,
+    name: &str,
+) -> Result<(), Box<dyn Error>> {
+    let va = grid.get_cell_at(a).value();
+    let vb = grid.get_cell_at(b).value();
+
+    match (va, vb) {
+        (Some(va), Some(vb)) if !rule(va, vb) => {
+            let msg = format!("{} is broken by {} at {} and {} at {}", name, va, a, vb, b);
+            Err(msg)?
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Returns the mask of the values from min to max, values outside of 1 to 9 are left out
+pub fn value_range(min: i32, max: i32) -> Cell {
+    let mut mask = Cell::new_empty();
+    for value in min.max(1)..=max.min(9) {
+        mask.set_possible(Mark::from_value(value as u16));
+    }
+
+    mask
+}
+
+/// Returns the lowest candidate of the mask, the mask can not be empty
+pub fn lowest_value(mask: Cell) -> i32 {
+    mask.iter_possible()
+        .next()
+        .map_or(0, |m| m.to_value() as i32)
+}
+
+/// Returns the highest candidate of the mask, the mask can not be empty
+pub fn highest_value(mask: Cell) -> i32 {
+    mask.iter_possible()
+        .last()
+        .map_or(0, |m| m.to_value() as i32)
+}
@@ -0,0 +1,87 @@
+use std::error::Error;
+
+use crate::{
+    grid::{coords::Coord, grid::Grid, window::Window},
+    solvers::{
+        solver::SolveResult,
+        validation_report::{Unit, ValidationReport},
+        validator::report_area,
+    },
+};
+
+use super::{
+    all_different::{mark_off_area, place_hidden_singles, validate_distinct},
+    constraint::Constraint,
+    constraint_set::ConstraintSet,
+};
+
+/// Returns the constraints of Windoku, the four visible and the five hidden windows must contain 1 to 9
+pub fn windoku() -> ConstraintSet {
+    Window::iter_windows().fold(ConstraintSet::new(), |set, window| set.with(window))
+}
+
+impl Constraint for Window {
+    fn name(&self) -> &'static str {
+        "Window"
+    }
+
+    fn mark_off(&self, grid: &mut Grid, coord: Coord, value: u16) {
+        if self.is_coord_in_window(coord) {
+            mark_off_area(self, grid, coord, value);
+        }
+    }
+
+    fn propagate(&self, grid: &mut Grid) -> SolveResult {
+        place_hidden_singles(self, grid)
+    }
+
+    fn validate(&self, grid: &Grid) -> Result<(), Box<dyn Error>> {
+        validate_distinct(self, grid)
+    }
+
+    fn report(&self, grid: &Grid, report: &mut ValidationReport) {
+        report_area(grid, *self, Unit::Window(self.index()), report);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::windoku;
+    use crate::{
+        grid::{coords::Coord, grid::Grid, mark::Mark},
+        solvers::{
+            validation_report::{Conflict, Unit},
+            validator::{is_valid_with, validation_report_with},
+        },
+    };
+
+    #[test]
+    fn test_mark_off() {
+        let constraints = windoku();
+        let grid = &mut Grid::new();
+
+        constraints.place_value_at(grid, Coord::new(0, 0), 5);
+
+        assert!(!grid.is_possible_at(Coord::new(8, 8), Mark::N5));
+        assert!(!grid.is_possible_at(Coord::new(4, 4), Mark::N5));
+        assert!(grid.is_possible_at(Coord::new(1, 5), Mark::N5));
+    }
+
+    #[test]
+    fn test_report() {
+        let constraints = windoku();
+        let grid = &mut Grid::new();
+        grid.place_value_at(Coord::new(1, 1), 7);
+        grid.place_value_at(Coord::new(3, 3), 7);
+
+        assert!(constraints.validate(grid).is_err());
+        assert!(!is_valid_with(grid, &constraints));
+        let report = validation_report_with(grid, &constraints);
+        assert!(report.conflicts().contains(&Conflict::Duplicate {
+            value: 7,
+            first: Coord::new(1, 1),
+            second: Coord::new(3, 3),
+            unit: Unit::Window(4),
+        }));
+    }
+}
@@ -0,0 +1,388 @@
+//! Compact binary codec for grids, for short shareable codes and storing large puzzle collections.
+//!
+//! Every encoding starts with a 2 bit tag that tells which layout follows:
+//! - Dense values: the 81 digits in groups of 3, each group being a number below 1000 stored in 10 bits, 34 bytes.
+//! - Sparse values: a bitmap of the 81 cells that are determined, followed by the values in groups of 3,
+//!   each group being a number below 729 stored in 10 bits, or below 9 or 81 if the last group holds
+//!   fewer values. A puzzle with 25 clues takes 22 bytes.
+//! - Full state: per cell a bit that tells if the cell is determined, followed by 4 bits with the value
+//!   or 9 bits with the candidates. At most 102 bytes.
+//!
+//! The string form is the URL-safe base64 encoding of the bytes, without padding.
+
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+use crate::grid::{cell::Cell, cell_collection::CellCollection, constants::GRID_SIZE, grid::Grid};
+
+const TAG_DENSE: u32 = 0;
+const TAG_SPARSE: u32 = 1;
+const TAG_FULL: u32 = 2;
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// The reason bytes or a string could not be decoded into a grid
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodeError {
+    /// A character that is not part of the URL-safe base64 alphabet
+    InvalidCharacter(char),
+    /// The data ended before the grid was complete
+    UnexpectedEnd,
+    /// The data starts with a tag that is not a known layout
+    InvalidTag(u8),
+    /// A group or cell holds a value that no grid can produce
+    InvalidValue(u32),
+    /// There is more data after the grid
+    TrailingData,
+    /// The base64 text ends with a lone character or with leftover bits that are not zero
+    InvalidPadding,
+}
+
+/// Encodes the determined values of the grid, picking the smallest of the dense and sparse layouts
+pub fn encode(grid: &Grid) -> Vec<u8> {
+    let values: Vec<u32> = grid
+        .iter()
+        .map(|i| grid.get_cell(i).value().unwrap_or(0) as u32)
+        .collect();
+    let clues: Vec<u32> = values.iter().filter(|v| **v != 0).copied().collect();
+
+    let mut writer = BitWriter::new();
+
+    if 81 + clues.len().div_ceil(3) * 10 < 270 {
+        writer.write(TAG_SPARSE, 2);
+        for value in values.iter() {
+            writer.write((*value != 0) as u32, 1);
+        }
+        for group in clues.chunks(3) {
+            let number = group.iter().rev().fold(0, |acc, v| acc * 9 + (v - 1));
+            writer.write(number, 10);
+        }
+    } else {
+        writer.write(TAG_DENSE, 2);
+        for group in values.chunks(3) {
+            writer.write(group[0] * 100 + group[1] * 10 + group[2], 10);
+        }
+    }
+
+    writer.finish()
+}
+
+/// Encodes the full state of the grid, keeping the candidates of the cells that are not determined
+pub fn encode_full(grid: &Grid) -> Vec<u8> {
+    let mut writer = BitWriter::new();
+    writer.write(TAG_FULL, 2);
+
+    for index in grid.iter() {
+        let cell = grid.get_cell(index);
+
+        match cell.value() {
+            Some(value) => {
+                writer.write(1, 1);
+                writer.write(value as u32, 4);
+            }
+            None => {
+                writer.write(0, 1);
+                writer.write((cell.only_possible().get_value() >> 7) as u32, 9);
+            }
+        }
+    }
+
+    writer.finish()
+}
+
+/// Decodes a grid made by [`encode`] or [`encode_full`].
+///
+/// Grids made by [`encode`] have their candidates marked off by the placed values.
+pub fn decode(bytes: &[u8]) -> Result<Grid, DecodeError> {
+    let mut reader = BitReader::new(bytes);
+    let mut grid = Grid::new();
+
+    match reader.read(2)? {
+        TAG_DENSE => {
+            for group in 0..27 {
+                let number = reader.read(10)?;
+                if number >= 1000 {
+                    return Err(DecodeError::InvalidValue(number));
+                }
+
+                let digits = [number / 100, number / 10 % 10, number % 10];
+                for (offset, value) in digits.iter().enumerate() {
+                    if *value != 0 {
+                        grid.place_value(group * 3 + offset, *value as u16);
+                    }
+                }
+            }
+        }
+        TAG_SPARSE => {
+            let mut clues = Vec::with_capacity(GRID_SIZE);
+            for index in 0..GRID_SIZE {
+                if reader.read(1)? == 1 {
+                    clues.push(index);
+                }
+            }
+
+            for group in clues.chunks(3) {
+                let mut number = reader.read(10)?;
+                if number >= 9u32.pow(group.len() as u32) {
+                    return Err(DecodeError::InvalidValue(number));
+                }
+
+                for index in group {
+                    grid.place_value(*index, (number % 9 + 1) as u16);
+                    number /= 9;
+                }
+            }
+        }
+        TAG_FULL => {
+            for index in 0..GRID_SIZE {
+                let cell = match reader.read(1)? {
+                    1 => {
+                        let value = reader.read(4)?;
+                        Cell::try_new_with_value(value as u16)
+                            .map_err(|_| DecodeError::InvalidValue(value))?
+                    }
+                    _ => Cell::from_data((reader.read(9)? << 7) as u16),
+                };
+
+                grid.set_cell(index, &cell);
+            }
+        }
+        tag => return Err(DecodeError::InvalidTag(tag as u8)),
+    }
+
+    reader.finish()?;
+    Ok(grid)
+}
+
+/// Returns the URL-safe base64 code of the determined values of the grid
+pub fn encode_string(grid: &Grid) -> String {
+    to_base64(&encode(grid))
+}
+
+/// Returns the URL-safe base64 code of the full state of the grid
+pub fn encode_full_string(grid: &Grid) -> String {
+    to_base64(&encode_full(grid))
+}
+
+/// Decodes a grid from a code made by [`encode_string`] or [`encode_full_string`]
+pub fn decode_string(code: &str) -> Result<Grid, DecodeError> {
+    decode(&from_base64(code)?)
+}
+
+/// Returns the URL-safe base64 encoding of the bytes, without padding
+pub fn to_base64(bytes: &[u8]) -> String {
+    let mut result = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+    for chunk in bytes.chunks(3) {
+        let number = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |acc, (i, b)| acc | (*b as u32) << (16 - i * 8));
+
+        for i in 0..=chunk.len() {
+            let index = (number >> (18 - i * 6)) & 0b11_1111;
+            result.push(BASE64[index as usize] as char);
+        }
+    }
+
+    result
+}
+
+/// Decodes URL-safe base64, padding at the end is allowed but not needed
+pub fn from_base64(text: &str) -> Result<Vec<u8>, DecodeError> {
+    let text = text.trim().trim_end_matches('=');
+    if text.len() % 4 == 1 {
+        return Err(DecodeError::InvalidPadding);
+    }
+
+    let mut result = Vec::with_capacity(text.len() * 3 / 4);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+
+    for c in text.chars() {
+        let value = BASE64
+            .iter()
+            .position(|b| *b as char == c)
+            .ok_or(DecodeError::InvalidCharacter(c))?;
+
+        buffer = (buffer << 6) | value as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            result.push((buffer >> bits) as u8);
+        }
+    }
+
+    if buffer & ((1 << bits) - 1) != 0 {
+        return Err(DecodeError::InvalidPadding);
+    }
+
+    Ok(result)
+}
+
+/// Writes values of a number of bits, most significant bit first
+struct BitWriter {
+    bytes: Vec<u8>,
+    bits: usize,
+}
+
+impl BitWriter {
+    fn new() -> Self {
+        Self {
+            bytes: Vec::with_capacity(102),
+            bits: 0,
+        }
+    }
+
+    fn write(&mut self, value: u32, count: usize) {
+        for bit in (0..count).rev() {
+            if self.bits.is_multiple_of(8) {
+                self.bytes.push(0);
+            }
+            if (value >> bit) & 1 == 1 {
+                let last = self.bytes.len() - 1;
+                self.bytes[last] |= 0x80 >> (self.bits % 8);
+            }
+            self.bits += 1;
+        }
+    }
+
+    fn finish(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+/// Reads values of a number of bits, most significant bit first
+struct BitReader<'a> {
+    bytes: &'a [u8],
+    bits: usize,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, bits: 0 }
+    }
+
+    fn read(&mut self, count: usize) -> Result<u32, DecodeError> {
+        let mut value = 0;
+
+        for _ in 0..count {
+            let byte = self
+                .bytes
+                .get(self.bits / 8)
+                .ok_or(DecodeError::UnexpectedEnd)?;
+            let bit = (byte >> (7 - self.bits % 8)) & 1;
+
+            value = (value << 1) | bit as u32;
+            self.bits += 1;
+        }
+
+        Ok(value)
+    }
+
+    /// Checks that only the padding of the last byte is left
+    fn finish(self) -> Result<(), DecodeError> {
+        match self.bits.div_ceil(8) == self.bytes.len() {
+            true => Ok(()),
+            false => Err(DecodeError::TrailingData),
+        }
+    }
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::InvalidCharacter(c) => write!(f, "invalid character '{}'", c),
+            DecodeError::UnexpectedEnd => write!(f, "the data ended before the grid was complete"),
+            DecodeError::InvalidTag(tag) => write!(f, "unknown layout {}", tag),
+            DecodeError::InvalidValue(v) => write!(f, "invalid value {}", v),
+            DecodeError::TrailingData => write!(f, "there is more data after the grid"),
+            DecodeError::InvalidPadding => write!(f, "the base64 text ends with invalid padding"),
+        }
+    }
+}
+
+impl Error for DecodeError {}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        decode, decode_string, encode, encode_full, encode_full_string, encode_string, from_base64,
+        to_base64, BitWriter, DecodeError, TAG_SPARSE,
+    };
+    use crate::{
+        grid::{cell::Cell, mark::Mark, utility::utility},
+        test::util::general_tests,
+    };
+
+    const PUZZLE: &str =
+        "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
+
+    #[test]
+    fn test_values_round_trip() {
+        let filled = general_tests::filled_sudoku();
+        let dense = encode(&filled);
+        assert_eq!(dense.len(), 34);
+        assert_eq!(decode(&dense).unwrap(), filled);
+
+        let puzzle = utility::from_digit(PUZZLE).unwrap();
+        let sparse = encode(&puzzle);
+        assert!(sparse.len() < 34);
+        assert_eq!(decode(&sparse).unwrap(), puzzle);
+
+        let code = encode_string(&puzzle);
+        assert!(code.len() < 81);
+        assert_eq!(utility::to_digits(&decode_string(&code).unwrap()), PUZZLE);
+    }
+
+    #[test]
+    fn test_full_round_trip() {
+        let mut grid = utility::from_digit(PUZZLE).unwrap();
+        grid.unset_possible(0, Mark::N4);
+        grid.set_cell(1, &Cell::new_empty());
+
+        let bytes = encode_full(&grid);
+        assert!(bytes.len() <= 102);
+        assert_eq!(decode(&bytes).unwrap(), grid);
+        assert_eq!(decode_string(&encode_full_string(&grid)).unwrap(), grid);
+    }
+
+    #[test]
+    fn test_base64() {
+        for data in [&b""[..], b"f", b"fo", b"foo", b"\xfb\xff"] {
+            assert_eq!(from_base64(&to_base64(data)).unwrap(), data);
+        }
+        assert_eq!(to_base64(b"\xfb\xff"), "-_8");
+        assert_eq!(from_base64("Zm9v").unwrap(), b"foo");
+    }
+
+    #[test]
+    fn test_errors() {
+        let bytes = encode(&general_tests::filled_sudoku());
+
+        assert_eq!(decode(&bytes[..20]), Err(DecodeError::UnexpectedEnd));
+        assert_eq!(decode(&[0xff]), Err(DecodeError::InvalidTag(3)));
+        assert_eq!(
+            decode(&[bytes.as_slice(), &[0]].concat()),
+            Err(DecodeError::TrailingData)
+        );
+        assert_eq!(
+            decode_string("ab+c"),
+            Err(DecodeError::InvalidCharacter('+'))
+        );
+        assert_eq!(from_base64("abcde"), Err(DecodeError::InvalidPadding));
+        assert_eq!(from_base64("AB=="), Err(DecodeError::InvalidPadding));
+        assert_eq!(from_base64("AQ=="), Ok(vec![1]));
+
+        // A single clue in the last group only holds a value below 9
+        let mut writer = BitWriter::new();
+        writer.write(TAG_SPARSE, 2);
+        for index in 0..81 {
+            writer.write((index == 0) as u32, 1);
+        }
+        writer.write(9, 10);
+        assert_eq!(decode(&writer.finish()), Err(DecodeError::InvalidValue(9)));
+    }
+}
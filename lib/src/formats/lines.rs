@@ -0,0 +1,282 @@
+//! Streaming reader and writer for files with one puzzle per line.
+//!
+//! Each line starts with 81 characters, digits with `.` or `0` for the empty cells. Anything after
+//! the puzzle that is separated by whitespace, `#`, `,` or `;` is a comment, such as a rating or the
+//! solution. Empty lines and lines starting with `#` are skipped.
+
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    io::{self, BufRead, Write},
+};
+
+use crate::grid::{grid::Grid, parse_error::ParseError, utility::utility};
+
+/// An error that occurred while reading puzzles, either from the reader or from one of the lines
+#[derive(Debug)]
+pub enum ReadError {
+    Io(io::Error),
+    Parse(ParseError),
+    /// The line with the given number, starting at 1, is not valid UTF-8
+    Encoding {
+        line: usize,
+    },
+}
+
+/// Reads one grid per line from a reader, only keeping a single line in memory at a time.
+///
+/// Reading stops after the first error from the reader. A line that is not valid UTF-8 or can not
+/// be parsed is yielded as an error, and reading continues with the next line.
+pub struct GridReader<R: BufRead> {
+    reader: R,
+    buffer: Vec<u8>,
+    line: usize,
+    /// Set once the reader failed, so a reader that keeps failing does not yield errors forever
+    done: bool,
+}
+
+impl<R: BufRead> GridReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            buffer: Vec::with_capacity(128),
+            line: 0,
+            done: false,
+        }
+    }
+
+    /// Returns the number of the last line read, starting at 1
+    pub fn line(&self) -> usize {
+        self.line
+    }
+}
+
+impl<R: BufRead> Iterator for GridReader<R> {
+    type Item = Result<Grid, ReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        loop {
+            self.buffer.clear();
+            match self.reader.read_until(b'\n', &mut self.buffer) {
+                Ok(0) => return None,
+                Ok(_) => self.line += 1,
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(ReadError::Io(e)));
+                }
+            }
+
+            let text = match std::str::from_utf8(&self.buffer) {
+                Ok(text) => text,
+                Err(_) => return Some(Err(ReadError::Encoding { line: self.line })),
+            };
+            let content = text.trim_start();
+            if content.is_empty() || content.starts_with('#') {
+                continue;
+            }
+
+            let end = content
+                .find(|c: char| c.is_whitespace() || matches!(c, '#' | ',' | ';'))
+                .unwrap_or(content.len());
+            // The leading whitespace is part of the column of any error
+            let offset = text.len() - content.len();
+
+            let result = utility::from_digit(&content[..end]).map_err(|e| {
+                ReadError::Parse(ParseError {
+                    line: self.line,
+                    column: e.column + offset,
+                    ..e
+                })
+            });
+
+            return Some(result);
+        }
+    }
+}
+
+/// Writes one grid per line to a writer, empty cells are written as `.`
+pub struct GridWriter<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> GridWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    /// Writes the determined values of the grid as a single line
+    pub fn write(&mut self, grid: &Grid) -> io::Result<()> {
+        writeln!(self.writer, "{}", line(grid))
+    }
+
+    /// Writes the determined values of the grid as a single line, followed by a comment such as a rating
+    pub fn write_with_comment(&mut self, grid: &Grid, comment: &str) -> io::Result<()> {
+        writeln!(self.writer, "{} {}", line(grid), comment.replace('\n', " "))
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Returns the underlying writer
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+fn line(grid: &Grid) -> String {
+    utility::to_digits(grid).replace('0', ".")
+}
+
+impl Display for ReadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ReadError::Io(e) => write!(f, "{}", e),
+            ReadError::Parse(e) => write!(f, "{}", e),
+            ReadError::Encoding { line } => write!(f, "line {} is not valid UTF-8", line),
+        }
+    }
+}
+
+impl Error for ReadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ReadError::Io(e) => Some(e),
+            ReadError::Parse(e) => Some(e),
+            ReadError::Encoding { .. } => None,
+        }
+    }
+}
+
+impl From<io::Error> for ReadError {
+    fn from(e: io::Error) -> Self {
+        ReadError::Io(e)
+    }
+}
+
+impl From<ParseError> for ReadError {
+    fn from(e: ParseError) -> Self {
+        ReadError::Parse(e)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::{self, BufRead, Cursor, Read};
+
+    use super::{GridReader, GridWriter, ReadError};
+    use crate::{
+        grid::{parse_error::ParseErrorKind, utility::utility},
+        test::util::general_tests,
+    };
+
+    const PUZZLE: &str =
+        "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
+
+    #[test]
+    fn test_read_lines() {
+        let text = format!(
+            "# top 2\n{}\n\n{}  4.5/1.2/1.2\n{},solution\n",
+            PUZZLE,
+            PUZZLE.replace('0', "."),
+            PUZZLE
+        );
+        let grids: Vec<_> = GridReader::new(Cursor::new(text))
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(grids.len(), 3);
+        for grid in grids.iter() {
+            assert_eq!(utility::to_digits(grid), PUZZLE);
+        }
+    }
+
+    #[test]
+    fn test_read_error() {
+        let text = format!("{}\n {}", PUZZLE, &PUZZLE[1..]);
+        let mut reader = GridReader::new(Cursor::new(text));
+
+        assert!(reader.next().unwrap().is_ok());
+        match reader.next().unwrap() {
+            Err(ReadError::Parse(e)) => {
+                assert_eq!((e.line, e.column), (2, 82));
+                assert_eq!(
+                    e.kind,
+                    ParseErrorKind::TooFewCells {
+                        found: 80,
+                        expected: 81
+                    }
+                );
+            }
+            _ => panic!("expected a parse error"),
+        }
+        assert!(reader.next().is_none());
+    }
+
+    /// A reader that fails on every read
+    struct Failing;
+
+    impl Read for Failing {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::other("broken"))
+        }
+    }
+
+    impl BufRead for Failing {
+        fn fill_buf(&mut self) -> io::Result<&[u8]> {
+            Err(io::Error::other("broken"))
+        }
+
+        fn consume(&mut self, _: usize) {}
+    }
+
+    #[test]
+    fn test_io_error() {
+        let mut reader = GridReader::new(Failing);
+
+        assert!(matches!(reader.next(), Some(Err(ReadError::Io(_)))));
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn test_invalid_utf8() {
+        let mut data = format!("{}\n", PUZZLE).into_bytes();
+        data.extend_from_slice(b"\xff\xfe\n");
+        data.extend_from_slice(PUZZLE.as_bytes());
+        let mut reader = GridReader::new(data.as_slice());
+
+        assert!(reader.next().unwrap().is_ok());
+        match reader.next().unwrap() {
+            Err(ReadError::Encoding { line }) => assert_eq!(line, 2),
+            _ => panic!("expected an encoding error"),
+        }
+        assert!(reader.next().unwrap().is_ok());
+        assert_eq!(reader.line(), 3);
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn test_write_read() {
+        let grids = [
+            general_tests::filled_sudoku(),
+            utility::from_digit(PUZZLE).unwrap(),
+        ];
+        let mut writer = GridWriter::new(Vec::new());
+
+        writer.write(&grids[0]).unwrap();
+        writer.write_with_comment(&grids[1], "# easy").unwrap();
+
+        let data = writer.into_inner();
+        let read: Vec<_> = GridReader::new(data.as_slice())
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(read.len(), 2);
+        assert_eq!(read[0], grids[0]);
+        assert_eq!(read[1], grids[1]);
+    }
+}
@@ -0,0 +1,41 @@
+use crate::grid::grid::Grid;
+
+/// The information about a puzzle that the file formats carry next to the grid
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Metadata {
+    pub title: Option<String>,
+    pub author: Option<String>,
+    /// The difficulty as written in the file, each format and source uses its own scale
+    pub difficulty: Option<String>,
+    pub source: Option<String>,
+}
+
+/// A grid read from or written to a file, together with its metadata
+#[derive(Debug, Clone)]
+pub struct Puzzle {
+    pub grid: Grid,
+    pub metadata: Metadata,
+}
+
+impl Metadata {
+    /// Returns true if none of the fields are set
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none()
+            && self.author.is_none()
+            && self.difficulty.is_none()
+            && self.source.is_none()
+    }
+}
+
+impl Puzzle {
+    pub fn new(grid: Grid) -> Self {
+        Self {
+            grid,
+            metadata: Metadata::default(),
+        }
+    }
+
+    pub fn with_metadata(grid: Grid, metadata: Metadata) -> Self {
+        Self { grid, metadata }
+    }
+}
@@ -0,0 +1,7 @@
+pub mod binary;
+pub mod lines;
+pub mod metadata;
+pub mod opensudoku;
+pub mod sdk;
+pub mod sdm;
+pub mod ss;
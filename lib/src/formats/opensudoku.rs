@@ -0,0 +1,253 @@
+//! The OpenSudoku XML format, a collection of games that share the metadata of the file.
+//!
+//! ```xml
+//! <opensudoku>
+//!   <name>Easy</name>
+//!   <author>John Doe</author>
+//!   <level>easy</level>
+//!   <source>Newspaper</source>
+//!   <game data="003020600900305001..." />
+//! </opensudoku>
+//! ```
+//!
+//! Files exported by newer versions put the games in `<folder name="...">` elements, the name of
+//! the folder is used as the title when there is no `<name>`.
+
+use crate::grid::{
+    parse_error::{ParseError, ParseErrorKind},
+    utility::utility,
+};
+
+use super::metadata::{Metadata, Puzzle};
+
+/// Reads all the games from an OpenSudoku XML document, each with the metadata of the document
+pub fn read(text: &str) -> Result<Vec<Puzzle>, ParseError> {
+    if !text.contains("<opensudoku") {
+        let kind = ParseErrorKind::Malformed(String::from("missing the <opensudoku> element"));
+        return Err(ParseError::new(1, 1, kind));
+    }
+
+    let metadata = Metadata {
+        title: element_text(text, "name").or_else(|| {
+            let (_, tag) = *tags(text, "folder").first()?;
+            attribute(tag, "name").map(|(_, v)| unescape(v))
+        }),
+        author: element_text(text, "author"),
+        difficulty: element_text(text, "level"),
+        source: element_text(text, "source"),
+    };
+    let mut result = Vec::new();
+
+    for (start, tag) in tags(text, "game") {
+        let (offset, data) = match attribute(tag, "data") {
+            Some(v) => v,
+            None => {
+                let (line, column) = position(text, start);
+                let kind = ParseErrorKind::Malformed(String::from("game without a data attribute"));
+                return Err(ParseError::new(line, column, kind));
+            }
+        };
+
+        let grid = utility::from_digit(data).map_err(|e| {
+            let (line, column) = position(text, start + offset);
+            ParseError {
+                line,
+                column: column + e.column - 1,
+                ..e
+            }
+        })?;
+        result.push(Puzzle::with_metadata(grid, metadata.clone()));
+    }
+
+    Ok(result)
+}
+
+/// Writes the puzzles as an OpenSudoku XML document.
+///
+/// The format keeps a single set of metadata for all the games, the metadata of the first puzzle is used.
+pub fn write(puzzles: &[Puzzle]) -> String {
+    let mut result = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<opensudoku>\n");
+
+    if let Some(first) = puzzles.first() {
+        let metadata = &first.metadata;
+
+        write_element(&mut result, "name", &metadata.title);
+        write_element(&mut result, "author", &metadata.author);
+        write_element(&mut result, "level", &metadata.difficulty);
+        write_element(&mut result, "source", &metadata.source);
+    }
+
+    for puzzle in puzzles {
+        let digits = utility::to_digits(&puzzle.grid);
+        result.push_str(&format!("  <game data=\"{}\" />\n", digits));
+    }
+
+    result.push_str("</opensudoku>\n");
+    result
+}
+
+fn write_element(result: &mut String, name: &str, value: &Option<String>) {
+    if let Some(value) = value {
+        result.push_str(&format!("  <{0}>{1}</{0}>\n", name, escape(value)));
+    }
+}
+
+/// Returns the opening tags with the given name, with the offset they start at
+fn tags<'a>(text: &'a str, name: &str) -> Vec<(usize, &'a str)> {
+    let open = format!("<{}", name);
+    let mut result = Vec::new();
+
+    for (start, _) in text.match_indices(&open) {
+        let next = text[start + open.len()..].chars().next();
+        if !matches!(next, Some(c) if c.is_whitespace() || c == '>' || c == '/') {
+            continue;
+        }
+
+        if let Some(end) = text[start..].find('>') {
+            result.push((start, &text[start..start + end]));
+        }
+    }
+
+    result
+}
+
+/// Returns the offset in the tag and the value of an attribute
+fn attribute<'a>(tag: &'a str, name: &str) -> Option<(usize, &'a str)> {
+    let pattern = format!("{}=", name);
+
+    for (index, _) in tag.match_indices(&pattern) {
+        let before = tag[..index].chars().last();
+        if !matches!(before, Some(c) if c.is_whitespace()) {
+            continue;
+        }
+
+        let rest = &tag[index + pattern.len()..];
+        let quote = rest.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+        let end = rest[1..].find(quote)?;
+        let offset = index + pattern.len() + 1;
+
+        return Some((offset, &rest[1..1 + end]));
+    }
+
+    None
+}
+
+/// Returns the trimmed text of the first element with the given name, if it is not empty
+fn element_text(text: &str, name: &str) -> Option<String> {
+    let (start, tag) = *tags(text, name).first()?;
+    if tag.ends_with('/') {
+        return None;
+    }
+
+    let content = &text[start + tag.len() + 1..];
+    let end = content.find(&format!("</{}>", name))?;
+    let value = unescape(content[..end].trim());
+
+    (!value.is_empty()).then_some(value)
+}
+
+/// Returns the line and column of the byte offset, starting at 1
+fn position(text: &str, offset: usize) -> (usize, usize) {
+    let before = &text[..offset];
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().map_or(0, |l| l.chars().count()) + 1;
+
+    (line, column)
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn unescape(value: &str) -> String {
+    value
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod test {
+    use super::{read, write};
+    use crate::{
+        formats::metadata::{Metadata, Puzzle},
+        grid::{parse_error::ParseErrorKind, utility::utility},
+        test::util::general_tests,
+    };
+
+    const FILE: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<opensudoku>
+  <name>Easy &amp; quick</name>
+  <author>John Doe</author>
+  <description>Some puzzles</description>
+  <comment/>
+  <level>easy</level>
+  <game data="003020600900305001001806400008102900700000008006708200002609500800203009005010300"/>
+  <game data='200080300060070084030500209000105408000000000402706000301007040720040060004010003'/>
+</opensudoku>
+"#;
+
+    #[test]
+    fn test_read() {
+        let puzzles = read(FILE).unwrap();
+
+        assert_eq!(puzzles.len(), 2);
+        assert_eq!(
+            puzzles[1].metadata,
+            Metadata {
+                title: Some(String::from("Easy & quick")),
+                author: Some(String::from("John Doe")),
+                difficulty: Some(String::from("easy")),
+                source: None,
+            }
+        );
+        assert_eq!(
+            utility::to_digits(&puzzles[1].grid),
+            "200080300060070084030500209000105408000000000402706000301007040720040060004010003"
+        );
+    }
+
+    #[test]
+    fn test_folder_name() {
+        let text = r#"<opensudoku version="2">
+  <folder name="Hard" created="1310913236000">
+    <game created="1310913236000" state="1" data="003020600900305001001806400008102900700000008006708200002609500800203009005010300" />
+  </folder>
+</opensudoku>"#;
+        let puzzles = read(text).unwrap();
+
+        assert_eq!(puzzles.len(), 1);
+        assert_eq!(puzzles[0].metadata.title.as_deref(), Some("Hard"));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let second = read(FILE).unwrap().remove(0);
+        let first = Puzzle::with_metadata(general_tests::filled_sudoku(), second.metadata.clone());
+
+        let parsed = read(&write(&[first.clone(), second.clone()])).unwrap();
+
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0].grid, first.grid);
+        assert_eq!(parsed[1].grid, second.grid);
+        assert_eq!(parsed[0].metadata, second.metadata);
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = read("<sudoku />").unwrap_err();
+        assert!(matches!(error.kind, ParseErrorKind::Malformed(_)));
+
+        let text = FILE.replace("0030206", "00x0206");
+        let error = read(&text).unwrap_err();
+        assert_eq!((error.line, error.column), (8, 17));
+        assert_eq!(error.kind, ParseErrorKind::InvalidCharacter('x'));
+    }
+}
@@ -0,0 +1,145 @@
+//! The SadMan Software `.sdk` format, a single puzzle of 9 lines with 9 characters each where `.`
+//! is an empty cell. The grid can be preceded by `#` header lines holding the metadata, such as
+//! `#A` for the author, `#D` for the description, `#L` for the level and `#S` for the source.
+
+use crate::grid::{
+    constants::GRID_HEIGHT_RANGE, grid::Grid, parse_error::ParseError, utility::utility,
+};
+
+use super::metadata::{Metadata, Puzzle};
+
+/// Reads a single puzzle from the text of a `.sdk` file
+pub fn read(text: &str) -> Result<Puzzle, ParseError> {
+    let mut metadata = Metadata::default();
+
+    // Header lines are replaced by empty lines so the error positions still match the text
+    let mut body = String::with_capacity(text.len());
+
+    for line in text.lines() {
+        if let Some(header) = line.strip_prefix('#') {
+            read_header(header, &mut metadata);
+        } else {
+            body.push_str(line);
+        }
+        body.push('\n');
+    }
+
+    let grid = utility::parse_from_ascii(&body)?;
+
+    Ok(Puzzle::with_metadata(grid, metadata))
+}
+
+/// Writes the puzzle as a `.sdk` file, only the determined values are kept
+pub fn write(puzzle: &Puzzle) -> String {
+    let mut result = String::new();
+    let metadata = &puzzle.metadata;
+
+    write_header(&mut result, 'A', &metadata.author);
+    write_header(&mut result, 'D', &metadata.title);
+    write_header(&mut result, 'L', &metadata.difficulty);
+    write_header(&mut result, 'S', &metadata.source);
+
+    result.push_str(&grid_lines(&puzzle.grid));
+    result
+}
+
+fn read_header(header: &str, metadata: &mut Metadata) {
+    let mut chars = header.chars();
+    let field = match chars.next() {
+        Some(c) => c.to_ascii_uppercase(),
+        None => return,
+    };
+    let value = chars.as_str().trim();
+    if value.is_empty() {
+        return;
+    }
+
+    let target = match field {
+        'A' => &mut metadata.author,
+        'D' => &mut metadata.title,
+        'L' => &mut metadata.difficulty,
+        'S' => &mut metadata.source,
+        // Dates, comments and urls are not kept
+        _ => return,
+    };
+    *target = Some(value.to_string());
+}
+
+fn write_header(result: &mut String, field: char, value: &Option<String>) {
+    if let Some(value) = value {
+        result.push_str(&format!("#{}{}\n", field, value.replace('\n', " ")));
+    }
+}
+
+/// Returns the grid as 9 lines of 9 characters, `.` being an empty cell
+fn grid_lines(grid: &Grid) -> String {
+    let digits = utility::to_digits(grid).replace('0', ".");
+    let mut result = String::with_capacity(digits.len() + 9);
+
+    for row in GRID_HEIGHT_RANGE {
+        result.push_str(&digits[row * 9..row * 9 + 9]);
+        result.push('\n');
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::{read, write};
+    use crate::{
+        formats::metadata::{Metadata, Puzzle},
+        grid::utility::utility,
+    };
+
+    const FILE: &str = "#AJohn Doe
+#DEasy Start
+#B01-02-2003
+#L Easy
+..3.2.6..
+9..3.5..1
+..18.64..
+..81.29..
+7.......8
+..67.82..
+..26.95..
+8..2.3..9
+..5.1.3..
+";
+
+    #[test]
+    fn test_read() {
+        let puzzle = read(FILE).unwrap();
+
+        assert_eq!(puzzle.metadata.author.as_deref(), Some("John Doe"));
+        assert_eq!(puzzle.metadata.title.as_deref(), Some("Easy Start"));
+        assert_eq!(puzzle.metadata.difficulty.as_deref(), Some("Easy"));
+        assert_eq!(puzzle.metadata.source, None);
+        assert_eq!(
+            utility::to_digits(&puzzle.grid),
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300"
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let puzzle = read(FILE).unwrap();
+        let metadata = Metadata {
+            source: Some(String::from("Project Euler")),
+            ..puzzle.metadata
+        };
+        let text = write(&Puzzle::with_metadata(puzzle.grid, metadata.clone()));
+        let parsed = read(&text).unwrap();
+
+        assert_eq!(parsed.metadata, metadata);
+        assert_eq!(parsed.grid, puzzle.grid);
+    }
+
+    #[test]
+    fn test_error_position() {
+        let text = FILE.replace("7.......8", "7...x...8");
+        let error = read(&text).unwrap_err();
+
+        assert_eq!((error.line, error.column), (9, 5));
+    }
+}
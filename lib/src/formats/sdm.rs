@@ -0,0 +1,77 @@
+//! The `.sdm` format, a collection of puzzles with one puzzle of 81 digits per line where `0` is
+//! an empty cell. The format has no metadata.
+
+use crate::grid::{parse_error::ParseError, utility::utility};
+
+use super::metadata::Puzzle;
+
+/// Reads all the puzzles from the text of a `.sdm` file, empty lines are skipped
+pub fn read(text: &str) -> Result<Vec<Puzzle>, ParseError> {
+    let mut result = Vec::new();
+
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let grid = utility::from_digit(line).map_err(|e| ParseError {
+            line: index + 1,
+            ..e
+        })?;
+        result.push(Puzzle::new(grid));
+    }
+
+    Ok(result)
+}
+
+/// Writes the puzzles as a `.sdm` file, only the determined values are kept
+pub fn write(puzzles: &[Puzzle]) -> String {
+    let mut result = String::with_capacity(puzzles.len() * 82);
+
+    for puzzle in puzzles {
+        result.push_str(&utility::to_digits(&puzzle.grid));
+        result.push('\n');
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::{read, write};
+    use crate::{
+        grid::{parse_error::ParseErrorKind, utility::utility},
+        test::util::general_tests,
+    };
+
+    const FILE: &str = "
+003020600900305001001806400008102900700000008006708200002609500800203009005010300
+200080300060070084030500209000105408000000000402706000301007040720040060004010003
+";
+
+    #[test]
+    fn test_round_trip() {
+        let puzzles = read(FILE).unwrap();
+
+        assert_eq!(puzzles.len(), 2);
+        assert!(puzzles.iter().all(|p| p.metadata.is_empty()));
+        assert_eq!(write(&puzzles), FILE.trim_start());
+    }
+
+    #[test]
+    fn test_error_line() {
+        let digits = utility::to_digits(&general_tests::filled_sudoku());
+        let text = format!("{}{}\n", FILE, &digits[1..]);
+        let error = read(&text).unwrap_err();
+
+        assert_eq!(error.line, 4);
+        assert_eq!(
+            error.kind,
+            ParseErrorKind::TooFewCells {
+                found: 80,
+                expected: 81
+            }
+        );
+    }
+}
@@ -0,0 +1,80 @@
+//! The Simple Sudoku `.ss` format, 9 rows where `.` is an empty cell and the boxes are separated
+//! by `|` and lines of `-`. The borders can start and end with `*`. The format has no metadata.
+
+use crate::grid::{
+    constants::GRID_HEIGHT_RANGE, grid::Grid, parse_error::ParseError, utility::utility,
+};
+
+use super::metadata::Puzzle;
+
+/// Reads a single puzzle from the text of a `.ss` file
+pub fn read(text: &str) -> Result<Puzzle, ParseError> {
+    // The corners are replaced by spaces so the error positions still match the text
+    let text = text.replace('*', " ");
+    let grid = utility::parse_from_ascii(&text)?;
+
+    Ok(Puzzle::new(grid))
+}
+
+/// Writes the grid as a `.ss` file, only the determined values are kept
+pub fn write(grid: &Grid) -> String {
+    let digits = utility::to_digits(grid).replace('0', ".");
+    let mut result = String::from("*-----------*\n");
+
+    for row in GRID_HEIGHT_RANGE {
+        if row == 3 || row == 6 {
+            result.push_str("|---+---+---|\n");
+        }
+
+        let line = &digits[row * 9..row * 9 + 9];
+        result.push_str(&format!(
+            "|{}|{}|{}|\n",
+            &line[0..3],
+            &line[3..6],
+            &line[6..9]
+        ));
+    }
+
+    result.push_str("*-----------*\n");
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::{read, write};
+    use crate::grid::utility::utility;
+
+    const FILE: &str = "*-----------*
+|..3|.2.|6..|
+|9..|3.5|..1|
+|..1|8.6|4..|
+|---+---+---|
+|..8|1.2|9..|
+|7..|...|..8|
+|..6|7.8|2..|
+|---+---+---|
+|..2|6.9|5..|
+|8..|2.3|..9|
+|..5|.1.|3..|
+*-----------*
+";
+
+    #[test]
+    fn test_round_trip() {
+        let puzzle = read(FILE).unwrap();
+
+        assert_eq!(
+            utility::to_digits(&puzzle.grid),
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300"
+        );
+        assert_eq!(write(&puzzle.grid), FILE);
+    }
+
+    #[test]
+    fn test_plain_rows() {
+        let text = FILE.replace('|', "").replace("---+---+---\n", "");
+        let puzzle = read(&text).unwrap();
+
+        assert_eq!(write(&puzzle.grid), FILE);
+    }
+}
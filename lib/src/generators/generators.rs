@@ -1,36 +1,66 @@
-use rand::{rngs::StdRng, seq::IteratorRandom, Rng, RngCore, SeedableRng};
+use std::{
+    error::Error,
+    time::{Duration, Instant},
+};
+
+use rand::{
+    rngs::StdRng,
+    seq::{IteratorRandom, SliceRandom},
+    Rng, RngCore, SeedableRng,
+};
 
 use crate::{
-    grid::{cell::Cell, cell_collection::CellCollection, grid::Grid, square::Square},
-    solvers::{solver::SolveResult, solver_manager::SolverManager},
+    constraints::constraint_set::ConstraintSet,
+    grid::{
+        board::{Board, BoardCell},
+        cell_collection::CellCollection,
+        grid::Grid,
+    },
+    solvers::{
+        solution_counter::SolutionCounter, solver::SolveResult, solver_manager::SolverManager,
+    },
 };
 
-pub struct Generator<T: RngCore> {
-    pub solvers: SolverManager,
+/// Generates filled grids and puzzles, the standard grid unless it is given another empty [`Board`]
+pub struct Generator<T: RngCore, G: Board = Grid> {
+    pub solvers: SolverManager<G>,
     pub rng: T,
+    /// The empty board the grids are generated from
+    board: G,
 }
 
 impl<T: RngCore> Generator<T> {
     /// Creates a new generator
     pub fn new(rng: T) -> Self {
-        let mut solvers = SolverManager::new();
+        Generator::new_with_board(rng, Grid::new())
+    }
+}
+
+impl<T: RngCore, G: Board> Generator<T, G> {
+    /// Creates a new generator for the given empty board, such as a jigsaw grid
+    pub fn new_with_board(rng: T, board: G) -> Self {
+        let mut solvers = SolverManager::default();
         solvers.config.max_iterations = 100;
 
-        Self { solvers, rng }
+        Self {
+            solvers,
+            rng,
+            board,
+        }
     }
 
     /// Generates a new grid
-    pub fn generate(&mut self) -> Grid {
+    pub fn generate(&mut self) -> G {
         loop {
-            let grid = &mut Grid::new();
+            let grid = &mut self.board.clone();
 
-            for sq in Square::iter_squares() {
+            for sq in grid.boxes() {
                 let mut count = 3;
                 loop {
                     let buf = &mut grid.clone();
-                    let result = self.determine_area(buf, sq);
+                    let result = self.determine_area(buf, &sq);
                     if result == SolveResult::Solved {
-                        return *buf;
+                        return buf.clone();
                     }
                     if result == SolveResult::Error {
                         count -= 1;
@@ -42,25 +72,28 @@ impl<T: RngCore> Generator<T> {
                     }
 
                     // println!("{}", buf);
-                    buf.clone_to(grid);
+                    grid.clone_from(buf);
                     break;
                 }
             }
         }
     }
 
-    fn determine_area<U: CellCollection>(&mut self, grid: &mut Grid, area: U) -> SolveResult {
+    fn determine_area<U>(&mut self, grid: &mut G, area: &U) -> SolveResult
+    where
+        U: CellCollection<Dimensions = G::Dimensions>,
+    {
         for index in area.iter() {
             let coord = area.get_coord(index);
-            let cell = &grid.get_cell_at(coord);
+            let cell = *grid.get_cell_at(coord);
             if cell.is_determined() {
                 continue;
             }
 
-            let iter = cell.iter_possible();
+            let iter = cell.iter_possible_values();
 
             match iter.choose(&mut self.rng) {
-                Some(value) => grid.place_value_at(coord, value.to_value()),
+                Some(value) => grid.place_value_at(coord, value),
                 None => return SolveResult::Error,
             }
         }
@@ -68,23 +101,87 @@ impl<T: RngCore> Generator<T> {
         return self.solvers.pre_solve(grid) | self.solvers.solve_round(grid);
     }
 
+    /// Generates a puzzle with a unique solution
+    pub fn generate_puzzle(&mut self) -> G {
+        self.generate_puzzle_with(&ConstraintSet::default(), None)
+            .expect("Without constraints or a time budget every board has a puzzle")
+    }
+
+    /// Removes cells in a random order, as long as the grid keeps a single solution
+    pub fn remove_cells_unique(&mut self, grid: &mut G) {
+        self.remove_cells_unique_with(grid, &ConstraintSet::default(), None);
+    }
+
+    /// Generates a puzzle with a unique solution under the extra constraints, such as killer cages or Kropki dots.
+    ///
+    /// The solution is a random one found by the exhaustive solver, which then checks every removed cell keeps it unique.
+    /// When the constraints force the solution by themselves, no given digits are left.
+    /// Returns an error if there is no solution or none is found within the time budget,
+    /// when the budget runs out while removing cells the puzzle so far is returned.
+    /// Without a budget the search takes as long as it needs.
+    pub fn generate_puzzle_with(
+        &mut self,
+        constraints: &ConstraintSet<G>,
+        budget: Option<Duration>,
+    ) -> Result<G, Box<dyn Error>> {
+        let deadline = budget.map(|budget| Instant::now() + budget);
+        let empty = &self.board;
+
+        let solution =
+            SolutionCounter::find_random_within(empty, constraints, &mut self.rng, deadline)?;
+        let grid = &mut solution.ok_or("The constraints can not be solved")?;
+        self.remove_cells_unique_with(grid, constraints, deadline);
+
+        Ok(grid.clone())
+    }
+
+    /// Removes cells in a random order, as long as the grid keeps a single solution under the constraints.
+    ///
+    /// Stops once the deadline has passed, the grid is unique either way.
+    pub fn remove_cells_unique_with(
+        &mut self,
+        grid: &mut G,
+        constraints: &ConstraintSet<G>,
+        deadline: Option<Instant>,
+    ) {
+        let mut indices: Vec<usize> = grid.iter().collect();
+        indices.shuffle(&mut self.rng);
+
+        for index in indices {
+            let cell = *grid.get_cell(index);
+            if !cell.is_determined() {
+                continue;
+            }
+
+            grid.set_cell(index, &G::Cell::new());
+            match SolutionCounter::find_within(grid, constraints, 2, deadline) {
+                Ok(solutions) if solutions.len() == 1 => continue,
+                Ok(_) => grid.set_cell(index, &cell),
+                Err(_) => {
+                    grid.set_cell(index, &cell);
+                    return;
+                }
+            }
+        }
+    }
+
     /// Removes a random amount of cells from the grid
-    pub fn remove_cells(&mut self, grid: &mut Grid) {
+    pub fn remove_cells(&mut self, grid: &mut G) {
         let amount = self.rng.gen_range(0..80);
 
         self.remove_cells_amount(grid, amount);
     }
 
     /// Removes a random amount of cells from the grid
-    pub fn remove_cells_amount(&mut self, grid: &mut Grid, amount: usize) {
+    pub fn remove_cells_amount(&mut self, grid: &mut G, amount: usize) {
         let mut removed = 0;
 
         while removed < amount {
             let index = self.rng.gen_range(0..grid.max());
-            let cell = &grid.get_cell(index);
+            let cell = grid.get_cell(index);
 
             if cell.is_determined() {
-                grid.set_cell(index, &Cell::new());
+                grid.set_cell(index, &G::Cell::new());
                 removed += 1;
             }
         }
@@ -107,6 +204,25 @@ impl Generator<StdRng> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        constraints::{
+            cage::Cage,
+            chess_move::{AntiKing, AntiKnight},
+            diagonal::sudoku_x,
+            killer::Killer,
+            kropki::{Dot, DotKind, Kropki},
+            non_consecutive::NonConsecutive,
+            thermometer::Thermometer,
+            util::iter_orthogonal_pairs,
+            window::windoku,
+        },
+        grid::{
+            coords::Coord, diagonal::Diagonal, jigsaw::Jigsaw, jigsaw_grid::JigsawGrid, mark::Mark,
+            window::Window,
+        },
+        solvers::validator::{is_valid, validate_grid, validate_grid_with},
+        test::util::general_tests,
+    };
 
     #[test]
     fn test_generator() {
@@ -117,4 +233,200 @@ mod tests {
 
         assert_eq!(grid.count_determined(), 81);
     }
+
+    #[test]
+    fn test_generate_unique() {
+        let mut generator = Generator::new_with_seed(77143266753986);
+        let grid = generator.generate_puzzle();
+
+        assert!(grid.count_determined() < 81);
+        assert!(SolutionCounter::is_unique(&grid));
+
+        let solution = &mut general_tests::filled_sudoku();
+        generator.remove_cells_unique(solution);
+        assert!(solution.count_determined() < 81);
+        assert!(SolutionCounter::is_unique(solution));
+    }
+
+    #[test]
+    fn test_generate_unique_jigsaw() {
+        let layout: Jigsaw = general_tests::JIGSAW_LAYOUT.parse().unwrap();
+        let rng = StdRng::seed_from_u64(77143266753986);
+        let mut generator = Generator::new_with_board(rng, JigsawGrid::new(layout));
+        let grid = generator.generate_puzzle();
+
+        println!("{}", grid);
+
+        assert!(grid.count_determined_cells() < 81);
+        assert!(SolutionCounter::is_unique(&grid));
+
+        let solution = &SolutionCounter::find_with(&grid, &Default::default(), 1)[0];
+        assert!(is_valid(solution));
+        for region in layout.regions() {
+            for mark in Mark::iter() {
+                assert_eq!(region.count_determined_value(solution, mark.to_value()), 1);
+            }
+        }
+    }
+
+    #[test]
+    fn test_generate_unique_windoku() {
+        let mut generator = Generator::new_with_seed(34598273645);
+        let constraints = windoku();
+        let grid = generator
+            .generate_puzzle_with(&constraints, Some(Duration::from_secs(60)))
+            .unwrap();
+
+        println!("{}", grid);
+
+        assert!(SolutionCounter::is_unique_with(&grid, &constraints));
+
+        let solution = &SolutionCounter::find_with(&grid, &constraints, 1)[0];
+        assert!(validate_grid_with(solution, &constraints).is_ok());
+        for window in Window::iter_visible() {
+            for mark in Mark::iter() {
+                assert_eq!(window.count_determined_value(solution, mark.to_value()), 1);
+            }
+        }
+    }
+
+    #[test]
+    fn test_generate_anti_knight() {
+        let constraints = ConstraintSet::new().with(AntiKnight).with(AntiKing);
+        let mut generator = Generator::new_with_seed(85822788013146);
+        let grid = generator
+            .generate_puzzle_with(&constraints, Some(Duration::from_secs(60)))
+            .unwrap();
+
+        println!("{}", grid);
+
+        assert!(SolutionCounter::is_unique_with(&grid, &constraints));
+
+        let solution = &SolutionCounter::find_with(&grid, &constraints, 1)[0];
+        assert!(validate_grid_with(solution, &constraints).is_ok());
+    }
+
+    #[test]
+    fn test_generate_unique_diagonal() {
+        let constraints = sudoku_x();
+        let mut generator = Generator::new_with_seed(85822788013146);
+        let grid = generator
+            .generate_puzzle_with(&constraints, Some(Duration::from_secs(60)))
+            .unwrap();
+
+        println!("{}", grid);
+
+        assert!(grid.count_determined() < 81);
+        assert!(SolutionCounter::is_unique_with(&grid, &constraints));
+
+        let solution = &SolutionCounter::find_with(&grid, &constraints, 1)[0];
+        assert!(validate_grid_with(solution, &constraints).is_ok());
+        for diagonal in Diagonal::iter_diagonals() {
+            for mark in Mark::iter() {
+                assert_eq!(
+                    diagonal.count_determined_value(solution, mark.to_value()),
+                    1
+                );
+            }
+        }
+    }
+
+    /// Cages over the filled sudoku: pairs along the rows, and pairs down the last column
+    fn domino_killer() -> Killer {
+        let solution = general_tests::filled_sudoku();
+        let value = |coord: Coord| solution.get_cell_at(coord).get_value();
+        let mut cages = Vec::new();
+
+        for row in 0..9 {
+            for col in [0, 2, 4, 6] {
+                let cells = vec![Coord::new(row, col), Coord::new(row, col + 1)];
+                cages.push(Cage::new(cells.iter().map(|c| value(*c)).sum(), cells).unwrap());
+            }
+        }
+        for row in [0, 2, 4, 6] {
+            let cells = vec![Coord::new(row, 8), Coord::new(row + 1, 8)];
+            cages.push(Cage::new(cells.iter().map(|c| value(*c)).sum(), cells).unwrap());
+        }
+        cages.push(Cage::new(value(Coord::new(8, 8)), vec![Coord::new(8, 8)]).unwrap());
+
+        Killer::new(cages)
+    }
+
+    /// The Kropki dots between every pair of neighbors in the filled sudoku, no dot means neither
+    fn full_kropki() -> Kropki {
+        let solution = general_tests::filled_sudoku();
+        let value = |coord: Coord| solution.get_cell_at(coord).get_value();
+        let mut dots = Vec::new();
+
+        for (a, b) in iter_orthogonal_pairs() {
+            let (va, vb) = (value(a), value(b));
+            if va.abs_diff(vb) == 1 {
+                dots.push(Dot::new(DotKind::White, a, b));
+            } else if va == vb * 2 || vb == va * 2 {
+                dots.push(Dot::new(DotKind::Black, a, b));
+            }
+        }
+
+        Kropki::new(dots).with_negative()
+    }
+
+    #[test]
+    fn test_generate_with_killer() {
+        let constraints = ConstraintSet::new().with(domino_killer());
+        let mut generator = Generator::new_with_seed(85822788013146);
+        let grid = generator
+            .generate_puzzle_with(&constraints, Some(Duration::from_secs(60)))
+            .unwrap();
+
+        println!("{}", grid);
+
+        assert!(grid.count_determined() < 81);
+        assert!(SolutionCounter::is_unique_with(&grid, &constraints));
+        let solution = &SolutionCounter::find_with(&grid, &constraints, 1)[0];
+        assert!(validate_grid_with(solution, &constraints).is_ok());
+    }
+
+    #[test]
+    fn test_generate_without_givens() {
+        let constraints = ConstraintSet::new()
+            .with(domino_killer())
+            .with(full_kropki());
+        let mut generator = Generator::new_with_seed(85822788013146);
+        let grid = generator
+            .generate_puzzle_with(&constraints, Some(Duration::from_secs(60)))
+            .unwrap();
+
+        // The cages and dots force the filled sudoku by themselves
+        assert_eq!(grid.count_determined(), 0);
+        let solution = &SolutionCounter::find_with(&grid, &constraints, 2);
+        assert_eq!(solution, &vec![general_tests::filled_sudoku()]);
+    }
+
+    #[test]
+    fn test_generate_with_constraints() {
+        let thermometer = Thermometer::new((0..5).map(|i| Coord::new(i, i + 1)).collect()).unwrap();
+        let dot = Dot::new(DotKind::Black, Coord::new(4, 0), Coord::new(5, 0));
+        let constraints = sudoku_x().with(thermometer).with(Kropki::new(vec![dot]));
+        let mut generator = Generator::new_with_seed(34598273645);
+        let grid = generator
+            .generate_puzzle_with(&constraints, Some(Duration::from_secs(60)))
+            .unwrap();
+
+        println!("{}", grid);
+
+        assert!(grid.count_determined() < 81);
+        assert!(SolutionCounter::is_unique_with(&grid, &constraints));
+        let solution = &SolutionCounter::find_with(&grid, &constraints, 1)[0];
+        assert!(validate_grid_with(solution, &constraints).is_ok());
+        assert!(validate_grid(solution).is_ok());
+    }
+
+    #[test]
+    fn test_generate_out_of_time() {
+        let constraints = ConstraintSet::new().with(NonConsecutive::new());
+        let mut generator = Generator::new_with_seed(34598273645);
+
+        let result = generator.generate_puzzle_with(&constraints, Some(Duration::ZERO));
+        assert!(result.is_err());
+    }
 }
@@ -0,0 +1,159 @@
+use std::fmt::Debug;
+
+use super::{cell::Cell, cell_collection::CellCollection, coords::Coord, grid::Grid, mark::Mark};
+
+/// A cell of a [`Board`], holding either a value or the values that are still possible.
+///
+/// The candidates are read as bits, bit `n - 1` is set if value `n` is possible.
+pub trait BoardCell: Debug + Clone + Copy + PartialEq {
+    /// Creates a new cell where every value of the grid is possible
+    fn new() -> Self;
+    /// Creates a new cell with the given value and no candidates
+    fn new_with_value(value: u16) -> Self;
+    /// Returns true if the cell has a value
+    fn is_determined(&self) -> bool;
+    /// Returns the value of the cell, if it is determined
+    fn value(&self) -> Option<u16>;
+    /// Returns the bits of the candidates, bit `n - 1` is set if value `n` is possible
+    fn candidates(&self) -> u32;
+    /// Removes the value from the candidates
+    fn unset_possible_value(&mut self, value: u16);
+    /// Returns an iterator over the candidates, from low to high
+    fn iter_possible_values(&self) -> impl Iterator<Item = u16>;
+
+    /// Returns the amount of candidates
+    fn possible_count(&self) -> u32 {
+        self.candidates().count_ones()
+    }
+
+    /// Returns true if the given value is a candidate of the cell
+    fn is_possible_value(&self, value: u16) -> bool {
+        self.candidates() & (1 << (value - 1)) != 0
+    }
+}
+
+/// A grid the solvers can work on, made of rows, columns and boxes that must each contain every value once.
+///
+/// The standard [`Grid`] is a board, as are the [`SizedGrid`](crate::sized::grid::SizedGrid) of any other size
+/// and the [`JigsawGrid`](super::jigsaw_grid::JigsawGrid) with irregular regions.
+pub trait Board: CellCollection + Clone + PartialEq {
+    /// The cells the board is made of
+    type Cell: BoardCell;
+    /// The boxes of the board, the collections next to the rows and columns that must contain every value
+    type Box: CellCollection<Dimensions = Self::Dimensions>;
+
+    /// Retrieves the cell at the given index
+    fn get_cell(&self, index: usize) -> &Self::Cell;
+    /// Sets the cell at the given index
+    fn set_cell(&mut self, index: usize, cell: &Self::Cell);
+    /// Places the value at the coord and removes it from the candidates of its row, column and box
+    fn place_value_at(&mut self, coord: Coord<Self::Dimensions>, value: u16);
+    /// Removes the value from the candidates of the row, column and box of the coord
+    fn mark_off(&mut self, coord: Coord<Self::Dimensions>, value: u16);
+    /// Returns the boxes of the board, the iterator does not borrow the board so it can change while iterating
+    fn boxes(&self) -> impl Iterator<Item = Self::Box> + use<Self>;
+
+    /// Returns true if every box spans whole segments of the rows and columns it covers.
+    ///
+    /// Solvers that reason about the overlap between boxes and lines rely on this.
+    fn has_aligned_boxes(&self) -> bool {
+        true
+    }
+
+    /// Returns the standard grid, used to explain the solving steps which are written for 9x9 grids with squares
+    fn as_grid(&self) -> Option<&Grid> {
+        None
+    }
+
+    /// Retrieves the cell at the given coordinate
+    fn get_cell_at(&self, coord: Coord<Self::Dimensions>) -> &Self::Cell {
+        self.get_cell(coord.get_index())
+    }
+
+    /// Sets the cell at the given coordinate
+    fn set_cell_at(&mut self, coord: Coord<Self::Dimensions>, cell: &Self::Cell) {
+        self.set_cell(coord.get_index(), cell);
+    }
+
+    /// Returns the amount of cells that have a value
+    fn count_determined_cells(&self) -> usize {
+        self.iter()
+            .filter(|i| self.get_cell(*i).is_determined())
+            .count()
+    }
+
+    /// Removes the value from the candidates of the cell at the coord
+    fn unset_possible_value_at(&mut self, coord: Coord<Self::Dimensions>, value: u16) {
+        let mut cell = *self.get_cell_at(coord);
+        cell.unset_possible_value(value);
+        self.set_cell_at(coord, &cell);
+    }
+}
+
+impl BoardCell for Cell {
+    #[inline]
+    fn new() -> Self {
+        Cell::new()
+    }
+
+    #[inline]
+    fn new_with_value(value: u16) -> Self {
+        Cell::new_with_value(value)
+    }
+
+    #[inline]
+    fn is_determined(&self) -> bool {
+        Cell::is_determined(*self)
+    }
+
+    #[inline]
+    fn value(&self) -> Option<u16> {
+        Cell::value(*self)
+    }
+
+    #[inline]
+    fn candidates(&self) -> u32 {
+        (self.only_possible().get_value() >> 7) as u32
+    }
+
+    #[inline]
+    fn unset_possible_value(&mut self, value: u16) {
+        self.unset_possible(Mark::from_value(value));
+    }
+
+    #[inline]
+    fn iter_possible_values(&self) -> impl Iterator<Item = u16> {
+        self.only_possible()
+            .iter_possible()
+            .map(|mark| mark.to_value())
+    }
+
+    #[inline]
+    fn possible_count(&self) -> u32 {
+        Cell::possible_count(*self)
+    }
+
+    #[inline]
+    fn is_possible_value(&self, value: u16) -> bool {
+        Cell::is_possible_value(*self, value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::BoardCell;
+    use crate::grid::{cell::Cell, mark::Mark};
+
+    #[test]
+    fn test_cell_candidates() {
+        let mut cell = Cell::new_with_possible(Mark::N1);
+        cell.set_possible(Mark::N9);
+        assert_eq!(cell.candidates(), 0b1_0000_0001);
+        assert_eq!(cell.iter_possible_values().collect::<Vec<_>>(), vec![1, 9]);
+
+        cell.unset_possible_value(9);
+        assert_eq!(cell, Cell::new_with_possible(Mark::N1));
+        assert_eq!(<Cell as BoardCell>::new(), Cell::new());
+        assert_eq!(BoardCell::value(&Cell::new_with_value(4)), Some(4));
+    }
+}
@@ -0,0 +1,316 @@
+use std::cmp::Ordering;
+
+use super::{
+    cell_collection::CellCollection, coords::Coord, grid::Grid, transform::Transform,
+    utility::utility,
+};
+
+type Permutation = [usize; 9];
+type Values = [[u8; 9]; 9];
+
+/// The minimal lexicographic representative of a grid, together with the transformation that produces it
+#[derive(Debug, Clone, Copy)]
+pub struct Canonical {
+    /// The canonical grid
+    pub grid: Grid,
+    /// The transformation that turns the original grid into the canonical grid
+    pub transform: Transform,
+}
+
+impl Canonical {
+    /// Returns the canonical grid as a string of 81 digits, usable as a key to find duplicates
+    pub fn key(&self) -> String {
+        utility::to_digits(&self.grid)
+    }
+}
+
+/// Computes the minimal lexicographic form (minlex) of the given grid.
+///
+/// Only the determined values are taken into account, empty cells read as 0. Out of all the
+/// 3,359,232 geometric transformations and 9! relabelings the one producing the smallest
+/// row by row digit string is returned.
+pub fn canonical_form(grid: &Grid) -> Canonical {
+    if grid.count_determined() == 0 {
+        return Canonical {
+            grid: *grid,
+            transform: Transform::identity(),
+        };
+    }
+
+    let mut search = Search::new(grid);
+    search.run();
+
+    let transform = search.transform.unwrap_or_default();
+
+    Canonical {
+        grid: transform.apply(grid),
+        transform,
+    }
+}
+
+/// Returns the canonical key of the given grid, grids with the same key are equivalent
+pub fn canonical_key(grid: &Grid) -> String {
+    canonical_form(grid).key()
+}
+
+/// Returns true if the two grids are transformations of each other
+pub fn is_equivalent(a: &Grid, b: &Grid) -> bool {
+    find_equivalence(a, b).is_some()
+}
+
+/// Returns a transformation that turns grid `a` into grid `b` if the grids are equivalent
+pub fn find_equivalence(a: &Grid, b: &Grid) -> Option<Transform> {
+    let ca = canonical_form(a);
+    let cb = canonical_form(b);
+
+    if ca.key() != cb.key() {
+        return None;
+    }
+
+    Some(ca.transform.then(&cb.transform.inverse()))
+}
+
+struct Search {
+    /// The values of the grid, once as is and once transposed
+    values: [Values; 2],
+    col_perms: Vec<Permutation>,
+    best: [u8; 81],
+    has_best: bool,
+    transform: Option<Transform>,
+}
+
+/// The state of a single branch in the search
+#[derive(Clone, Copy)]
+struct Branch {
+    transpose: usize,
+    rows: Permutation,
+    cols: Permutation,
+    /// The new label for each value, 0 if not labeled yet
+    labels: [u8; 10],
+    next_label: u8,
+    improved: bool,
+}
+
+impl Search {
+    fn new(grid: &Grid) -> Self {
+        let mut values = [[[0u8; 9]; 9]; 2];
+
+        for index in grid.iter() {
+            let coord = Coord::from_index(index);
+            let (row, col) = coord.get_row_col();
+            let value = grid.get_cell_at(coord).value().unwrap_or(0) as u8;
+
+            values[0][row][col] = value;
+            values[1][col][row] = value;
+        }
+
+        Self {
+            values,
+            col_perms: band_preserving_permutations(),
+            best: [0; 81],
+            has_best: false,
+            transform: None,
+        }
+    }
+
+    fn run(&mut self) {
+        let col_perms = std::mem::take(&mut self.col_perms);
+
+        for transpose in 0..2 {
+            for cols in col_perms.iter() {
+                let mut branch = Branch {
+                    transpose,
+                    rows: [0; 9],
+                    cols: *cols,
+                    labels: [0; 10],
+                    next_label: 1,
+                    improved: false,
+                };
+
+                for source in 0..9 {
+                    self.try_row(&mut branch, 0, source);
+                }
+            }
+        }
+    }
+
+    /// Places the source row at the given destination row, and continues the search if it is not worse than the best
+    fn try_row(&mut self, parent: &mut Branch, row: usize, source: usize) {
+        let mut branch = *parent;
+        branch.rows[row] = source;
+
+        let mut ordering = match self.has_best && !parent.improved {
+            true => Ordering::Equal,
+            false => Ordering::Less,
+        };
+        let mut line = [0u8; 9];
+
+        for (col, label) in line.iter_mut().enumerate() {
+            let value = self.values[branch.transpose][source][branch.cols[col]] as usize;
+            if value != 0 && branch.labels[value] == 0 {
+                branch.labels[value] = branch.next_label;
+                branch.next_label += 1;
+            }
+            *label = branch.labels[value];
+
+            if ordering == Ordering::Equal {
+                ordering = (*label).cmp(&self.best[row * 9 + col]);
+                if ordering == Ordering::Greater {
+                    return;
+                }
+            }
+        }
+
+        if ordering == Ordering::Less {
+            self.best[row * 9..row * 9 + 9].copy_from_slice(&line);
+            branch.improved = true;
+        }
+
+        if row == 8 {
+            self.finish(&branch);
+            // Everything after this is compared against the full best
+            parent.improved = false;
+            return;
+        }
+
+        self.next_rows(&mut branch, row + 1);
+        parent.improved = false;
+    }
+
+    /// Tries all the possible source rows for the given destination row
+    fn next_rows(&mut self, branch: &mut Branch, row: usize) {
+        if !row.is_multiple_of(3) {
+            // Stay inside of the band of the first row of this band
+            let band = branch.rows[row - row % 3] / 3;
+            for source in band * 3..band * 3 + 3 {
+                if !branch.rows[row - row % 3..row].contains(&source) {
+                    self.try_row(branch, row, source);
+                }
+            }
+            return;
+        }
+
+        for band in 0..3 {
+            let used = (0..row).step_by(3).any(|r| branch.rows[r] / 3 == band);
+            if used {
+                continue;
+            }
+
+            for source in band * 3..band * 3 + 3 {
+                self.try_row(branch, row, source);
+            }
+        }
+    }
+
+    fn finish(&mut self, branch: &Branch) {
+        if self.has_best && !branch.improved {
+            return;
+        }
+        self.has_best = true;
+
+        // Digits that do not appear in the grid get the remaining labels in order
+        let mut labels = branch.labels;
+        let mut next = branch.next_label;
+        for label in labels.iter_mut().skip(1) {
+            if *label == 0 {
+                *label = next;
+                next += 1;
+            }
+        }
+
+        let mut digits = [0; 9];
+        for value in 1..10 {
+            digits[value - 1] = labels[value] as usize - 1;
+        }
+
+        self.transform =
+            Transform::from_parts(branch.transpose == 1, branch.rows, branch.cols, digits);
+    }
+}
+
+/// Returns all the permutations that keep rows inside of their band
+fn band_preserving_permutations() -> Vec<Permutation> {
+    const ORDERS: [[usize; 3]; 6] = [
+        [0, 1, 2],
+        [0, 2, 1],
+        [1, 0, 2],
+        [1, 2, 0],
+        [2, 0, 1],
+        [2, 1, 0],
+    ];
+    let mut result = Vec::with_capacity(6 * 6 * 6 * 6);
+
+    for blocks in ORDERS.iter() {
+        for first in ORDERS.iter() {
+            for second in ORDERS.iter() {
+                for third in ORDERS.iter() {
+                    let mut perm = [0; 9];
+                    let offsets = [first, second, third];
+
+                    for block in 0..3 {
+                        for offset in 0..3 {
+                            perm[block * 3 + offset] = blocks[block] * 3 + offsets[block][offset];
+                        }
+                    }
+
+                    result.push(perm);
+                }
+            }
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::{canonical_form, canonical_key, find_equivalence, is_equivalent};
+    use crate::{
+        grid::{transform::Transform, utility::utility},
+        test::util::general_tests,
+    };
+
+    const PUZZLE: &str =
+        "003020600900305001001806400008102900700000008006708200002609500800203009005010300";
+
+    #[test]
+    fn test_filled_is_minimal() {
+        let grid = general_tests::filled_sudoku();
+        let canonical = canonical_form(&grid);
+
+        // The first row of a canonical solution is always 1 to 9
+        assert!(canonical.key().starts_with("123456789"));
+        assert_eq!(canonical.transform.apply(&grid), canonical.grid);
+    }
+
+    #[test]
+    fn test_transformed_puzzle_same_key() {
+        let grid = utility::from_digit(PUZZLE).unwrap();
+        let key = canonical_key(&grid);
+        let mut rng = StdRng::seed_from_u64(77143266753986);
+
+        for _ in 0..3 {
+            let transformed = Transform::random(&mut rng).apply(&grid);
+
+            assert_eq!(canonical_key(&transformed), key);
+        }
+    }
+
+    #[test]
+    fn test_find_equivalence() {
+        let grid = utility::from_digit(PUZZLE).unwrap();
+        let mut rng = StdRng::seed_from_u64(85822788013146);
+        let other = Transform::random(&mut rng).apply(&grid);
+
+        let transform = find_equivalence(&grid, &other).expect("grids should be equivalent");
+        assert_eq!(
+            utility::to_digits(&transform.apply(&grid)),
+            utility::to_digits(&other)
+        );
+
+        let filled = general_tests::filled_sudoku();
+        assert!(!is_equivalent(&grid, &filled));
+    }
+}
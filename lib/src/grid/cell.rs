@@ -3,7 +3,7 @@ use std::{
     ops::{BitAnd, BitOr, BitXor},
 };
 
-use super::mark::Mark;
+use super::mark::{InvalidValueError, Mark};
 
 // If the cell is determined, the value is stored here
 // pppp pppp p000 vvvv
@@ -50,14 +50,28 @@ impl Cell {
         }
     }
 
-    /// Creates a new cell with a value, and all possibilities off
+    /// Creates a new cell with no value, and all possibilities off
     pub const fn new_empty() -> Cell {
         Cell::new_with_value(0)
     }
 
     /// Creates a new cell with a value, and all possibilities off
-    /// Assumes the value is between 1 and 9
-    pub const fn new_with_value(data: InnerCell) -> Cell {
+    /// Panics if the value is above 9, a value of 0 results in an empty cell
+    pub const fn new_with_value(value: InnerCell) -> Cell {
+        assert!(value <= 9, "Value out of range");
+        Cell { data: value }
+    }
+
+    /// Creates a new cell with a value, and all possibilities off
+    /// Returns an error if the value is not between 1 and 9
+    pub fn try_new_with_value(value: u16) -> Result<Cell, InvalidValueError> {
+        let mark = Mark::try_from(value)?;
+
+        Ok(Cell::new_from_mark_as_value(mark))
+    }
+
+    /// Creates a cell from its raw data, used to build masks
+    pub const fn from_data(data: InnerCell) -> Cell {
         Cell { data }
     }
 
@@ -65,7 +79,7 @@ impl Cell {
     pub const fn new_with_possible(mark: Mark) -> Cell {
         let v = mark.to_data();
 
-        Self::new_with_value(v)
+        Self::from_data(v)
     }
 
     /// Creates a new cell with a mark as a value, and all possibilities off
@@ -75,6 +89,20 @@ impl Cell {
         Self::new_with_value(v)
     }
 
+    /// Creates a new cell with only the even values as possibilities, used as a mask
+    pub const fn new_even() -> Cell {
+        Cell {
+            data: 0b0101_0101_0000_0000,
+        }
+    }
+
+    /// Creates a new cell with only the odd values as possibilities, used as a mask
+    pub const fn new_odd() -> Cell {
+        Cell {
+            data: 0b1010_1010_1000_0000,
+        }
+    }
+
     /// Creates a new cell with a mark as a value, and all possibilities off
     pub const fn mask() -> Cell {
         Cell {
@@ -115,9 +143,17 @@ impl Cell {
     }
 
     /// Stores the given value in the cell, sets all possibilities off
-    /// Assumes the value is between 1 and 9
+    /// Panics if the value is above 9
     pub fn set_value(&mut self, value: u16) {
-        self.data = value as InnerCell;
+        *self = Cell::new_with_value(value);
+    }
+
+    /// Stores the given value in the cell, sets all possibilities off
+    /// Returns an error and leaves the cell untouched if the value is not between 1 and 9
+    pub fn try_set_value(&mut self, value: u16) -> Result<(), InvalidValueError> {
+        *self = Cell::try_new_with_value(value)?;
+
+        Ok(())
     }
 
     /// Returns the value of this cell
@@ -160,12 +196,12 @@ impl Cell {
 
     /// Filters all possibilities from this cell, removing the value
     pub fn only_possible(&self) -> Self {
-        self.clone() & Cell::new_with_value(CELL_POSSIBLE_MASK)
+        self.clone() & Cell::from_data(CELL_POSSIBLE_MASK)
     }
 
     /// Filters out all possibilities from this cell, keeping the value
     pub fn only_determined(&self) -> Self {
-        self.clone() & Cell::new_with_value(CELL_VALUE_MASK)
+        self.clone() & Cell::from_data(CELL_VALUE_MASK)
     }
 
     /// Iterates over all possible values for this cell
@@ -318,6 +354,16 @@ mod test {
         assert_eq!(iter.next(), None);
     }
 
+    #[test]
+    pub fn test_parity_masks() {
+        let even: Vec<Mark> = Cell::new_even().iter_possible().collect();
+        let odd: Vec<Mark> = Cell::new_odd().iter_possible().collect();
+
+        assert_eq!(even, vec![Mark::N2, Mark::N4, Mark::N6, Mark::N8]);
+        assert_eq!(odd, vec![Mark::N1, Mark::N3, Mark::N5, Mark::N7, Mark::N9]);
+        assert_eq!(Cell::new_even() | Cell::new_odd(), Cell::new());
+    }
+
     #[test]
     pub fn test_determined() {
         for i in 1..=9 {
@@ -328,6 +374,21 @@ mod test {
         }
     }
 
+    #[test]
+    pub fn test_try_new_with_value() {
+        for i in 1..=9 {
+            let cell = Cell::try_new_with_value(i).unwrap();
+            assert_eq!(cell.value(), Some(i));
+        }
+
+        assert!(Cell::try_new_with_value(0).is_err());
+        assert!(Cell::try_new_with_value(10).is_err());
+
+        let mut cell = Cell::new();
+        assert!(cell.try_set_value(0x40).is_err());
+        assert_eq!(cell, Cell::new());
+    }
+
     #[test]
     pub fn test_not_determined() {
         let cell = Cell::new();
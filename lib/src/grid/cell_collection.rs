@@ -1,28 +1,47 @@
-use super::{coords::Coord, grid::Grid, mark::Mark};
+use super::{
+    board::{Board, BoardCell},
+    coords::Coord,
+    dimensions::Dimensions,
+    mark::Mark,
+};
 
 /// A trait for collection of cells
 pub trait CellCollection {
+    /// The dimensions of the grid the cells belong to
+    type Dimensions: Dimensions;
+
     /// Sets the cell at the given index
-    fn get_coord(&self, index: usize) -> Coord;
+    fn get_coord(&self, index: usize) -> Coord<Self::Dimensions>;
     /// Returns a new iterator over the cells in this collection
     fn iter(&self) -> std::ops::Range<usize>;
     /// Returns the maximum number of cells in this collection
     fn max(&self) -> usize;
 
-    fn count_possible(&self, grid: &Grid, mark: Mark) -> usize {
+    fn count_possible<G: Board<Dimensions = Self::Dimensions>>(
+        &self,
+        grid: &G,
+        mark: Mark,
+    ) -> usize {
+        self.count_possible_value(grid, mark.to_value())
+    }
+
+    /// Counts the cells where the value is still a candidate
+    fn count_possible_value<G: Board<Dimensions = Self::Dimensions>>(
+        &self,
+        grid: &G,
+        value: u16,
+    ) -> usize {
         let mut count = 0;
 
         for i in self.iter() {
             let coord = self.get_coord(i);
-            if grid.is_possible_at(coord, mark) {
-                count += 1;
-            }
+            count += grid.get_cell_at(coord).is_possible_value(value) as usize;
         }
 
         count
     }
 
-    fn count_determined(&self, grid: &Grid) -> usize {
+    fn count_determined<G: Board<Dimensions = Self::Dimensions>>(&self, grid: &G) -> usize {
         let mut count = 0;
 
         for i in self.iter() {
@@ -36,17 +55,17 @@ pub trait CellCollection {
         count
     }
 
-    fn count_determined_value(&self, grid: &Grid, value: u16) -> usize {
+    fn count_determined_value<G: Board<Dimensions = Self::Dimensions>>(
+        &self,
+        grid: &G,
+        value: u16,
+    ) -> usize {
         let mut count = 0;
 
+        // Counting without branches is faster than skipping the cells that do not match
         for i in self.iter() {
             let coord = self.get_coord(i);
-            let cell = grid.get_cell_at(coord);
-            if let Some(v) = cell.value() {
-                if v == value {
-                    count += 1;
-                }
-            }
+            count += (grid.get_cell_at(coord).value() == Some(value)) as usize;
         }
 
         count
@@ -1,42 +1,61 @@
+use std::marker::PhantomData;
+
 use super::{
-    cell_collection::CellCollection, constants::GRID_WIDTH, coords::Coord, square::Square,
+    cell_collection::CellCollection,
+    constants::GRID_WIDTH,
+    coords::Coord,
+    dimensions::{Dimensions, Nine},
+    square::Square,
 };
 
 #[derive(Debug, Clone, Copy, PartialEq)]
-pub struct Column {
+pub struct Column<D: Dimensions = Nine> {
     //The column index
     col: usize,
+    dimensions: PhantomData<D>,
 }
 
 impl Column {
     pub const fn new(col: usize) -> Self {
-        Self { col }
+        Column::at(col)
     }
 
     pub fn get_square(&self, index: usize) -> Square {
         Square::from(index, self.col)
     }
 
-    pub fn col_index(&self) -> usize {
-        self.col
-    }
-
     pub fn iter_col() -> impl Iterator<Item = Column> {
         (0..GRID_WIDTH).map(|i| Column::new(i))
     }
 }
 
-impl CellCollection for Column {
-    fn get_coord(&self, index: usize) -> Coord {
-        Coord::new(index, self.col)
+impl<D: Dimensions> Column<D> {
+    /// Creates the column with the given index in a grid of any size
+    pub const fn at(col: usize) -> Self {
+        Self {
+            col,
+            dimensions: PhantomData,
+        }
+    }
+
+    pub fn col_index(&self) -> usize {
+        self.col
+    }
+}
+
+impl<D: Dimensions> CellCollection for Column<D> {
+    type Dimensions = D;
+
+    fn get_coord(&self, index: usize) -> Coord<D> {
+        Coord::at(index, self.col)
     }
 
     fn iter(&self) -> std::ops::Range<usize> {
-        0..GRID_WIDTH
+        0..D::SIZE
     }
 
     fn max(&self) -> usize {
-        GRID_WIDTH
+        D::SIZE
     }
 }
 
@@ -1,12 +1,17 @@
-use std::fmt::{self, Display, Formatter};
+use std::{
+    fmt::{self, Display, Formatter},
+    marker::PhantomData,
+};
 
 use crate::grid::constants::GRID_SIZE;
 
-use super::constants::GRID_WIDTH;
+use super::dimensions::{Dimensions, Nine};
 
+/// The position of a cell in a grid with the given dimensions, the standard 9x9 grid unless specified
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub struct Coord {
+pub struct Coord<D: Dimensions = Nine> {
     index: usize,
+    dimensions: PhantomData<D>,
 }
 
 impl Coord {
@@ -19,17 +24,33 @@ impl Coord {
     /// Creates a new coord from an index
     pub const fn from_index(index: usize) -> Self {
         debug_assert!(index < GRID_SIZE, "Index out of bounds");
-        Coord { index: index }
+        Coord::at_index(index)
+    }
+}
+
+impl<D: Dimensions> Coord<D> {
+    /// Creates a new coord in a grid of any size
+    pub const fn at(row: usize, col: usize) -> Self {
+        Coord::at_index(row * D::SIZE + col)
+    }
+
+    /// Creates a new coord from an index in a grid of any size
+    pub const fn at_index(index: usize) -> Self {
+        debug_assert!(index < D::CELL_COUNT, "Index out of bounds");
+        Coord {
+            index,
+            dimensions: PhantomData,
+        }
     }
 
     /// Returns the index of the coord
     pub const fn get_row(self) -> usize {
-        self.index / GRID_WIDTH
+        self.index / D::SIZE
     }
 
     /// Returns the index of the coord
     pub const fn get_col(self) -> usize {
-        self.index % GRID_WIDTH
+        self.index % D::SIZE
     }
 
     /// Returns the index of the coord
@@ -42,23 +63,28 @@ impl Coord {
         (self.get_row(), self.get_col())
     }
 
+    /// Returns the coord in the standard rXcY notation, counting rows and columns from 1
+    pub fn notation(self) -> String {
+        format!("r{}c{}", self.get_row() + 1, self.get_col() + 1)
+    }
+
     /// Returns a new coord with the row and column offset by the given amount
     pub const fn offset_row(self, offset: usize) -> Self {
-        Coord::new(self.get_row() + offset, self.get_col())
+        Coord::at(self.get_row() + offset, self.get_col())
     }
 
     /// Returns a new coord with the row and column offset by the given amount
     pub const fn offset_col(self, offset: usize) -> Self {
-        Coord::new(self.get_row(), self.get_col() + offset)
+        Coord::at(self.get_row(), self.get_col() + offset)
     }
 
     /// Returns a new coord with the row and column offset by the given amount
     pub const fn offset(self, row_offset: usize, col_offset: usize) -> Self {
-        Coord::new(self.get_row() + row_offset, self.get_col() + col_offset)
+        Coord::at(self.get_row() + row_offset, self.get_col() + col_offset)
     }
 }
 
-impl Display for Coord {
+impl<D: Dimensions> Display for Coord<D> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(f, "[{}, {}]", self.get_row(), self.get_col())
     }
@@ -66,7 +92,7 @@ impl Display for Coord {
 
 #[cfg(test)]
 mod test {
-    use crate::grid::constants::GRID_HEIGHT_RANGE;
+    use crate::grid::{constants::GRID_HEIGHT_RANGE, dimensions::Sixteen};
 
     use super::*;
 
@@ -77,6 +103,13 @@ mod test {
         assert_eq!(coord.get_col(), 2);
     }
 
+    #[test]
+    fn test_notation() {
+        assert_eq!(Coord::new(0, 0).notation(), "r1c1");
+        assert_eq!(Coord::new(3, 1).notation(), "r4c2");
+        assert_eq!(Coord::new(8, 8).notation(), "r9c9");
+    }
+
     #[test]
     fn test_coord_from_index() {
         for row in GRID_HEIGHT_RANGE {
@@ -88,4 +121,12 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_sized_coord() {
+        let coord: Coord<Sixteen> = Coord::at(2, 13);
+        assert_eq!(coord.get_index(), 45);
+        assert_eq!(coord.get_row_col(), (2, 13));
+        assert_eq!(coord.notation(), "r3c14");
+    }
 }
@@ -0,0 +1,97 @@
+use std::fmt::{Display, Formatter};
+
+use super::{
+    cell_collection::CellCollection, constants::GRID_WIDTH, coords::Coord, dimensions::Nine,
+};
+
+/// One of the two main diagonals of the grid, used by the Sudoku-X variant
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Diagonal {
+    /// True for the diagonal from the top right to the bottom left
+    anti: bool,
+}
+
+impl Diagonal {
+    /// The diagonal from the top left to the bottom right
+    pub const fn main() -> Self {
+        Self { anti: false }
+    }
+
+    /// The diagonal from the top right to the bottom left
+    pub const fn anti() -> Self {
+        Self { anti: true }
+    }
+
+    /// Returns true if this is the diagonal from the top right to the bottom left
+    pub fn is_anti(&self) -> bool {
+        self.anti
+    }
+
+    /// Returns true if the coord is on this diagonal
+    pub const fn is_coord_in_diagonal(&self, coord: Coord) -> bool {
+        let (row, col) = coord.get_row_col();
+
+        match self.anti {
+            false => row == col,
+            true => row + col == GRID_WIDTH - 1,
+        }
+    }
+
+    /// Iterates over both diagonals
+    pub fn iter_diagonals() -> impl Iterator<Item = Diagonal> {
+        [Diagonal::main(), Diagonal::anti()].into_iter()
+    }
+
+    /// Iterates over the diagonals the coord is on, the center cell is on both
+    pub fn iter_at(coord: Coord) -> impl Iterator<Item = Diagonal> {
+        Diagonal::iter_diagonals().filter(move |d| d.is_coord_in_diagonal(coord))
+    }
+}
+
+impl CellCollection for Diagonal {
+    type Dimensions = Nine;
+
+    fn get_coord(&self, index: usize) -> Coord {
+        match self.anti {
+            false => Coord::new(index, index),
+            true => Coord::new(index, GRID_WIDTH - 1 - index),
+        }
+    }
+
+    fn iter(&self) -> std::ops::Range<usize> {
+        0..self.max()
+    }
+
+    fn max(&self) -> usize {
+        GRID_WIDTH
+    }
+}
+
+impl Display for Diagonal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.anti {
+            false => write!(f, "[Diagonal]"),
+            true => write!(f, "[Anti Diagonal]"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Diagonal;
+    use crate::grid::{cell_collection::CellCollection, coords::Coord};
+
+    #[test]
+    fn test_coords_on_diagonal() {
+        for diagonal in Diagonal::iter_diagonals() {
+            for index in diagonal.iter() {
+                assert!(diagonal.is_coord_in_diagonal(diagonal.get_coord(index)));
+            }
+        }
+
+        assert_eq!(Diagonal::anti().get_coord(0), Coord::new(0, 8));
+        assert_eq!(Diagonal::iter_at(Coord::new(4, 4)).count(), 2);
+        assert_eq!(Diagonal::iter_at(Coord::new(2, 6)).count(), 1);
+        assert_eq!(Diagonal::iter_at(Coord::new(2, 5)).count(), 0);
+    }
+}
@@ -0,0 +1,139 @@
+use std::{fmt::Debug, hash::Hash, sync::OnceLock};
+
+/// The largest number of values a grid supports
+pub const MAX_SIZE: usize = 25;
+
+/// The symbols used to write the values of a grid, value 1 is the first symbol
+pub const SYMBOLS: &[u8; MAX_SIZE] = b"123456789ABCDEFGHIJKLMNOP";
+
+/// The shape of a grid, determined by the size of its boxes.
+///
+/// A grid with boxes of `BOX_HEIGHT` rows and `BOX_WIDTH` columns has `BOX_HEIGHT * BOX_WIDTH` rows,
+/// columns, boxes and values. The shapes are types, so coords and grids of different sizes can not be mixed.
+pub trait Dimensions: Debug + Clone + Copy + PartialEq + Eq + Hash + Default + 'static {
+    /// The amount of rows in a box
+    const BOX_HEIGHT: usize;
+    /// The amount of columns in a box
+    const BOX_WIDTH: usize;
+    /// The amount of rows, columns, boxes and values
+    const SIZE: usize = Self::BOX_HEIGHT * Self::BOX_WIDTH;
+    /// The amount of cells in the grid
+    const CELL_COUNT: usize = Self::SIZE * Self::SIZE;
+
+    /// Returns the indexes of the cells that share a row, column or box with each cell, computed once
+    fn peer_table() -> &'static [Vec<usize>];
+
+    /// Returns the indexes of the cells that share a row, column or box with the given cell, without the cell itself
+    fn peers(index: usize) -> &'static [usize] {
+        &Self::peer_table()[index]
+    }
+
+    /// Returns the box of the cell at the given index, boxes are numbered row by row
+    fn box_of(index: usize) -> usize {
+        let (row, col) = (index / Self::SIZE, index % Self::SIZE);
+        let boxes_per_row = Self::SIZE / Self::BOX_WIDTH;
+
+        (row / Self::BOX_HEIGHT) * boxes_per_row + col / Self::BOX_WIDTH
+    }
+
+    /// Returns the symbol of the given value
+    fn symbol(value: u16) -> char {
+        SYMBOLS[value as usize - 1] as char
+    }
+
+    /// Returns the value of the given symbol, if it is a value of this grid
+    fn value_of(symbol: char) -> Option<u16> {
+        let upper = symbol.to_ascii_uppercase() as u8;
+        let position = SYMBOLS[..Self::SIZE].iter().position(|s| *s == upper)?;
+
+        Some(position as u16 + 1)
+    }
+}
+
+macro_rules! dimensions {
+    ($(#[$doc:meta])* $name:ident, $box_height:expr, $box_width:expr) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+        pub struct $name;
+
+        impl Dimensions for $name {
+            const BOX_HEIGHT: usize = $box_height;
+            const BOX_WIDTH: usize = $box_width;
+
+            fn peer_table() -> &'static [Vec<usize>] {
+                static PEERS: OnceLock<Vec<Vec<usize>>> = OnceLock::new();
+                PEERS.get_or_init(generate_peers::<$name>)
+            }
+        }
+    };
+}
+
+dimensions!(
+    /// 4x4 grid with 2x2 boxes
+    Four, 2, 2
+);
+dimensions!(
+    /// 6x6 grid with boxes of 2 rows and 3 columns
+    Six, 2, 3
+);
+dimensions!(
+    /// The standard 9x9 grid with 3x3 boxes
+    Nine, 3, 3
+);
+dimensions!(
+    /// 12x12 grid with boxes of 3 rows and 4 columns
+    Twelve, 3, 4
+);
+dimensions!(
+    /// 16x16 grid with 4x4 boxes
+    Sixteen, 4, 4
+);
+
+fn generate_peers<D: Dimensions>() -> Vec<Vec<usize>> {
+    let size = D::SIZE;
+
+    (0..D::CELL_COUNT)
+        .map(|index| {
+            let (row, col) = (index / size, index % size);
+
+            (0..D::CELL_COUNT)
+                .filter(|other| {
+                    let same_line = other / size == row || other % size == col;
+                    *other != index && (same_line || D::box_of(*other) == D::box_of(index))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Dimensions, Four, Nine, Six, Sixteen, Twelve};
+
+    fn expected_peers<D: Dimensions>() -> usize {
+        // Every cell sees its row, column and the rest of its box
+        2 * (D::SIZE - 1) + D::SIZE - D::BOX_HEIGHT - D::BOX_WIDTH + 1
+    }
+
+    #[test]
+    fn test_boxes() {
+        assert_eq!(Six::SIZE, 6);
+        assert_eq!(Six::box_of(8), 0);
+        assert_eq!(Six::box_of(9), 1);
+        assert_eq!(Six::box_of(12), 2);
+        assert_eq!(Twelve::CELL_COUNT, 144);
+
+        assert_eq!(Four::peers(0).len(), expected_peers::<Four>());
+        assert_eq!(Nine::peers(40).len(), expected_peers::<Nine>());
+        assert_eq!(Twelve::peers(0).len(), expected_peers::<Twelve>());
+        assert_eq!(Sixteen::peers(255).len(), expected_peers::<Sixteen>());
+        assert!(std::ptr::eq(Six::peers(7), Six::peers(7)));
+    }
+
+    #[test]
+    fn test_symbols() {
+        assert_eq!(Sixteen::symbol(10), 'A');
+        assert_eq!(Sixteen::value_of('g'), Some(16));
+        assert_eq!(Six::value_of('7'), None);
+    }
+}
@@ -1,15 +1,19 @@
-use std::{fmt::Display, ops::BitAnd};
+use std::{fmt::Display, ops::BitAnd, str::FromStr};
 
 use crate::grid::utility::utility;
 
 use super::{
+    board::Board,
     cell::Cell,
     cell_collection::CellCollection,
     column::Column,
     constants::{GRID_HEIGHT_RANGE, GRID_SIZE, GRID_WIDTH_RANGE},
     coords::Coord,
+    dimensions::Nine,
     grid_mask::get_unset_influence_mask,
     mark::Mark,
+    parse_error::ParseError,
+    region::Region,
     row::Row,
     square::Square,
 };
@@ -83,6 +87,11 @@ impl Grid {
         Square::from(row, col)
     }
 
+    /// Retrieves the square of the coord as a region
+    pub fn get_region_at(&self, coord: Coord) -> Region {
+        Region::from(self.get_square_at(coord))
+    }
+
     /// Returns true if the given value is present in this collection
     pub fn is_possible_at(&self, coord: Coord, mark: Mark) -> bool {
         self.get_cell_at(coord).is_possible(mark)
@@ -134,6 +143,11 @@ impl Grid {
         Square::iter_squares()
     }
 
+    /// Returns the squares as the regions that must contain 1 to 9
+    pub fn regions(&self) -> [Region; 9] {
+        std::array::from_fn(|i| Region::from(Square::from_square_index(i)))
+    }
+
     /// Iterates over all cells and counts the determined cells
     pub fn count_determined(&self) -> usize {
         let mut sum: usize = 0;
@@ -195,10 +209,15 @@ impl Grid {
         self.unset_possible_area(square, mark);
     }
 
+    /// Clearing off the region
+    pub fn mark_off_region(&mut self, region: &Region, mark: Mark) {
+        self.unset_possible_area(region, mark);
+    }
+
     /// Unset the given area
-    fn unset_possible_area<T: CellCollection>(&mut self, area: &T, mark: Mark) {
+    fn unset_possible_area<T: CellCollection<Dimensions = Nine>>(&mut self, area: &T, mark: Mark) {
         let mut mask = Cell::mask();
-        mask = mask & Cell::new_with_value(!mark.to_data());
+        mask = mask & Cell::from_data(!mark.to_data());
 
         for index in area.iter() {
             let coord = area.get_coord(index);
@@ -217,6 +236,8 @@ impl Default for Grid {
 }
 
 impl CellCollection for Grid {
+    type Dimensions = Nine;
+
     fn get_coord(&self, index: usize) -> Coord {
         Coord::from_index(index)
     }
@@ -230,12 +251,67 @@ impl CellCollection for Grid {
     }
 }
 
+impl Board for Grid {
+    type Cell = Cell;
+    type Box = Square;
+
+    #[inline]
+    fn get_cell(&self, index: usize) -> &Cell {
+        Grid::get_cell(self, index)
+    }
+
+    #[inline]
+    fn set_cell(&mut self, index: usize, cell: &Cell) {
+        Grid::set_cell(self, index, cell);
+    }
+
+    #[inline]
+    fn place_value_at(&mut self, coord: Coord, value: u16) {
+        Grid::place_value_at(self, coord, value);
+    }
+
+    #[inline]
+    fn mark_off(&mut self, coord: Coord, value: u16) {
+        Grid::mark_off(self, coord, value);
+    }
+
+    fn boxes(&self) -> impl Iterator<Item = Square> + use<> {
+        Square::iter_squares()
+    }
+
+    fn as_grid(&self) -> Option<&Grid> {
+        Some(self)
+    }
+
+    #[inline]
+    fn unset_possible_value_at(&mut self, coord: Coord, value: u16) {
+        self.unset_possible_at(coord, Mark::from_value(value));
+    }
+}
+
 impl Display for Grid {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", utility::ascii_grid(self))
     }
 }
 
+impl FromStr for Grid {
+    type Err = ParseError;
+
+    /// Parses a grid from either the ascii or the digit format
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        utility::parse_from_ascii(s)
+    }
+}
+
+impl TryFrom<&str> for Grid {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
 impl PartialEq for Grid {
     fn eq(&self, other: &Self) -> bool {
         for index in self.iter() {
@@ -270,6 +346,8 @@ mod tests {
         let grid = Grid::new();
 
         println!("Grid object size: {}", size_of_val(&grid));
+        // Only the cells, so copying a grid stays cheap
+        assert_eq!(size_of_val(&grid), 162);
     }
 
     #[test]
@@ -9,7 +9,7 @@ pub const INFLUENCE_MASK: [Grid; GRID_SIZE] = generate_influence_masks();
 pub const UNSET_INFLUENCE_MASK: [[Grid; GRID_SIZE]; 9] = generate_unset_influence_masks_all();
 
 pub fn get_unset_influence_mask(coord: Coord, value: u16) -> Grid {
-    debug_assert!(value >= 1 && value <= 9, "Value must be between 1 and 9");
+    assert!(value >= 1 && value <= 9, "Value must be between 1 and 9");
     let index = (value - 1) as usize;
 
     unsafe {
@@ -313,8 +313,8 @@ const fn generate_unset_influence_mask(coord: Coord, mark: Mark) -> Grid {
     let (row, col) = coord.get_row_col();
     let square = Square::from(row, col);
 
-    let cm = Cell::new_with_value(mark.to_data());
-    let cell_mask = Cell::new_with_value(Cell::mask().get_value() ^ cm.get_value());
+    let cm = Cell::from_data(mark.to_data());
+    let cell_mask = Cell::from_data(Cell::mask().get_value() ^ cm.get_value());
 
     //Row
     cells[Coord::new(row, 0).get_index()] = cell_mask;
@@ -368,7 +368,7 @@ const fn generate_set_possible_masks() -> [Grid; 9] {
 
 const fn generate_set_possible_mask(mark: Mark) -> Grid {
     let d = mark.to_data();
-    let mask = Cell::new_with_value(d);
+    let mask = Cell::from_data(d);
     Grid::from([mask; GRID_SIZE])
 }
 
@@ -390,6 +390,6 @@ const fn generate_unset_possible_masks() -> [Grid; 9] {
 
 const fn generate_unset_possible_mask(mark: Mark) -> Grid {
     let d = mark.to_data();
-    let mask = Cell::new_with_value(!d);
+    let mask = Cell::from_data(!d);
     Grid::from([mask; GRID_SIZE])
 }
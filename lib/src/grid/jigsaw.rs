@@ -0,0 +1,264 @@
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+use super::{
+    constants::{GRID_SIZE, GRID_WIDTH},
+    coords::Coord,
+    parse_error::{ParseError, ParseErrorKind},
+    region::Region,
+};
+
+/// The layout of a jigsaw sudoku, which replaces the squares with nine connected regions of nine cells.
+///
+/// The layout is written as 81 region numbers from 1 to 9, row by row, whitespace is ignored:
+///
+/// ```text
+/// 111222233
+/// 111222333
+/// 114223333
+/// ...
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Jigsaw {
+    /// The region index (0..9) of each cell
+    regions: [u8; GRID_SIZE],
+}
+
+impl Jigsaw {
+    /// Creates a layout from the region index (0..9) of each cell
+    pub fn new(regions: [u8; GRID_SIZE]) -> Result<Jigsaw, Box<dyn Error>> {
+        let mut counts = [0; 9];
+        for (index, region) in regions.iter().enumerate() {
+            if *region >= 9 {
+                let msg = format!("Invalid region {} at {}", region, Coord::from_index(index));
+                return Err(msg)?;
+            }
+            counts[*region as usize] += 1;
+        }
+
+        let layout = Jigsaw { regions };
+        for (region, count) in counts.iter().enumerate() {
+            if *count != 9 {
+                let msg = format!("Region {} has {} cells instead of 9", region + 1, count);
+                return Err(msg)?;
+            }
+            if !layout.is_connected(region) {
+                let msg = format!("Region {} is not connected", region + 1);
+                return Err(msg)?;
+            }
+        }
+
+        Ok(layout)
+    }
+
+    /// The layout of a standard sudoku, where the regions are the squares
+    pub const fn standard() -> Jigsaw {
+        let mut regions = [0; GRID_SIZE];
+        let mut index = 0;
+        while index < GRID_SIZE {
+            let (row, col) = Coord::from_index(index).get_row_col();
+            regions[index] = ((row / 3) * 3 + col / 3) as u8;
+            index += 1;
+        }
+
+        Jigsaw { regions }
+    }
+
+    /// Returns the index (0..9) of the region the coord is in
+    pub fn region_index(&self, coord: Coord) -> usize {
+        self.regions[coord.get_index()] as usize
+    }
+
+    /// Returns the region with the given index (0..9)
+    pub fn region(&self, index: usize) -> Region {
+        let mut coords = [Coord::new(0, 0); 9];
+        let cells = (0..GRID_SIZE).filter(|i| self.regions[*i] as usize == index);
+
+        for (coord, cell) in coords.iter_mut().zip(cells) {
+            *coord = Coord::from_index(cell);
+        }
+
+        Region::new(coords)
+    }
+
+    /// Returns the region the coord is in
+    pub fn region_at(&self, coord: Coord) -> Region {
+        self.region(self.region_index(coord))
+    }
+
+    /// Returns all nine regions, in order of their index
+    pub fn regions(&self) -> [Region; 9] {
+        let mut coords = [[Coord::new(0, 0); 9]; 9];
+        let mut counts = [0; 9];
+
+        for (index, region) in self.regions.iter().enumerate() {
+            let region = *region as usize;
+            coords[region][counts[region]] = Coord::from_index(index);
+            counts[region] += 1;
+        }
+
+        coords.map(Region::new)
+    }
+
+    fn is_connected(&self, region: usize) -> bool {
+        let start = match (0..GRID_SIZE).find(|i| self.regions[*i] as usize == region) {
+            Some(start) => start,
+            None => return false,
+        };
+        let mut visited = [false; GRID_SIZE];
+        let mut stack = vec![start];
+        let mut count = 0;
+        visited[start] = true;
+
+        while let Some(index) = stack.pop() {
+            count += 1;
+            let (row, col) = Coord::from_index(index).get_row_col();
+            let neighbors = [
+                (row > 0).then(|| index - GRID_WIDTH),
+                (row < 8).then(|| index + GRID_WIDTH),
+                (col > 0).then(|| index - 1),
+                (col < 8).then(|| index + 1),
+            ];
+
+            for next in neighbors.into_iter().flatten() {
+                if !visited[next] && self.regions[next] as usize == region {
+                    visited[next] = true;
+                    stack.push(next);
+                }
+            }
+        }
+
+        count == 9
+    }
+}
+
+impl Default for Jigsaw {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+impl FromStr for Jigsaw {
+    type Err = ParseError;
+
+    /// Parses the 81 region numbers from 1 to 9
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut regions = [0; GRID_SIZE];
+        let mut index = 0;
+        let (mut line, mut column) = (1, 0);
+
+        for c in s.chars() {
+            column += 1;
+            if c == '\n' {
+                line += 1;
+                column = 0;
+                continue;
+            }
+            if c.is_whitespace() {
+                continue;
+            }
+
+            let region = match c {
+                '1'..='9' => c.to_digit(10).unwrap_or_default() as u8 - 1,
+                _ => {
+                    let kind = ParseErrorKind::InvalidCharacter(c);
+                    return Err(ParseError::new(line, column, kind));
+                }
+            };
+            if index >= GRID_SIZE {
+                return Err(ParseError::new(line, column, ParseErrorKind::TooManyCells));
+            }
+
+            regions[index] = region;
+            index += 1;
+        }
+
+        if index < GRID_SIZE {
+            let kind = ParseErrorKind::TooFewCells {
+                found: index,
+                expected: GRID_SIZE,
+            };
+            return Err(ParseError::new(line, column + 1, kind));
+        }
+
+        Jigsaw::new(regions)
+            .map_err(|e| ParseError::new(1, 1, ParseErrorKind::Malformed(e.to_string())))
+    }
+}
+
+impl Display for Jigsaw {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (index, region) in self.regions.iter().enumerate() {
+            if index > 0 && index % GRID_WIDTH == 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", region + 1)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Jigsaw;
+    use crate::grid::{
+        cell_collection::CellCollection, coords::Coord, parse_error::ParseErrorKind, square::Square,
+    };
+    use crate::test::util::general_tests;
+
+    #[test]
+    fn test_parse() {
+        let layout: Jigsaw = general_tests::JIGSAW_LAYOUT.parse().unwrap();
+
+        assert_eq!(layout.region_index(Coord::new(2, 2)), 3);
+        assert_eq!(layout.region_index(Coord::new(0, 6)), 1);
+        assert!(layout
+            .region_at(Coord::new(3, 0))
+            .contains(Coord::new(0, 0)));
+        assert_eq!(layout.to_string().parse::<Jigsaw>().unwrap(), layout);
+
+        for (index, region) in layout.regions().iter().enumerate() {
+            assert_eq!(region, &layout.region(index));
+        }
+    }
+
+    #[test]
+    fn test_standard() {
+        let layout = Jigsaw::standard();
+
+        for square in Square::iter_squares() {
+            let region = layout.region(square.to_square_index());
+            for index in square.iter() {
+                assert!(region.contains(square.get_coord(index)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_invalid() {
+        // Swapping two far away cells disconnects both regions
+        let text = general_tests::JIGSAW_LAYOUT.replacen("111222233", "511222233", 1);
+        let text = text.replacen("444555666", "444515666", 1);
+        let error = text.parse::<Jigsaw>().unwrap_err();
+        assert!(matches!(error.kind, ParseErrorKind::Malformed(_)));
+
+        let error = general_tests::JIGSAW_LAYOUT
+            .replacen('1', "0", 1)
+            .parse::<Jigsaw>()
+            .unwrap_err();
+        assert_eq!(error.kind, ParseErrorKind::InvalidCharacter('0'));
+
+        let error = "1234".parse::<Jigsaw>().unwrap_err();
+        assert_eq!(
+            error.kind,
+            ParseErrorKind::TooFewCells {
+                found: 4,
+                expected: 81
+            }
+        );
+    }
+}
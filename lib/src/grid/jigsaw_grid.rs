@@ -0,0 +1,183 @@
+use std::{
+    fmt::{Display, Formatter},
+    sync::Arc,
+};
+
+use super::{
+    board::Board, cell::Cell, cell_collection::CellCollection, constants::GRID_SIZE, coords::Coord,
+    dimensions::Nine, grid::Grid, jigsaw::Jigsaw, mark::Mark, region::Region,
+};
+
+/// A grid where the squares are replaced by the regions of a jigsaw layout.
+///
+/// The layout is kept next to the cells and shared between the copies of the grid,
+/// so the standard [`Grid`] stays a plain array of cells.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JigsawGrid {
+    grid: Grid,
+    layout: Arc<Layout>,
+}
+
+/// The layout with its regions, computed once
+#[derive(Debug, PartialEq)]
+struct Layout {
+    jigsaw: Jigsaw,
+    regions: [Region; 9],
+}
+
+impl JigsawGrid {
+    /// Creates a new grid with the given layout, where every value is possible
+    pub fn new(layout: Jigsaw) -> JigsawGrid {
+        let layout = Layout {
+            jigsaw: layout,
+            regions: layout.regions(),
+        };
+
+        JigsawGrid {
+            grid: Grid::new(),
+            layout: Arc::new(layout),
+        }
+    }
+
+    /// Creates a grid with the given layout and places the values of the grid, the candidates follow the layout
+    pub fn from_values(values: &Grid, layout: Jigsaw) -> JigsawGrid {
+        let mut grid = JigsawGrid::new(layout);
+        for index in values.iter() {
+            if let Some(value) = values.get_cell(index).value() {
+                grid.place_value_at(Coord::from_index(index), value);
+            }
+        }
+
+        grid
+    }
+
+    /// Returns the layout of the regions
+    pub fn layout(&self) -> &Jigsaw {
+        &self.layout.jigsaw
+    }
+
+    /// Returns the cells of the grid, without the layout
+    pub fn grid(&self) -> &Grid {
+        &self.grid
+    }
+
+    /// Returns the region the coord is in
+    pub fn region_at(&self, coord: Coord) -> Region {
+        self.layout.regions[self.layout.jigsaw.region_index(coord)]
+    }
+
+    /// Returns the regions that must contain 1 to 9, in order of their index
+    pub fn regions(&self) -> [Region; 9] {
+        self.layout.regions
+    }
+}
+
+impl CellCollection for JigsawGrid {
+    type Dimensions = Nine;
+
+    fn get_coord(&self, index: usize) -> Coord {
+        Coord::from_index(index)
+    }
+
+    fn iter(&self) -> std::ops::Range<usize> {
+        0..GRID_SIZE
+    }
+
+    fn max(&self) -> usize {
+        GRID_SIZE
+    }
+}
+
+impl Board for JigsawGrid {
+    type Cell = Cell;
+    type Box = Region;
+
+    #[inline]
+    fn get_cell(&self, index: usize) -> &Cell {
+        self.grid.get_cell(index)
+    }
+
+    #[inline]
+    fn set_cell(&mut self, index: usize, cell: &Cell) {
+        self.grid.set_cell(index, cell);
+    }
+
+    fn place_value_at(&mut self, coord: Coord, value: u16) {
+        self.grid.set_cell_at(coord, &Cell::new_with_value(value));
+
+        self.mark_off(coord, value);
+    }
+
+    /// Marks off the value from the row, column and region of the coord
+    fn mark_off(&mut self, coord: Coord, value: u16) {
+        let mark = Mark::from_value(value);
+        let region = self.region_at(coord);
+
+        self.grid.mark_off_row(coord.get_row(), mark);
+        self.grid.mark_off_column(coord.get_col(), mark);
+        self.grid.mark_off_region(&region, mark);
+    }
+
+    fn boxes(&self) -> impl Iterator<Item = Region> + use<> {
+        self.layout.regions.into_iter()
+    }
+
+    fn has_aligned_boxes(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn unset_possible_value_at(&mut self, coord: Coord, value: u16) {
+        self.grid.unset_possible_at(coord, Mark::from_value(value));
+    }
+}
+
+impl Display for JigsawGrid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.grid)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::JigsawGrid;
+    use crate::{
+        grid::{
+            board::Board, cell_collection::CellCollection, coords::Coord, grid::Grid,
+            jigsaw::Jigsaw, mark::Mark,
+        },
+        test::util::general_tests,
+    };
+
+    #[test]
+    fn test_place_value() {
+        let layout: Jigsaw = general_tests::JIGSAW_LAYOUT.parse().unwrap();
+        let grid = &mut JigsawGrid::new(layout);
+        grid.place_value_at(Coord::new(2, 2), 5);
+
+        assert_eq!(layout.region(3).count_possible(grid, Mark::N5), 0);
+        // The square is no longer a region, only the row and column are marked off
+        assert!(grid.get_cell_at(Coord::new(0, 0)).is_possible(Mark::N5));
+        assert!(!grid.get_cell_at(Coord::new(2, 0)).is_possible(Mark::N5));
+        assert!(!grid.get_cell_at(Coord::new(3, 1)).is_possible(Mark::N5));
+
+        let values = &mut Grid::empty();
+        values.place_value_at(Coord::new(2, 2), 5);
+        assert_eq!(&JigsawGrid::from_values(values, layout), grid);
+
+        let standard = &mut Grid::new();
+        standard.place_value_at(Coord::new(2, 2), 5);
+        assert_ne!(grid.grid(), standard);
+    }
+
+    #[test]
+    fn test_boxes() {
+        let layout: Jigsaw = general_tests::JIGSAW_LAYOUT.parse().unwrap();
+        let grid = JigsawGrid::new(layout);
+
+        assert!(!grid.has_aligned_boxes());
+        assert!(grid.as_grid().is_none());
+        assert_eq!(grid.boxes().collect::<Vec<_>>(), layout.regions().to_vec());
+        assert_eq!(grid.region_at(Coord::new(3, 1)), layout.region(3));
+    }
+}
@@ -1,4 +1,5 @@
 use std::{
+    error::Error,
     fmt::{Display, Formatter},
     ops::Shl,
 };
@@ -17,6 +18,33 @@ pub enum Mark {
     N9 = 0b1000_0000_0000_0000, //256
 }
 
+/// All the marks, ordered by their value
+const MARKS: [Mark; 9] = [
+    Mark::N1,
+    Mark::N2,
+    Mark::N3,
+    Mark::N4,
+    Mark::N5,
+    Mark::N6,
+    Mark::N7,
+    Mark::N8,
+    Mark::N9,
+];
+
+/// The error returned when a value is not between 1 and 9
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidValueError {
+    pub value: u16,
+}
+
+impl Display for InvalidValueError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "value {} is not between 1 and 9", self.value)
+    }
+}
+
+impl Error for InvalidValueError {}
+
 impl Default for Mark {
     fn default() -> Self {
         Self::N1
@@ -32,17 +60,7 @@ impl Display for Mark {
 impl Mark {
     // Returns an iterator over all possible values
     pub fn iter() -> impl Iterator<Item = Mark> {
-        let mut value = Mark::N1 as usize;
-
-        std::iter::from_fn(move || {
-            if value > (Mark::N9 as usize) {
-                return None;
-            }
-
-            let mark = unsafe { std::mem::transmute(value) };
-            value <<= 1;
-            Some(mark)
-        })
+        MARKS.into_iter()
     }
 
     // Returns the index of the given value, starting from 0 to 8
@@ -51,17 +69,22 @@ impl Mark {
     }
 
     // Returns the value of the given index
-    pub fn from_index(index: u16) -> Mark {
-        Mark::from_value(index + 1)
+    // Panics if the index is not between 0 and 8
+    pub const fn from_index(index: u16) -> Mark {
+        MARKS[index as usize]
     }
 
     pub const fn to_value(self) -> u16 {
         ((self as usize).trailing_zeros() - 6) as u16
     }
 
+    // Returns the mark of the given value
+    // Panics if the value is not between 1 and 9
     pub fn from_value(value: u16) -> Mark {
-        let v = (1 << (value + 6)) as usize;
-        unsafe { std::mem::transmute(v) }
+        match Mark::try_from(value) {
+            Ok(mark) => mark,
+            Err(e) => panic!("{}", e),
+        }
     }
 
     // Returns raw data of the mark
@@ -70,14 +93,26 @@ impl Mark {
     }
 }
 
+impl TryFrom<u16> for Mark {
+    type Error = InvalidValueError;
+
+    fn try_from(value: u16) -> Result<Self, Self::Error> {
+        match value {
+            1..=9 => Ok(MARKS[value as usize - 1]),
+            _ => Err(InvalidValueError { value }),
+        }
+    }
+}
+
 impl Shl<u32> for Mark {
     type Output = Mark;
 
+    // Shifts the mark to a higher value, panics if it would go past 9
     fn shl(self, rhs: u32) -> Self::Output {
-        //Convert unsafe to usize, shift it and convert back to Mark
-        let mut value = self as usize;
-        value = value << rhs;
-        unsafe { std::mem::transmute(value) }
+        let value = (self.to_value() as u32).saturating_add(rhs);
+        let value = u16::try_from(value).unwrap_or(u16::MAX);
+
+        Mark::try_from(value).expect("the shifted mark is past 9")
     }
 }
 
@@ -129,6 +164,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_try_from() {
+        for value in 1..=9 {
+            let mark = Mark::try_from(value).unwrap();
+            assert_eq!(mark.to_value(), value);
+        }
+
+        assert_eq!(Mark::try_from(0), Err(InvalidValueError { value: 0 }));
+        assert_eq!(Mark::try_from(10), Err(InvalidValueError { value: 10 }));
+        assert_eq!(Mark::try_from(0x40), Err(InvalidValueError { value: 0x40 }));
+    }
+
+    #[test]
+    fn test_shl() {
+        assert_eq!(Mark::N1 << 1, Mark::N2);
+        assert_eq!(Mark::N3 << 6, Mark::N9);
+    }
+
+    #[test]
+    #[should_panic(expected = "past 9")]
+    fn test_shl_past_nine() {
+        let _ = Mark::N1 << 0x1_0000;
+    }
+
     #[test]
     fn test_iter() {
         let mut iter = Mark::iter();
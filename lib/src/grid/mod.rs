@@ -1,15 +1,28 @@
+pub mod board;
+pub mod canonical;
 pub mod cell;
 pub mod cell_collection;
 pub mod column;
 pub mod constants;
 pub mod coords;
+pub mod diagonal;
+pub mod dimensions;
 pub mod flags;
 pub mod format;
 pub mod grid;
 pub mod grid_mask;
+pub mod jigsaw;
+pub mod jigsaw_grid;
 pub mod mark;
+pub mod parse_error;
 pub mod queries;
+pub mod region;
 pub mod row;
+pub mod samurai;
+#[cfg(feature = "serde")]
+pub mod serialization;
 pub mod slice;
 pub mod square;
+pub mod transform;
 pub mod utility;
+pub mod window;
@@ -0,0 +1,57 @@
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+/// The reason a text could not be parsed into a grid
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// A character that is not part of the format
+    InvalidCharacter(char),
+    /// A cell with a value that is not a valid cell
+    InvalidValue(u16),
+    /// The text contains more cells than fit in the grid
+    TooManyCells,
+    /// The text ended after `found` cells, while the grid has `expected` cells
+    TooFewCells { found: usize, expected: usize },
+    /// The text does not follow the structure of the format
+    Malformed(String),
+}
+
+/// An error that occurred while parsing a grid, with the position in the text it occurred at
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The line the error occurred on, starting at 1
+    pub line: usize,
+    /// The column the error occurred on, starting at 1
+    pub column: usize,
+    pub kind: ParseErrorKind,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, kind: ParseErrorKind) -> Self {
+        Self { line, column, kind }
+    }
+}
+
+impl Display for ParseErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseErrorKind::InvalidCharacter(c) => write!(f, "invalid character '{}'", c),
+            ParseErrorKind::InvalidValue(v) => write!(f, "invalid cell value {:#06x}", v),
+            ParseErrorKind::TooManyCells => write!(f, "too many cells"),
+            ParseErrorKind::TooFewCells { found, expected } => {
+                write!(f, "expected {} cells, found only {}", expected, found)
+            }
+            ParseErrorKind::Malformed(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at {}:{}", self.kind, self.line, self.column)
+    }
+}
+
+impl Error for ParseError {}
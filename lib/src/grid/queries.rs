@@ -1,6 +1,12 @@
-use super::{cell_collection::CellCollection, grid::Grid, mark::Mark, slice::Slice};
-
-pub fn count_determine_value<T: CellCollection>(grid: &Grid, area: T, value: u16) -> usize {
+use super::{
+    cell_collection::CellCollection, dimensions::Nine, grid::Grid, mark::Mark, slice::Slice,
+};
+
+pub fn count_determine_value<T: CellCollection<Dimensions = Nine>>(
+    grid: &Grid,
+    area: T,
+    value: u16,
+) -> usize {
     if area.max() == 9 {
         let s = Slice::from(grid, &area);
 
@@ -20,7 +26,11 @@ pub fn count_determine_value<T: CellCollection>(grid: &Grid, area: T, value: u16
     count
 }
 
-pub fn count_possible<T: CellCollection>(grid: &Grid, area: T, mark: Mark) -> usize {
+pub fn count_possible<T: CellCollection<Dimensions = Nine>>(
+    grid: &Grid,
+    area: T,
+    mark: Mark,
+) -> usize {
     if area.max() == 9 {
         let s = Slice::from(grid, &area);
 
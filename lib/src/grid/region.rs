@@ -0,0 +1,64 @@
+use std::fmt::{Display, Formatter};
+
+use super::{cell_collection::CellCollection, coords::Coord, dimensions::Nine, square::Square};
+
+/// A region of nine cells that must contain 1 to 9, either a square or a jigsaw piece
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Region {
+    coords: [Coord; 9],
+}
+
+impl Region {
+    /// Creates a region from the given cells
+    pub const fn new(coords: [Coord; 9]) -> Self {
+        Self { coords }
+    }
+
+    /// Returns true if the coord is within the region
+    pub fn contains(&self, coord: Coord) -> bool {
+        self.coords.contains(&coord)
+    }
+
+    /// Returns the cells of the region
+    pub fn coords(&self) -> [Coord; 9] {
+        self.coords
+    }
+
+    /// Iterates over the cells of the region
+    pub fn iter_coords(&self) -> impl Iterator<Item = Coord> + '_ {
+        self.coords.iter().copied()
+    }
+}
+
+impl From<Square> for Region {
+    fn from(square: Square) -> Self {
+        let mut coords = [Coord::new(0, 0); 9];
+        for (index, coord) in coords.iter_mut().enumerate() {
+            *coord = square.get_coord(index);
+        }
+
+        Region::new(coords)
+    }
+}
+
+impl CellCollection for Region {
+    type Dimensions = Nine;
+
+    fn get_coord(&self, index: usize) -> Coord {
+        self.coords[index]
+    }
+
+    fn iter(&self) -> std::ops::Range<usize> {
+        0..self.max()
+    }
+
+    fn max(&self) -> usize {
+        9
+    }
+}
+
+impl Display for Region {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[Region at {}]", self.coords[0])
+    }
+}
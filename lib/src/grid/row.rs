@@ -1,28 +1,29 @@
-use std::fmt::Display;
+use std::{fmt::Display, marker::PhantomData};
 
 use super::{
-    cell_collection::CellCollection, constants::GRID_HEIGHT, coords::Coord, square::Square,
+    cell_collection::CellCollection,
+    constants::GRID_HEIGHT,
+    coords::Coord,
+    dimensions::{Dimensions, Nine},
+    square::Square,
 };
 
 #[derive(Debug, Clone, Copy, PartialEq)]
-pub struct Row {
+pub struct Row<D: Dimensions = Nine> {
     //The row index
     row: usize,
+    dimensions: PhantomData<D>,
 }
 
 impl Row {
     pub const fn new(row: usize) -> Self {
-        Self { row }
+        Row::at(row)
     }
 
     pub fn get_square(&self, index: usize) -> Square {
         Square::from(self.row, index)
     }
 
-    pub fn row_index(&self) -> usize {
-        self.row
-    }
-
     pub fn iter_row() -> impl Iterator<Item = Row> {
         (0..Row::max()).map(|i| Row::new(i))
     }
@@ -32,27 +33,43 @@ impl Row {
     }
 }
 
+impl<D: Dimensions> Row<D> {
+    /// Creates the row with the given index in a grid of any size
+    pub const fn at(row: usize) -> Self {
+        Self {
+            row,
+            dimensions: PhantomData,
+        }
+    }
+
+    pub fn row_index(&self) -> usize {
+        self.row
+    }
+}
+
 impl From<usize> for Row {
     fn from(row: usize) -> Self {
         Self::new(row)
     }
 }
 
-impl CellCollection for Row {
-    fn get_coord(&self, col: usize) -> Coord {
-        Coord::new(self.row, col)
+impl<D: Dimensions> CellCollection for Row<D> {
+    type Dimensions = D;
+
+    fn get_coord(&self, col: usize) -> Coord<D> {
+        Coord::at(self.row, col)
     }
 
     fn iter(&self) -> std::ops::Range<usize> {
-        0..Row::max()
+        0..D::SIZE
     }
 
     fn max(&self) -> usize {
-        Row::max()
+        D::SIZE
     }
 }
 
-impl Display for Row {
+impl<D: Dimensions> Display for Row<D> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "[Row {}]", self.row)
     }
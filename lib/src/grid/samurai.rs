@@ -0,0 +1,235 @@
+use std::fmt::{Display, Formatter};
+
+use super::{coords::Coord, grid::Grid, mark::Mark, square::Square};
+use crate::solvers::{solver::SolveResult, validator::is_valid};
+
+/// The index of the central grid
+pub const CENTER: usize = 2;
+
+/// The squares shared between an outer grid and the central grid: the outer grid, its square and the square of the center
+const SHARED: [(usize, usize, usize); 4] = [(0, 8, 0), (1, 6, 2), (3, 2, 6), (4, 0, 8)];
+
+/// A samurai sudoku, five grids where a corner square of each outer grid is also a corner square of the central grid.
+///
+/// The grids are in reading order: top left, top right, center, bottom left and bottom right.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Samurai {
+    grids: [Grid; 5],
+}
+
+impl Samurai {
+    /// Creates a samurai sudoku of five empty grids
+    pub fn new() -> Self {
+        Self::from_grids([Grid::new(); 5])
+    }
+
+    pub fn from_grids(grids: [Grid; 5]) -> Self {
+        Self { grids }
+    }
+
+    pub fn grids(&self) -> &[Grid; 5] {
+        &self.grids
+    }
+
+    pub fn grid(&self, index: usize) -> &Grid {
+        &self.grids[index]
+    }
+
+    pub fn grid_mut(&mut self, index: usize) -> &mut Grid {
+        &mut self.grids[index]
+    }
+
+    /// Returns the grid and coord of the same cell in another grid, if the cell is in a shared square
+    pub fn shared_at(grid: usize, coord: Coord) -> Option<(usize, Coord)> {
+        let square = Square::from(coord.get_row(), coord.get_col()).to_square_index();
+
+        SHARED
+            .iter()
+            .find_map(|(outer, outer_square, center_square)| {
+                let (other, other_square) = match grid {
+                    g if g == *outer && square == *outer_square => (CENTER, *center_square),
+                    CENTER if square == *center_square => (*outer, *outer_square),
+                    _ => return None,
+                };
+                let start = Square::from_square_index(other_square);
+
+                Some((
+                    other,
+                    start.get_coord_at(coord.get_row() % 3, coord.get_col() % 3),
+                ))
+            })
+    }
+
+    /// Places the value in the grid and in the grid that shares the cell
+    pub fn place_value_at(&mut self, grid: usize, coord: Coord, value: u16) {
+        self.grids[grid].place_value_at(coord, value);
+
+        if let Some((other, other_coord)) = Samurai::shared_at(grid, coord) {
+            self.grids[other].place_value_at(other_coord, value);
+        }
+    }
+
+    /// Makes the shared cells agree: values are copied over and only the candidates of both grids are kept
+    pub fn sync(&mut self) -> SolveResult {
+        let mut changed = false;
+
+        for (outer, outer_square, center_square) in SHARED {
+            let from = Square::from_square_index(outer_square);
+            let to = Square::from_square_index(center_square);
+
+            for (row, col) in (0..3).flat_map(|row| (0..3).map(move |col| (row, col))) {
+                let a = (outer, from.get_coord_at(row, col));
+                let b = (CENTER, to.get_coord_at(row, col));
+
+                match self.sync_cell(a, b) {
+                    SolveResult::Error => return SolveResult::Error,
+                    result => changed |= result == SolveResult::Updated,
+                }
+            }
+        }
+
+        SolveResult::from_changed(changed)
+    }
+
+    fn sync_cell(&mut self, a: (usize, Coord), b: (usize, Coord)) -> SolveResult {
+        let cell_a = *self.grids[a.0].get_cell_at(a.1);
+        let cell_b = *self.grids[b.0].get_cell_at(b.1);
+
+        match (cell_a.value(), cell_b.value()) {
+            (Some(va), Some(vb)) if va != vb => SolveResult::Error,
+            (Some(_), Some(_)) => SolveResult::Nothing,
+            (Some(value), None) => self.copy_value(b, value),
+            (None, Some(value)) => self.copy_value(a, value),
+            (None, None) => {
+                let mut changed = false;
+
+                for mark in Mark::iter() {
+                    let (in_a, in_b) = (cell_a.is_possible(mark), cell_b.is_possible(mark));
+                    if in_a && !in_b {
+                        self.grids[a.0].unset_possible_at(a.1, mark);
+                        changed = true;
+                    } else if in_b && !in_a {
+                        self.grids[b.0].unset_possible_at(b.1, mark);
+                        changed = true;
+                    }
+                }
+
+                match self.grids[a.0].get_cell_at(a.1).possible_count() {
+                    0 => SolveResult::Error,
+                    _ => SolveResult::from_changed(changed),
+                }
+            }
+        }
+    }
+
+    fn copy_value(&mut self, (grid, coord): (usize, Coord), value: u16) -> SolveResult {
+        if !self.grids[grid].is_possible_at(coord, Mark::from_value(value)) {
+            return SolveResult::Error;
+        }
+
+        self.grids[grid].place_value_at(coord, value);
+        SolveResult::Updated
+    }
+
+    pub fn count_determined(&self) -> usize {
+        self.grids.iter().map(|g| g.count_determined()).sum()
+    }
+
+    /// Returns true if every cell of every grid has a value
+    pub fn is_filled(&self) -> bool {
+        self.grids.iter().all(|g| g.count_determined() == 81)
+    }
+
+    /// Returns true if no grid breaks the rules and the shared cells agree
+    pub fn is_valid(&self) -> bool {
+        let shared_agree = SHARED.iter().all(|(outer, outer_square, center_square)| {
+            let from = Square::from_square_index(*outer_square);
+            let to = Square::from_square_index(*center_square);
+
+            (0..9).all(|i| {
+                let a = self.grids[*outer].get_cell_at(from.get_coord_at(i / 3, i % 3));
+                let b = self.grids[CENTER].get_cell_at(to.get_coord_at(i / 3, i % 3));
+                a.value().is_none() || b.value().is_none() || a.value() == b.value()
+            })
+        });
+
+        shared_agree && self.grids.iter().all(is_valid)
+    }
+}
+
+impl Default for Samurai {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Display for Samurai {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (index, grid) in self.grids.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            writeln!(f, "Grid {}", index + 1)?;
+            write!(f, "{}", grid)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Samurai, CENTER};
+    use crate::grid::{coords::Coord, mark::Mark};
+    use crate::solvers::solver::SolveResult;
+
+    #[test]
+    fn test_shared_at() {
+        assert_eq!(
+            Samurai::shared_at(0, Coord::new(8, 8)),
+            Some((CENTER, Coord::new(2, 2)))
+        );
+        assert_eq!(
+            Samurai::shared_at(CENTER, Coord::new(0, 7)),
+            Some((1, Coord::new(6, 1)))
+        );
+        assert_eq!(
+            Samurai::shared_at(CENTER, Coord::new(6, 0)),
+            Some((3, Coord::new(0, 6)))
+        );
+        assert_eq!(
+            Samurai::shared_at(4, Coord::new(1, 1)),
+            Some((CENTER, Coord::new(7, 7)))
+        );
+        assert_eq!(Samurai::shared_at(0, Coord::new(0, 0)), None);
+        assert_eq!(Samurai::shared_at(CENTER, Coord::new(4, 4)), None);
+    }
+
+    #[test]
+    fn test_sync() {
+        let samurai = &mut Samurai::new();
+
+        samurai.grid_mut(1).place_value_at(Coord::new(6, 0), 5);
+        samurai
+            .grid_mut(CENTER)
+            .unset_possible_at(Coord::new(0, 7), Mark::N3);
+
+        assert_eq!(samurai.sync(), SolveResult::Updated);
+        while samurai.sync() == SolveResult::Updated {}
+        assert_eq!(
+            samurai.grid(CENTER).get_cell_at(Coord::new(0, 6)).value(),
+            Some(5)
+        );
+        assert!(!samurai.grid(1).is_possible_at(Coord::new(6, 1), Mark::N3));
+        // The value placed through the shared cell is marked off in the central grid, and from there in the bottom right grid
+        assert!(!samurai
+            .grid(CENTER)
+            .is_possible_at(Coord::new(6, 6), Mark::N5));
+        assert!(!samurai.grid(4).is_possible_at(Coord::new(0, 0), Mark::N5));
+
+        samurai.grid_mut(CENTER).place_value_at(Coord::new(8, 8), 1);
+        samurai.grid_mut(4).place_value_at(Coord::new(2, 2), 2);
+        assert_eq!(samurai.sync(), SolveResult::Error);
+        assert!(!samurai.is_valid());
+    }
+}
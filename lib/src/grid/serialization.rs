@@ -0,0 +1,273 @@
+//! Serde support for the grid types, enabled with the `serde` feature.
+//!
+//! A [`Grid`] is written in the detailed form by default, a list of 81 cells with their value and candidates.
+//! It reads both the detailed form and the compact form, a string of 81 digits where `0` or `.` is an empty cell.
+//! Formats that are not human readable, such as bincode, can not tell the forms apart and only read the detailed form.
+//! Use `#[serde(with = "compact")]` or `#[serde(with = "detailed")]` to pick a single form.
+
+use std::fmt::{self, Formatter};
+
+use serde::{
+    de::{self, SeqAccess, Visitor},
+    ser::SerializeSeq,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use super::{
+    cell::Cell, cell_collection::CellCollection, constants::GRID_SIZE, coords::Coord,
+    dimensions::Dimensions, grid::Grid, mark::Mark, utility::utility,
+};
+
+impl Serialize for Mark {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u16(self.to_value())
+    }
+}
+
+impl<'de> Deserialize<'de> for Mark {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = u16::deserialize(deserializer)?;
+
+        Mark::try_from(value).map_err(de::Error::custom)
+    }
+}
+
+/// The detailed form of a cell, the value is 0 if the cell is not determined
+#[derive(Serialize, Deserialize)]
+struct CellData {
+    value: u16,
+    #[serde(default)]
+    candidates: Vec<Mark>,
+}
+
+impl Serialize for Cell {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let data = CellData {
+            value: self.value().unwrap_or(0),
+            candidates: self.iter_possible().collect(),
+        };
+
+        data.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Cell {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = CellData::deserialize(deserializer)?;
+        if data.value != 0 {
+            return Cell::try_new_with_value(data.value).map_err(de::Error::custom);
+        }
+
+        let mut cell = Cell::new_empty();
+        for mark in data.candidates {
+            cell.set_possible(mark);
+        }
+
+        Ok(cell)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct CoordData {
+    row: usize,
+    col: usize,
+}
+
+impl<D: Dimensions> Serialize for Coord<D> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (row, col) = self.get_row_col();
+
+        CoordData { row, col }.serialize(serializer)
+    }
+}
+
+impl<'de, D: Dimensions> Deserialize<'de> for Coord<D> {
+    fn deserialize<De: Deserializer<'de>>(deserializer: De) -> Result<Self, De::Error> {
+        let data = CoordData::deserialize(deserializer)?;
+        if data.row >= D::SIZE || data.col >= D::SIZE {
+            let msg = format!("coord [{}, {}] is outside of the grid", data.row, data.col);
+            return Err(de::Error::custom(msg));
+        }
+
+        Ok(Coord::at(data.row, data.col))
+    }
+}
+
+impl Serialize for Grid {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        detailed::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Grid {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match deserializer.is_human_readable() {
+            true => deserializer.deserialize_any(GridVisitor),
+            false => detailed::deserialize(deserializer),
+        }
+    }
+}
+
+/// Reads either the compact or the detailed form of a grid
+struct GridVisitor;
+
+impl<'de> Visitor<'de> for GridVisitor {
+    type Value = Grid;
+
+    fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "a string of 81 digits or a list of 81 cells")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        utility::from_digit(v).map_err(E::custom)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut grid = Grid::empty();
+        let mut index = 0;
+
+        while let Some(cell) = seq.next_element::<Cell>()? {
+            if index >= GRID_SIZE {
+                return Err(de::Error::invalid_length(index + 1, &self));
+            }
+
+            grid.set_cell(index, &cell);
+            index += 1;
+        }
+
+        if index != GRID_SIZE {
+            return Err(de::Error::invalid_length(index, &self));
+        }
+
+        Ok(grid)
+    }
+}
+
+/// Writes and reads a grid as a string of 81 digits, only keeping the determined values
+pub mod compact {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(grid: &Grid, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&utility::to_digits(grid))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Grid, D::Error> {
+        let digits = String::deserialize(deserializer)?;
+
+        utility::from_digit(&digits).map_err(de::Error::custom)
+    }
+}
+
+/// Writes and reads a grid as a list of 81 cells, keeping the candidates of each cell
+pub mod detailed {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(grid: &Grid, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(GRID_SIZE))?;
+        for index in grid.iter() {
+            seq.serialize_element(grid.get_cell(index))?;
+        }
+
+        seq.end()
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Grid, D::Error> {
+        deserializer.deserialize_seq(GridVisitor)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde::{Deserialize, Serialize};
+
+    use super::compact;
+    use crate::{
+        grid::{cell::Cell, coords::Coord, grid::Grid, mark::Mark, utility::utility},
+        solvers::solver::{AnnotatedSolverResult, SolveResult},
+        test::util::general_tests,
+    };
+
+    #[derive(Serialize, Deserialize)]
+    struct Compact {
+        #[serde(with = "compact")]
+        grid: Grid,
+    }
+
+    #[test]
+    fn test_cell() {
+        let mut cell = Cell::new_empty();
+        cell.set_possible(Mark::N2);
+        cell.set_possible(Mark::N7);
+
+        let json = serde_json::to_string(&cell).unwrap();
+        assert_eq!(json, r#"{"value":0,"candidates":[2,7]}"#);
+        assert_eq!(serde_json::from_str::<Cell>(&json).unwrap(), cell);
+
+        let json = serde_json::to_string(&Cell::new_with_value(5)).unwrap();
+        assert_eq!(json, r#"{"value":5,"candidates":[]}"#);
+
+        assert!(serde_json::from_str::<Cell>(r#"{"value":12}"#).is_err());
+        assert!(serde_json::from_str::<Mark>("0").is_err());
+    }
+
+    #[test]
+    fn test_coord() {
+        let coord = Coord::new(4, 7);
+        let json = serde_json::to_string(&coord).unwrap();
+
+        assert_eq!(json, r#"{"row":4,"col":7}"#);
+        assert_eq!(serde_json::from_str::<Coord>(&json).unwrap(), coord);
+        assert!(serde_json::from_str::<Coord>(r#"{"row":9,"col":0}"#).is_err());
+    }
+
+    #[test]
+    fn test_grid_forms() {
+        let mut grid = general_tests::filled_sudoku();
+        general_tests::remove_cells_amount(&mut grid, 30);
+
+        let detailed = serde_json::to_string(&grid).unwrap();
+        assert_eq!(serde_json::from_str::<Grid>(&detailed).unwrap(), grid);
+
+        let compact = serde_json::to_string(&Compact { grid }).unwrap();
+        let parsed: Compact = serde_json::from_str(&compact).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&compact).unwrap();
+        let from_string: Grid = serde_json::from_value(value["grid"].clone()).unwrap();
+
+        assert_eq!(value["grid"], utility::to_digits(&grid));
+        assert_eq!(utility::to_digits(&parsed.grid), utility::to_digits(&grid));
+        assert_eq!(parsed.grid, from_string);
+        assert!(serde_json::from_str::<Grid>("[]").is_err());
+    }
+
+    #[test]
+    fn test_binary() {
+        let mut grid = general_tests::filled_sudoku();
+        general_tests::remove_cells_amount(&mut grid, 30);
+        let mut cell = Cell::new_with_possible(Mark::N2);
+        cell.set_possible(Mark::N7);
+        grid.set_cell_at(Coord::new(0, 0), &cell);
+
+        let bytes = bincode::serialize(&grid).unwrap();
+        assert_eq!(bincode::deserialize::<Grid>(&bytes).unwrap(), grid);
+
+        let bytes = bincode::serialize(&Compact { grid }).unwrap();
+        let parsed: Compact = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(parsed.grid.count_determined(), grid.count_determined());
+    }
+
+    #[test]
+    fn test_result() {
+        let result = AnnotatedSolverResult {
+            result: SolveResult::Solved,
+            grid: general_tests::filled_sudoku(),
+            iterations: 3,
+        };
+
+        let json = serde_json::to_string(&result).unwrap();
+        let parsed: AnnotatedSolverResult = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(parsed.result, SolveResult::Solved);
+        assert_eq!(parsed.grid, result.grid);
+        assert_eq!(parsed.iterations, 3);
+    }
+}
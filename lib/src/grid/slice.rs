@@ -6,6 +6,7 @@ use std::{
 use super::{
     cell::{Cell, CELL_BITS_SIZE},
     cell_collection::CellCollection,
+    dimensions::Nine,
     grid::Grid,
     mark::Mark,
 };
@@ -32,7 +33,7 @@ impl Slice {
     }
 
     /// Creates a slice from the given cell collection
-    pub fn from<T: CellCollection>(grid: &Grid, area: &T) -> Slice {
+    pub fn from<T: CellCollection<Dimensions = Nine>>(grid: &Grid, area: &T) -> Slice {
         if area.max() >= SLICE_SIZE {
             panic!("Slice must be of size {}", SLICE_SIZE);
         }
@@ -183,7 +184,7 @@ impl Slice {
 
     /// Returns a slice, which has all determined cells removed, with only the given mark
     pub fn only_possible_value(&self, mark: Mark) -> Slice {
-        let mask = Cell::new_with_value(mark.to_data());
+        let mask = Cell::from_data(mark.to_data());
         let mut slice = self.clone();
 
         for i in slice.iter() {
@@ -1,19 +1,34 @@
-use std::fmt::{Display, Formatter};
-
-use super::{cell_collection::CellCollection, column::Column, coords::Coord, row::Row};
-
+use std::{
+    fmt::{Display, Formatter},
+    marker::PhantomData,
+};
+
+use super::{
+    cell_collection::CellCollection,
+    column::Column,
+    coords::Coord,
+    dimensions::{Dimensions, Nine},
+    row::Row,
+};
+
+/// A box of the grid, 3x3 cells in a standard grid
 #[derive(Debug, Clone, Copy, PartialEq)]
-pub struct Square {
+pub struct Square<D: Dimensions = Nine> {
     /// The row index the square starts at
     pub row: usize,
     /// The column index the square starts at
     pub col: usize,
+    dimensions: PhantomData<D>,
 }
 
 impl Square {
     /// Creates a new square from the given row and column
     pub const fn new(row: usize, col: usize) -> Self {
-        Self { row, col }
+        Self {
+            row,
+            col,
+            dimensions: PhantomData,
+        }
     }
 
     /// Creates a new square from the given row and column
@@ -98,11 +113,26 @@ impl Square {
     }
 }
 
-impl CellCollection for Square {
-    fn get_coord(&self, index: usize) -> Coord {
-        let row = self.row + index / 3;
-        let col = self.col + index % 3;
-        Coord::new(row, col)
+impl<D: Dimensions> Square<D> {
+    /// Returns the box with the given index in a grid of any size, boxes are numbered row by row
+    pub const fn at(index: usize) -> Self {
+        let boxes_per_row = D::SIZE / D::BOX_WIDTH;
+
+        Self {
+            row: (index / boxes_per_row) * D::BOX_HEIGHT,
+            col: (index % boxes_per_row) * D::BOX_WIDTH,
+            dimensions: PhantomData,
+        }
+    }
+}
+
+impl<D: Dimensions> CellCollection for Square<D> {
+    type Dimensions = D;
+
+    fn get_coord(&self, index: usize) -> Coord<D> {
+        let row = self.row + index / D::BOX_WIDTH;
+        let col = self.col + index % D::BOX_WIDTH;
+        Coord::at(row, col)
     }
 
     fn iter(&self) -> std::ops::Range<usize> {
@@ -110,17 +140,17 @@ impl CellCollection for Square {
     }
 
     fn max(&self) -> usize {
-        9
+        D::SIZE
     }
 }
 
-impl Default for Square {
+impl<D: Dimensions> Default for Square<D> {
     fn default() -> Self {
-        Self { row: 0, col: 0 }
+        Square::at(0)
     }
 }
 
-impl Display for Square {
+impl<D: Dimensions> Display for Square<D> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "Square({}, {})", self.row, self.col)
     }
@@ -128,7 +158,10 @@ impl Display for Square {
 
 #[cfg(test)]
 mod test {
-    use crate::{grid::cell_collection::CellCollection, test::util::general_tests};
+    use crate::{
+        grid::{cell_collection::CellCollection, dimensions::Six},
+        test::util::general_tests,
+    };
 
     use super::Square;
 
@@ -169,6 +202,18 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_sized_square() {
+        let square: Square<Six> = Square::at(3);
+        assert_eq!((square.row, square.col), (2, 3));
+
+        let coords: Vec<usize> = square
+            .iter()
+            .map(|i| square.get_coord(i).get_index())
+            .collect();
+        assert_eq!(coords, vec![15, 16, 17, 21, 22, 23]);
+    }
+
     #[test]
     fn test_from_index() {
         for i in 0..9 {
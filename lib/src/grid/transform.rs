@@ -0,0 +1,499 @@
+use rand::{seq::SliceRandom, Rng};
+
+use super::{cell::Cell, cell_collection::CellCollection, coords::Coord, grid::Grid, mark::Mark};
+
+type Permutation = [usize; 9];
+
+const IDENTITY: Permutation = [0, 1, 2, 3, 4, 5, 6, 7, 8];
+const REVERSED: Permutation = [8, 7, 6, 5, 4, 3, 2, 1, 0];
+
+/// A validity preserving transformation of a sudoku grid.
+///
+/// A transformation is stored as an optional transpose, followed by a row and column permutation
+/// and a relabeling of the digits. Every destination cell `(row, col)` reads from the source cell
+/// `(rows[row], cols[col])`, or `(cols[col], rows[row])` when transposed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Transform {
+    transpose: bool,
+    rows: Permutation,
+    cols: Permutation,
+    /// The new value for each digit, indexed by the old value - 1
+    digits: Permutation,
+}
+
+impl Transform {
+    /// Creates the transformation that leaves the grid untouched
+    pub const fn identity() -> Self {
+        Self {
+            transpose: false,
+            rows: IDENTITY,
+            cols: IDENTITY,
+            digits: IDENTITY,
+        }
+    }
+
+    /// Creates a transformation from its parts, returns None if the parts would not preserve a valid grid
+    ///
+    /// `rows` and `cols` contain for each destination row/column the source row/column,
+    /// `digits` contains for each old digit (index 0 is digit 1) the new digit index
+    pub fn from_parts(
+        transpose: bool,
+        rows: [usize; 9],
+        cols: [usize; 9],
+        digits: [usize; 9],
+    ) -> Option<Self> {
+        if !is_band_preserving(&rows) || !is_band_preserving(&cols) || !is_permutation(&digits) {
+            return None;
+        }
+
+        Some(Self {
+            transpose,
+            rows,
+            cols,
+            digits,
+        })
+    }
+
+    /// Relabels the digits, `map[0]` is the new mark for the digit 1 and so on
+    pub fn relabel(map: [Mark; 9]) -> Option<Self> {
+        let mut digits = IDENTITY;
+        for (index, mark) in map.iter().enumerate() {
+            digits[index] = mark.to_index() as usize;
+        }
+
+        Self::from_parts(false, IDENTITY, IDENTITY, digits)
+    }
+
+    /// Swaps two digits
+    pub fn swap_digits(a: Mark, b: Mark) -> Self {
+        let mut digits = IDENTITY;
+        digits.swap(a.to_index() as usize, b.to_index() as usize);
+
+        Self {
+            digits,
+            ..Self::identity()
+        }
+    }
+
+    /// Reorders the rows inside of a band (0..3), `order` holds the source row offset for each destination row
+    pub fn permute_rows_in_band(band: usize, order: [usize; 3]) -> Option<Self> {
+        let rows = permute_in_block(band, order)?;
+
+        Self::from_parts(false, rows, IDENTITY, IDENTITY)
+    }
+
+    /// Reorders the columns inside of a stack (0..3), `order` holds the source column offset for each destination column
+    pub fn permute_cols_in_stack(stack: usize, order: [usize; 3]) -> Option<Self> {
+        let cols = permute_in_block(stack, order)?;
+
+        Self::from_parts(false, IDENTITY, cols, IDENTITY)
+    }
+
+    /// Reorders the bands, `order` holds the source band for each destination band
+    pub fn permute_bands(order: [usize; 3]) -> Option<Self> {
+        let rows = permute_blocks(order)?;
+
+        Self::from_parts(false, rows, IDENTITY, IDENTITY)
+    }
+
+    /// Reorders the stacks, `order` holds the source stack for each destination stack
+    pub fn permute_stacks(order: [usize; 3]) -> Option<Self> {
+        let cols = permute_blocks(order)?;
+
+        Self::from_parts(false, IDENTITY, cols, IDENTITY)
+    }
+
+    /// Swaps two rows, returns None if they are not in the same band
+    pub fn swap_rows(a: usize, b: usize) -> Option<Self> {
+        let rows = swap_in_block(a, b)?;
+
+        Self::from_parts(false, rows, IDENTITY, IDENTITY)
+    }
+
+    /// Swaps two columns, returns None if they are not in the same stack
+    pub fn swap_cols(a: usize, b: usize) -> Option<Self> {
+        let cols = swap_in_block(a, b)?;
+
+        Self::from_parts(false, IDENTITY, cols, IDENTITY)
+    }
+
+    /// Mirrors the grid over its main diagonal
+    pub const fn transpose() -> Self {
+        Self {
+            transpose: true,
+            ..Self::identity()
+        }
+    }
+
+    /// Mirrors the grid top to bottom
+    pub const fn reflect_horizontal() -> Self {
+        Self {
+            rows: REVERSED,
+            ..Self::identity()
+        }
+    }
+
+    /// Mirrors the grid left to right
+    pub const fn reflect_vertical() -> Self {
+        Self {
+            cols: REVERSED,
+            ..Self::identity()
+        }
+    }
+
+    /// Rotates the grid clockwise by the given amount of quarter turns
+    pub fn rotate(quarter_turns: usize) -> Self {
+        // A clockwise quarter turn reads the destination (row, col) from the source (8 - col, row)
+        let quarter = Self {
+            transpose: true,
+            rows: IDENTITY,
+            cols: REVERSED,
+            digits: IDENTITY,
+        };
+
+        let mut result = Self::identity();
+        for _ in 0..(quarter_turns % 4) {
+            result = result.then(&quarter);
+        }
+
+        result
+    }
+
+    /// Creates a random transformation out of all the possible transformations
+    pub fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        let mut rows = IDENTITY;
+        let mut cols = IDENTITY;
+        let mut digits = IDENTITY;
+
+        shuffle_blocks(rng, &mut rows);
+        shuffle_blocks(rng, &mut cols);
+        digits.shuffle(rng);
+
+        Self {
+            transpose: rng.gen(),
+            rows,
+            cols,
+            digits,
+        }
+    }
+
+    /// Returns true if the grid is transposed by this transformation
+    pub fn is_transposed(&self) -> bool {
+        self.transpose
+    }
+
+    /// Returns true if this transformation does not change anything
+    pub fn is_identity(&self) -> bool {
+        *self == Self::identity()
+    }
+
+    /// Returns the transformation that first applies this one and then the other
+    pub fn then(&self, other: &Transform) -> Transform {
+        // When the second transformation transposes, it reads our columns as rows
+        let (rows, cols) = match other.transpose {
+            true => (self.cols, self.rows),
+            false => (self.rows, self.cols),
+        };
+
+        Transform {
+            transpose: self.transpose ^ other.transpose,
+            rows: compose(&rows, &other.rows),
+            cols: compose(&cols, &other.cols),
+            digits: compose(&other.digits, &self.digits),
+        }
+    }
+
+    /// Returns the transformation that undoes this one
+    pub fn inverse(&self) -> Transform {
+        let rows = invert(&self.rows);
+        let cols = invert(&self.cols);
+        let digits = invert(&self.digits);
+
+        match self.transpose {
+            true => Transform {
+                transpose: true,
+                rows: cols,
+                cols: rows,
+                digits,
+            },
+            false => Transform {
+                transpose: false,
+                rows,
+                cols,
+                digits,
+            },
+        }
+    }
+
+    /// Returns the coordinate in the source grid that ends up at the given coordinate
+    pub fn source_coord(&self, coord: Coord) -> Coord {
+        let (row, col) = coord.get_row_col();
+        let (source_row, source_col) = (self.rows[row], self.cols[col]);
+
+        match self.transpose {
+            true => Coord::new(source_col, source_row),
+            false => Coord::new(source_row, source_col),
+        }
+    }
+
+    /// Returns where the given coordinate of the source grid ends up after the transformation
+    pub fn map_coord(&self, coord: Coord) -> Coord {
+        self.inverse().source_coord(coord)
+    }
+
+    /// Returns the mark the given mark is relabeled into
+    pub fn map_mark(&self, mark: Mark) -> Mark {
+        Mark::from_index(self.digits[mark.to_index() as usize] as u16)
+    }
+
+    /// Returns the value the given value is relabeled into, 0 stays 0
+    pub fn map_value(&self, value: u16) -> u16 {
+        match value {
+            0 => 0,
+            v => self.digits[v as usize - 1] as u16 + 1,
+        }
+    }
+
+    /// Relabels the value and candidates of the given cell
+    pub fn apply_cell(&self, cell: Cell) -> Cell {
+        if let Some(value) = cell.value() {
+            return Cell::new_with_value(self.map_value(value));
+        }
+
+        let mut result = Cell::new_empty();
+        for mark in cell.iter_possible() {
+            result.set_possible(self.map_mark(mark));
+        }
+
+        result
+    }
+
+    /// Returns a new grid with this transformation applied
+    pub fn apply(&self, grid: &Grid) -> Grid {
+        let mut result = Grid::empty();
+
+        for index in result.iter() {
+            let coord = Coord::from_index(index);
+            let source = grid.get_cell_at(self.source_coord(coord));
+
+            result.set_cell(index, &self.apply_cell(*source));
+        }
+
+        result
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+/// Composes two permutations, the result reads `first[second[i]]`
+fn compose(first: &Permutation, second: &Permutation) -> Permutation {
+    let mut result = IDENTITY;
+    for i in 0..9 {
+        result[i] = first[second[i]];
+    }
+
+    result
+}
+
+fn invert(perm: &Permutation) -> Permutation {
+    let mut result = IDENTITY;
+    for i in 0..9 {
+        result[perm[i]] = i;
+    }
+
+    result
+}
+
+fn is_permutation(perm: &Permutation) -> bool {
+    let mut seen = [false; 9];
+    for &i in perm.iter() {
+        if i >= 9 || seen[i] {
+            return false;
+        }
+        seen[i] = true;
+    }
+
+    true
+}
+
+/// Returns true if the permutation only moves rows inside of their band and bands as a whole
+fn is_band_preserving(perm: &Permutation) -> bool {
+    if !is_permutation(perm) {
+        return false;
+    }
+
+    for block in 0..3 {
+        let source_block = perm[block * 3] / 3;
+        for offset in 1..3 {
+            if perm[block * 3 + offset] / 3 != source_block {
+                return false;
+            }
+        }
+    }
+
+    true
+}
+
+fn permute_in_block(block: usize, order: [usize; 3]) -> Option<Permutation> {
+    if block >= 3 {
+        return None;
+    }
+
+    let mut perm = IDENTITY;
+    for (offset, source) in order.iter().enumerate() {
+        if *source >= 3 {
+            return None;
+        }
+        perm[block * 3 + offset] = block * 3 + source;
+    }
+
+    Some(perm)
+}
+
+fn permute_blocks(order: [usize; 3]) -> Option<Permutation> {
+    let mut perm = IDENTITY;
+    for (block, source) in order.iter().enumerate() {
+        for offset in 0..3 {
+            perm[block * 3 + offset] = source * 3 + offset;
+        }
+    }
+
+    Some(perm).filter(is_permutation)
+}
+
+fn swap_in_block(a: usize, b: usize) -> Option<Permutation> {
+    if a >= 9 || b >= 9 || a / 3 != b / 3 {
+        return None;
+    }
+
+    let mut perm = IDENTITY;
+    perm.swap(a, b);
+
+    Some(perm)
+}
+
+fn shuffle_blocks<R: Rng + ?Sized>(rng: &mut R, perm: &mut Permutation) {
+    let mut blocks = [0, 1, 2];
+    blocks.shuffle(rng);
+
+    for (block, source) in blocks.iter().enumerate() {
+        let mut offsets = [0, 1, 2];
+        offsets.shuffle(rng);
+
+        for (offset, source_offset) in offsets.iter().enumerate() {
+            perm[block * 3 + offset] = source * 3 + source_offset;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::Transform;
+    use crate::{
+        grid::{cell::Cell, cell_collection::CellCollection, coords::Coord, mark::Mark},
+        solvers::validator::is_valid,
+        test::util::general_tests,
+    };
+
+    #[test]
+    fn test_identity() {
+        let grid = general_tests::filled_sudoku();
+        let result = Transform::identity().apply(&grid);
+
+        assert_eq!(grid, result);
+    }
+
+    #[test]
+    fn test_rotate() {
+        let grid = general_tests::filled_sudoku();
+        let rotated = Transform::rotate(1).apply(&grid);
+
+        // The top left corner moves to the top right corner
+        assert_eq!(
+            grid.get_cell_at(Coord::new(0, 0)),
+            rotated.get_cell_at(Coord::new(0, 8))
+        );
+        assert_eq!(
+            grid.get_cell_at(Coord::new(8, 0)),
+            rotated.get_cell_at(Coord::new(0, 0))
+        );
+
+        let full = Transform::rotate(4).apply(&grid);
+        assert_eq!(grid, full);
+    }
+
+    #[test]
+    fn test_invalid_parts() {
+        assert!(Transform::swap_rows(2, 3).is_none());
+        assert!(Transform::swap_cols(0, 2).is_some());
+        assert!(Transform::permute_bands([0, 0, 1]).is_none());
+        assert!(Transform::permute_rows_in_band(1, [2, 0, 1]).is_some());
+    }
+
+    #[test]
+    fn test_random_stays_valid() {
+        let grid = general_tests::filled_sudoku();
+        let mut rng = StdRng::seed_from_u64(77143266753986);
+
+        for _ in 0..50 {
+            let transform = Transform::random(&mut rng);
+            let result = transform.apply(&grid);
+
+            assert!(is_valid(&result), "{}", result);
+            assert_eq!(transform.inverse().apply(&result), grid);
+        }
+    }
+
+    #[test]
+    fn test_then() {
+        let grid = general_tests::filled_sudoku();
+        let mut rng = StdRng::seed_from_u64(85822788013146);
+
+        for _ in 0..50 {
+            let first = Transform::random(&mut rng);
+            let second = Transform::random(&mut rng);
+
+            let expected = second.apply(&first.apply(&grid));
+            let combined = first.then(&second).apply(&grid);
+
+            assert_eq!(expected, combined);
+        }
+    }
+
+    #[test]
+    fn test_map_coord() {
+        let grid = general_tests::filled_sudoku();
+        let mut rng = StdRng::seed_from_u64(77143266753986);
+        let transform = Transform::random(&mut rng);
+        let result = transform.apply(&grid);
+
+        for index in grid.iter() {
+            let coord = Coord::from_index(index);
+            let value = grid.get_cell_at(coord).get_value();
+            let mapped = result.get_cell_at(transform.map_coord(coord)).get_value();
+
+            assert_eq!(transform.map_value(value), mapped);
+        }
+    }
+
+    #[test]
+    fn test_candidates() {
+        let mut cell = Cell::new_empty();
+        cell.set_possible(Mark::N1);
+        cell.set_possible(Mark::N5);
+
+        let transform = Transform::swap_digits(Mark::N1, Mark::N9);
+        let result = transform.apply_cell(cell);
+
+        assert!(result.is_possible(Mark::N9));
+        assert!(result.is_possible(Mark::N5));
+        assert!(!result.is_possible(Mark::N1));
+        assert!(!result.is_determined());
+    }
+}
@@ -6,6 +6,8 @@ pub mod utility {
         constants::{GRID_HEIGHT_RANGE, GRID_SIZE, GRID_WIDTH_RANGE},
         coords::Coord,
         grid::Grid,
+        mark::Mark,
+        parse_error::{ParseError, ParseErrorKind},
         square::Square,
     };
 
@@ -63,29 +65,79 @@ pub mod utility {
         s
     }
 
-    /// Returns a grid from a hex string
-    pub fn grid_from_hex(hex: &str) -> Grid {
+    /// Returns a grid from a hex string, each 4 characters representing a cell
+    pub fn grid_from_hex(hex: &str) -> Result<Grid, ParseError> {
         let mut grid = Grid::new();
-        let max = (hex.len() / 4).min(GRID_SIZE);
+        let mut index = 0;
+        let mut chunk = String::with_capacity(4);
+        // The position of the first character of the chunk, a chunk can span multiple lines
+        let mut start = (1, 1);
+
+        for (line, column, c) in positions(hex) {
+            if c.is_whitespace() {
+                continue;
+            }
+            if !c.is_ascii_hexdigit() {
+                return Err(ParseError::new(
+                    line,
+                    column,
+                    ParseErrorKind::InvalidCharacter(c),
+                ));
+            }
+            if index >= GRID_SIZE {
+                return Err(ParseError::new(line, column, ParseErrorKind::TooManyCells));
+            }
+
+            if chunk.is_empty() {
+                start = (line, column);
+            }
+            chunk.push(c);
+            if chunk.len() < 4 {
+                continue;
+            }
 
-        for i in 0..max {
-            let value = u16::from_str_radix(&hex[i * 4..i * 4 + 4], 16).unwrap();
-            grid.set_cell(i, &Cell::new_with_value(value));
+            // Only hex digits are pushed, so this can't fail
+            let value = u16::from_str_radix(&chunk, 16).unwrap_or_default();
+            if !is_valid_cell_data(value) {
+                let kind = ParseErrorKind::InvalidValue(value);
+                return Err(ParseError::new(start.0, start.1, kind));
+            }
+
+            grid.set_cell(index, &Cell::from_data(value));
+            chunk.clear();
+            index += 1;
         }
 
-        grid
+        expect_all_cells(hex, index)?;
+        Ok(grid)
     }
 
-    /// Returns a grid from a hex string
-    pub fn grid_from_hex_value(hex: &str) -> Grid {
+    /// Returns a grid from a hex string but only on the value, each 1 character representing a cell
+    pub fn grid_from_hex_value(hex: &str) -> Result<Grid, ParseError> {
         let mut grid = Grid::new();
+        let mut index = 0;
+
+        for (line, column, c) in positions(hex) {
+            if c.is_whitespace() {
+                continue;
+            }
+            let value = match c.to_digit(10) {
+                Some(v) => v as u16,
+                None => {
+                    let kind = ParseErrorKind::InvalidCharacter(c);
+                    return Err(ParseError::new(line, column, kind));
+                }
+            };
+            if index >= GRID_SIZE {
+                return Err(ParseError::new(line, column, ParseErrorKind::TooManyCells));
+            }
 
-        for (index, c) in hex.chars().enumerate() {
-            let value = c as u16 - '0' as u16;
             grid.set_cell(index, &Cell::new_with_value(value));
+            index += 1;
         }
 
-        grid
+        expect_all_cells(hex, index)?;
+        Ok(grid)
     }
 
     /// Returns a string representation of a square
@@ -110,28 +162,44 @@ pub mod utility {
         result
     }
 
-    /// Returns the ASCII representation of a grid
-    pub fn parse_from_ascii(ascii: &str) -> Grid {
+    /// Parses the ASCII representation of a grid, as made by [`ascii_grid`].
+    ///
+    /// Digits 1 to 9 are placed, `.` and `0` are empty cells, whitespace and the `|`, `-` and `+`
+    /// separators are skipped. The text must contain exactly 81 cells.
+    pub fn parse_from_ascii(ascii: &str) -> Result<Grid, ParseError> {
         let mut grid = Grid::new();
         let mut index = 0;
 
-        for line in ascii.lines() {
-            for char in line.chars() {
-                if char == '.' || char == '0' {
-                    index += 1;
-                } else if let Some(value) = char.to_digit(10) {
-                    grid.place_value(index, value as u16);
-                    index += 1;
+        for (line, column, c) in positions(ascii) {
+            if c.is_whitespace() || c == '|' || c == '-' || c == '+' {
+                continue;
+            }
+
+            let value = match c {
+                '.' => 0,
+                '0'..='9' => c.to_digit(10).unwrap_or_default() as u16,
+                _ => {
+                    let kind = ParseErrorKind::InvalidCharacter(c);
+                    return Err(ParseError::new(line, column, kind));
                 }
+            };
+            if index >= GRID_SIZE {
+                return Err(ParseError::new(line, column, ParseErrorKind::TooManyCells));
+            }
+
+            if value != 0 {
+                grid.place_value(index, value);
             }
+            index += 1;
         }
 
-        grid
+        expect_all_cells(ascii, index)?;
+        Ok(grid)
     }
 
     /// Returns a grid with all cells set to the given value
     pub fn filled_sudoku() -> Grid {
-        parse_from_ascii(
+        let grid = parse_from_ascii(
             r#"4 3 5 | 2 6 9 | 7 8 1
                6 8 2 | 5 7 1 | 4 9 3
                1 9 7 | 8 3 4 | 5 6 2
@@ -143,7 +211,9 @@ pub mod utility {
                5 1 9 | 3 2 6 | 8 7 4
                2 4 8 | 9 5 7 | 1 3 6
                7 6 3 | 4 1 8 | 2 5 9"#,
-        )
+        );
+
+        grid.expect("the filled sudoku is a valid grid")
     }
 
     /// Returns a string representation of a grid in a digit format
@@ -163,25 +233,226 @@ pub mod utility {
         chars.iter().collect()
     }
 
-    /// Returns a grid from a digit format
-    pub fn from_digit(digits: &str) -> Grid {
+    /// Returns a grid from a digit format, 81 characters where `0` or `.` is an empty cell
+    pub fn from_digit(digits: &str) -> Result<Grid, ParseError> {
+        let mut grid = Grid::new();
+        let mut index = 0;
+
+        for (line, column, c) in positions(digits.trim_end()) {
+            let value = match c {
+                '.' => 0,
+                '0'..='9' => c.to_digit(10).unwrap_or_default() as u16,
+                _ => {
+                    let kind = ParseErrorKind::InvalidCharacter(c);
+                    return Err(ParseError::new(line, column, kind));
+                }
+            };
+            if index >= GRID_SIZE {
+                return Err(ParseError::new(line, column, ParseErrorKind::TooManyCells));
+            }
+
+            if value != 0 {
+                grid.place_value(index, value);
+            }
+            index += 1;
+        }
+
+        expect_all_cells(digits.trim_end(), index)?;
+        Ok(grid)
+    }
+
+    /// Returns the pencil-mark representation of a grid, as used by HoDoKu and Sudoku Explainer.
+    ///
+    /// Every cell shows its value, or its candidates if it is not determined, and each column is
+    /// padded to its widest cell. A cell with a single candidate is shown the same as a placed
+    /// value. The layout has no form for a cell without candidates, such a cell is shown as `0`.
+    pub fn pencil_mark_grid(grid: &Grid) -> String {
+        write_pencil_marks(grid, false)
+    }
+
+    /// Returns the pencil-mark representation of a grid with a non-standard extension.
+    ///
+    /// Like [`pencil_mark_grid`], but a cell with a single candidate gets a `*` so it can be told
+    /// apart from a placed value. Other tools do not read the `*`, use [`parse_pencil_marks`].
+    pub fn pencil_mark_grid_extended(grid: &Grid) -> String {
+        write_pencil_marks(grid, true)
+    }
+
+    /// Writes the pencil-mark grid, marking single candidates with `*` if extended
+    fn write_pencil_marks(grid: &Grid, extended: bool) -> String {
+        let mut texts: Vec<String> = Vec::with_capacity(GRID_SIZE);
+        let mut widths = [1; 9];
+
+        for i in grid.iter() {
+            let cell = grid.get_cell(i);
+            let text = match cell.value() {
+                Some(v) => v.to_string(),
+                None if cell.possible_count() == 0 => String::from("0"),
+                None => {
+                    let candidates: String = cell.iter_possible().map(|m| m.to_string()).collect();
+                    match candidates.len() {
+                        1 if extended => candidates + "*",
+                        _ => candidates,
+                    }
+                }
+            };
+
+            let col = i % 9;
+            widths[col] = widths[col].max(text.len());
+            texts.push(text);
+        }
+
+        // Each box is the cells with 2 spaces in between and a space on both sides
+        let box_width = |b: usize| widths[b * 3..b * 3 + 3].iter().sum::<usize>() + 6;
+        let border = |corner: char, middle: char, end: char| {
+            let mut line = String::new();
+            line.push(corner);
+            for b in 0..3 {
+                line.push_str(&"-".repeat(box_width(b)));
+                line.push(if b == 2 { end } else { middle });
+            }
+            line.push('\n');
+            line
+        };
+
+        let mut result = border('.', '.', '.');
+
+        for row in GRID_HEIGHT_RANGE {
+            if row == 3 || row == 6 {
+                result.push_str(&border(':', '+', ':'));
+            }
+
+            result.push('|');
+            for col in GRID_WIDTH_RANGE {
+                let text = &texts[row * 9 + col];
+                let width = widths[col];
+
+                result.push_str(&format!(" {:<width$} ", text, width = width));
+                if col % 3 == 2 {
+                    result.push('|');
+                }
+            }
+            result.push('\n');
+        }
+
+        result.push_str(&border('\'', '\'', '\''));
+        result
+    }
+
+    /// Parses the pencil-mark representation of a grid, as made by [`pencil_mark_grid`] or
+    /// [`pencil_mark_grid_extended`].
+    ///
+    /// Each cell is a group of digits, a single digit is a determined value and more digits are
+    /// the candidates of the cell, `0` is a cell without candidates. A single digit followed by
+    /// `*` is a cell with only that candidate left, as written by the extended form. Whitespace and the border characters
+    /// `|`, `-`, `+`, `.`, `:` and `'` separate the cells.
+    pub fn parse_pencil_marks(text: &str) -> Result<Grid, ParseError> {
         let mut grid = Grid::new();
+        let mut index = 0;
+        // The cell that is being read, with the position it started at and whether it is a single candidate
+        let mut current: Option<(usize, usize, Cell, bool)> = None;
+
+        for (line, column, c) in positions(text) {
+            if c.is_whitespace() || matches!(c, '|' | '-' | '+' | '.' | ':' | '\'') {
+                if let Some(cell) = current.take() {
+                    push_pencil_cell(&mut grid, &mut index, cell)?;
+                }
+                continue;
+            }
+
+            let (_, _, cell, single) =
+                current.get_or_insert((line, column, Cell::new_empty(), false));
+            match c.to_digit(10) {
+                Some(v @ 1..=9) if !*single => cell.set_possible(Mark::from_value(v as u16)),
+                // A lone 0 is a cell without candidates
+                Some(0) if cell.is_empty() => {}
+                None if c == '*' && !*single && cell.possible_count() == 1 => *single = true,
+                _ => {
+                    let kind = ParseErrorKind::InvalidCharacter(c);
+                    return Err(ParseError::new(line, column, kind));
+                }
+            }
+        }
+        if let Some(cell) = current.take() {
+            push_pencil_cell(&mut grid, &mut index, cell)?;
+        }
+
+        expect_all_cells(text, index)?;
+        Ok(grid)
+    }
+
+    /// Iterates over the characters of a text, with the line and column they are at (starting at 1)
+    fn positions(text: &str) -> impl Iterator<Item = (usize, usize, char)> + '_ {
+        text.lines().enumerate().flat_map(|(line, content)| {
+            content
+                .chars()
+                .enumerate()
+                .map(move |(column, c)| (line + 1, column + 1, c))
+        })
+    }
 
-        for (i, c) in digits.chars().enumerate() {
-            if c != '0' {
-                grid.place_value(i, c.to_digit(10).unwrap() as u16);
+    /// Sets the next cell of a pencil-mark grid, a single digit without a `*` becomes determined
+    fn push_pencil_cell(
+        grid: &mut Grid,
+        index: &mut usize,
+        (line, column, cell, single): (usize, usize, Cell, bool),
+    ) -> Result<(), ParseError> {
+        if *index >= GRID_SIZE {
+            return Err(ParseError::new(line, column, ParseErrorKind::TooManyCells));
+        }
+
+        match cell.iter_possible().next() {
+            Some(mark) if cell.possible_count() == 1 && !single => {
+                grid.set_cell(*index, &Cell::new_from_mark_as_value(mark))
             }
+            _ => grid.set_cell(*index, &cell),
+        }
+        *index += 1;
+
+        Ok(())
+    }
+
+    /// Returns an error pointing at the end of the text if not all cells were read
+    fn expect_all_cells(text: &str, count: usize) -> Result<(), ParseError> {
+        if count == GRID_SIZE {
+            return Ok(());
         }
 
-        grid
+        let line = text.lines().count().max(1);
+        let column = text.lines().last().map_or(0, |l| l.chars().count()) + 1;
+
+        Err(ParseError::new(
+            line,
+            column,
+            ParseErrorKind::TooFewCells {
+                found: count,
+                expected: GRID_SIZE,
+            },
+        ))
+    }
+
+    /// Returns true if the raw data is a value between 1 and 9, or no value with some candidates
+    fn is_valid_cell_data(data: u16) -> bool {
+        let value = data & 0b0000_0000_0000_1111;
+        let unused = data & 0b0000_0000_0111_0000;
+        let candidates = data & 0b1111_1111_1000_0000;
+
+        value <= 9 && unused == 0 && (value == 0 || candidates == 0)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        grid::utility::utility::{
-            ascii_grid, filled_sudoku, grid_from_hex, hex_grid, parse_from_ascii,
+        grid::{
+            cell::Cell,
+            cell_collection::CellCollection,
+            grid::Grid,
+            mark::Mark,
+            parse_error::{ParseError, ParseErrorKind},
+            utility::utility::{
+                ascii_grid, filled_sudoku, grid_from_hex, hex_grid, parse_from_ascii,
+            },
         },
         test::util::general_tests::remove_cells_amount,
     };
@@ -200,13 +471,13 @@ mod tests {
     fn test_hex_grid() {
         let grid = &mut filled_sudoku();
         let hex = hex_grid(&grid);
-        let grid2 = &grid_from_hex(&hex);
+        let grid2 = &grid_from_hex(&hex).unwrap();
 
         assert_eq!(grid, grid2);
 
         remove_cells_amount(grid, 16);
         let hex = hex_grid(&grid);
-        let grid2 = &grid_from_hex(&hex);
+        let grid2 = &grid_from_hex(&hex).unwrap();
 
         assert_eq!(grid, grid2);
     }
@@ -215,7 +486,7 @@ mod tests {
     fn test_ascii_grid() {
         let grid = &mut filled_sudoku();
         let ascii = ascii_grid(&grid);
-        let grid2 = &mut parse_from_ascii(&ascii);
+        let grid2 = &mut parse_from_ascii(&ascii).unwrap();
 
         assert_eq!(grid, grid2);
     }
@@ -234,7 +505,8 @@ mod tests {
                5 1 9 | 3 2 6 | . . 4
                2 4 8 | 9 5 7 | . . .
                7 6 3 | 4 1 8 | 2 5 9"#,
-        );
+        )
+        .unwrap();
 
         println!("{}", grid);
 
@@ -243,4 +515,145 @@ mod tests {
 
         assert_eq!(grid.get_cell(6).is_determined(), false);
     }
+
+    #[test]
+    fn test_digits_round_trip() {
+        let grid = &mut filled_sudoku();
+        remove_cells_amount(grid, 40);
+
+        let digits = utility::to_digits(grid);
+        let parsed = utility::from_digit(&digits).unwrap();
+
+        assert_eq!(digits, utility::to_digits(&parsed));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = parse_from_ascii("1 2 3\n4 x 6").unwrap_err();
+        assert_eq!(
+            error,
+            ParseError::new(2, 3, ParseErrorKind::InvalidCharacter('x'))
+        );
+
+        let error = parse_from_ascii("1 2 3").unwrap_err();
+        assert_eq!(
+            error,
+            ParseError::new(
+                1,
+                6,
+                ParseErrorKind::TooFewCells {
+                    found: 3,
+                    expected: 81
+                }
+            )
+        );
+
+        let too_many = "0".repeat(82);
+        let error = utility::from_digit(&too_many).unwrap_err();
+        assert_eq!(error, ParseError::new(1, 82, ParseErrorKind::TooManyCells));
+
+        let error = utility::grid_from_hex_value("12a").unwrap_err();
+        assert_eq!(
+            error,
+            ParseError::new(1, 3, ParseErrorKind::InvalidCharacter('a'))
+        );
+
+        let error = grid_from_hex(&"000f".repeat(81)).unwrap_err();
+        assert_eq!(
+            error,
+            ParseError::new(1, 1, ParseErrorKind::InvalidValue(0xf))
+        );
+
+        // A cell can not have both a value and candidates
+        let error = grid_from_hex(&"0085".repeat(81)).unwrap_err();
+        assert_eq!(
+            error,
+            ParseError::new(1, 1, ParseErrorKind::InvalidValue(0x85))
+        );
+
+        // A cell split over two lines points at its first character
+        let error = grid_from_hex(&format!("00\n0f{}", "0000".repeat(80))).unwrap_err();
+        assert_eq!(
+            error,
+            ParseError::new(1, 1, ParseErrorKind::InvalidValue(0xf))
+        );
+    }
+
+    #[test]
+    fn test_from_str() {
+        let grid = filled_sudoku();
+        let parsed: Grid = utility::to_digits(&grid).parse().unwrap();
+
+        assert_eq!(grid, parsed);
+        assert!(Grid::try_from("not a grid").is_err());
+    }
+
+    #[test]
+    fn test_pencil_mark_round_trip() {
+        let mut grid = utility::from_digit(
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300",
+        )
+        .unwrap();
+        grid.unset_possible(0, Mark::N4);
+        grid.set_cell(1, &Cell::new_empty());
+        grid.set_cell(3, &Cell::new_with_possible(Mark::N7));
+
+        let text = utility::pencil_mark_grid_extended(&grid);
+        let parsed = utility::parse_pencil_marks(&text).unwrap();
+
+        assert!(text.starts_with(".--"));
+        assert_eq!(text.lines().count(), 13);
+        assert_eq!(text, utility::pencil_mark_grid_extended(&parsed));
+
+        // A single candidate stays a candidate, a placed value stays placed
+        assert_eq!(*parsed.get_cell(3), Cell::new_with_possible(Mark::N7));
+        assert_eq!(parsed.get_cell(2).value(), Some(3));
+        for index in grid.iter() {
+            assert_eq!(grid.get_cell(index), parsed.get_cell(index));
+        }
+
+        // The standard layout has no `*`, so a single candidate reads back as a placed value
+        let text = utility::pencil_mark_grid(&grid);
+        assert!(!text.contains('*'));
+        let parsed = utility::parse_pencil_marks(&text).unwrap();
+        assert_eq!(parsed.get_cell(3).value(), Some(7));
+        assert_eq!(*parsed.get_cell(1), Cell::new_empty());
+        assert_eq!(parsed.get_cell(2).value(), Some(3));
+    }
+
+    #[test]
+    fn test_parse_pencil_marks() {
+        let text = r#"
+.----------------------.----------------.-------------------.
+| 127  3     48        | 9    2    1    | 6    5     7      |
+"#;
+        let error = utility::parse_pencil_marks(text).unwrap_err();
+        assert_eq!(
+            error.kind,
+            ParseErrorKind::TooFewCells {
+                found: 9,
+                expected: 81
+            }
+        );
+
+        let text = format!("{}{}", text, "12x ".repeat(72));
+        let error = utility::parse_pencil_marks(&text).unwrap_err();
+        assert_eq!(error.kind, ParseErrorKind::InvalidCharacter('x'));
+
+        let text = text.replace("12x", "5");
+        let grid = utility::parse_pencil_marks(&text).unwrap();
+        let first = grid.get_cell(0);
+
+        assert!(!first.is_determined());
+        assert!(first.is_possible(Mark::N1) && first.is_possible(Mark::N7));
+        assert_eq!(first.possible_count(), 3);
+        assert_eq!(grid.get_cell(1).value(), Some(3));
+
+        let text = text.replacen("5 ", "5* ", 1);
+        let grid = utility::parse_pencil_marks(&text).unwrap();
+        assert_eq!(*grid.get_cell(7), Cell::new_with_possible(Mark::N5));
+
+        let error = utility::parse_pencil_marks(&text.replacen("127", "127*", 1)).unwrap_err();
+        assert_eq!(error.kind, ParseErrorKind::InvalidCharacter('*'));
+    }
 }
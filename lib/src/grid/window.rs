@@ -0,0 +1,116 @@
+use std::fmt::{Display, Formatter};
+
+use super::{cell_collection::CellCollection, coords::Coord, dimensions::Nine};
+
+/// The rows and columns that make up the windows, together they split the grid in nine parts
+const GROUPS: [[usize; 3]; 3] = [[0, 4, 8], [1, 2, 3], [5, 6, 7]];
+
+/// A region of the Windoku variant, three rows by three columns.
+///
+/// The four visible windows start at (1, 1), (1, 5), (5, 1) and (5, 5), the five hidden windows are
+/// implied by them and combine rows 0, 4 and 8 or columns 0, 4 and 8.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Window {
+    rows: [usize; 3],
+    cols: [usize; 3],
+}
+
+impl Window {
+    /// Creates the visible window that starts at the given row and column
+    pub const fn new(row: usize, col: usize) -> Self {
+        Self {
+            rows: [row, row + 1, row + 2],
+            cols: [col, col + 1, col + 2],
+        }
+    }
+
+    /// Returns true if this is one of the four visible windows
+    pub fn is_visible(&self) -> bool {
+        self.rows[0] != 0 && self.cols[0] != 0
+    }
+
+    /// Returns true if the coord is within the window
+    pub fn is_coord_in_window(&self, coord: Coord) -> bool {
+        self.rows.contains(&coord.get_row()) && self.cols.contains(&coord.get_col())
+    }
+
+    /// Returns the position of the window in the order of iter_windows
+    pub fn index(&self) -> usize {
+        let group = |first: usize| GROUPS.iter().position(|g| g[0] == first).unwrap_or(0);
+
+        group(self.rows[0]) * 3 + group(self.cols[0])
+    }
+
+    /// Iterates over the four visible windows
+    pub fn iter_visible() -> impl Iterator<Item = Window> {
+        Window::iter_windows().filter(|w| w.is_visible())
+    }
+
+    /// Iterates over all nine windows, the visible and the hidden ones
+    pub fn iter_windows() -> impl Iterator<Item = Window> {
+        (0..9).map(|i| Window {
+            rows: GROUPS[i / 3],
+            cols: GROUPS[i % 3],
+        })
+    }
+
+    /// Returns the window the coord is in, every cell is in exactly one window
+    pub fn at(coord: Coord) -> Window {
+        let group = |index: usize| GROUPS.iter().position(|g| g.contains(&index)).unwrap_or(0);
+
+        Window {
+            rows: GROUPS[group(coord.get_row())],
+            cols: GROUPS[group(coord.get_col())],
+        }
+    }
+}
+
+impl CellCollection for Window {
+    type Dimensions = Nine;
+
+    fn get_coord(&self, index: usize) -> Coord {
+        Coord::new(self.rows[index / 3], self.cols[index % 3])
+    }
+
+    fn iter(&self) -> std::ops::Range<usize> {
+        0..self.max()
+    }
+
+    fn max(&self) -> usize {
+        9
+    }
+}
+
+impl Display for Window {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[Window at {}]", self.get_coord(0))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Window;
+    use crate::grid::{cell_collection::CellCollection, coords::Coord, grid::Grid};
+
+    #[test]
+    fn test_windows_split_grid() {
+        let grid = Grid::new();
+
+        for index in grid.iter() {
+            let coord = Coord::from_index(index);
+            let windows = Window::iter_windows().filter(|w| w.is_coord_in_window(coord));
+
+            assert_eq!(windows.count(), 1);
+            assert!(Window::at(coord).is_coord_in_window(coord));
+            assert_eq!(
+                Window::iter_windows().nth(Window::at(coord).index()),
+                Some(Window::at(coord))
+            );
+        }
+
+        let visible: Vec<Window> = Window::iter_visible().collect();
+        assert_eq!(visible.len(), 4);
+        assert_eq!(visible[0], Window::new(1, 1));
+        assert_eq!(visible[3], Window::new(5, 5));
+    }
+}
@@ -1,5 +1,9 @@
+pub mod analysis;
+pub mod constraints;
+pub mod formats;
 pub mod generators;
 pub mod grid;
+pub mod sized;
 pub mod solvers;
 
 #[cfg(test)]
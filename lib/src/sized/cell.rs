@@ -0,0 +1,164 @@
+use std::marker::PhantomData;
+
+use crate::grid::{board::BoardCell, dimensions::Dimensions};
+
+/// A cell of a sized grid, holding either a value or the candidates that are still possible
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SizedCell<D: Dimensions> {
+    /// The value of the cell, 0 if it is not determined
+    value: u16,
+    /// Bit `n - 1` is set if value `n` is possible
+    candidates: u32,
+    dimensions: PhantomData<D>,
+}
+
+impl<D: Dimensions> SizedCell<D> {
+    /// Creates a new cell where every value of the grid is possible
+    pub const fn new() -> Self {
+        SizedCell {
+            value: 0,
+            candidates: (1u32 << D::SIZE) - 1,
+            dimensions: PhantomData,
+        }
+    }
+
+    /// Creates a new cell without any candidates
+    pub const fn new_empty() -> Self {
+        SizedCell {
+            value: 0,
+            candidates: 0,
+            dimensions: PhantomData,
+        }
+    }
+
+    /// Creates a new cell with the given value, the value must not be 0
+    pub const fn new_with_value(value: u16) -> Self {
+        assert!(
+            value > 0 && value as usize <= D::SIZE,
+            "value is out of range"
+        );
+
+        SizedCell {
+            value,
+            candidates: 0,
+            dimensions: PhantomData,
+        }
+    }
+
+    /// Returns true if the cell has a value
+    pub const fn is_determined(self) -> bool {
+        self.value != 0
+    }
+
+    /// Returns the value of the cell, if it is determined
+    pub const fn value(self) -> Option<u16> {
+        match self.value {
+            0 => None,
+            v => Some(v),
+        }
+    }
+
+    /// Returns true if the given value is a candidate of the cell
+    pub const fn is_possible(self, value: u16) -> bool {
+        self.candidates & SizedCell::<D>::bit(value) != 0
+    }
+
+    /// Adds the value to the candidates
+    pub fn set_possible(&mut self, value: u16) {
+        self.candidates |= SizedCell::<D>::bit(value);
+    }
+
+    /// Removes the value from the candidates
+    pub fn unset_possible(&mut self, value: u16) {
+        self.candidates &= !SizedCell::<D>::bit(value);
+    }
+
+    /// Returns the amount of candidates
+    pub const fn possible_count(self) -> u32 {
+        self.candidates.count_ones()
+    }
+
+    /// Returns the bits of the candidates, bit `n - 1` is set if value `n` is possible
+    pub const fn candidates(self) -> u32 {
+        self.candidates
+    }
+
+    /// Returns an iterator over the candidates, from low to high
+    pub fn iter_possible(self) -> impl Iterator<Item = u16> {
+        (1..=D::SIZE as u16).filter(move |v| self.is_possible(*v))
+    }
+
+    const fn bit(value: u16) -> u32 {
+        1 << (value - 1)
+    }
+}
+
+impl<D: Dimensions> Default for SizedCell<D> {
+    fn default() -> Self {
+        SizedCell::new()
+    }
+}
+
+impl<D: Dimensions> BoardCell for SizedCell<D> {
+    #[inline]
+    fn new() -> Self {
+        SizedCell::new()
+    }
+
+    #[inline]
+    fn new_with_value(value: u16) -> Self {
+        SizedCell::new_with_value(value)
+    }
+
+    #[inline]
+    fn is_determined(&self) -> bool {
+        SizedCell::is_determined(*self)
+    }
+
+    #[inline]
+    fn value(&self) -> Option<u16> {
+        SizedCell::value(*self)
+    }
+
+    #[inline]
+    fn candidates(&self) -> u32 {
+        self.candidates
+    }
+
+    #[inline]
+    fn unset_possible_value(&mut self, value: u16) {
+        self.unset_possible(value);
+    }
+
+    #[inline]
+    fn iter_possible_values(&self) -> impl Iterator<Item = u16> {
+        self.iter_possible()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SizedCell;
+    use crate::grid::dimensions::{Four, Sixteen};
+
+    #[test]
+    fn test_candidates() {
+        let mut cell = SizedCell::<Sixteen>::new();
+        assert_eq!(cell.possible_count(), 16);
+        assert!(cell.is_possible(16));
+        assert!(!cell.is_possible(17));
+
+        cell.unset_possible(3);
+        cell.unset_possible(16);
+        assert_eq!(cell.possible_count(), 14);
+        assert_eq!(
+            cell.iter_possible().take(3).collect::<Vec<_>>(),
+            vec![1, 2, 4]
+        );
+
+        let determined = SizedCell::<Sixteen>::new_with_value(12);
+        assert_eq!(determined.value(), Some(12));
+        assert_eq!(determined.possible_count(), 0);
+        assert_eq!(SizedCell::<Four>::new().possible_count(), 4);
+    }
+}
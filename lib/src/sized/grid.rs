@@ -0,0 +1,351 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::{
+    grid::{
+        board::Board,
+        cell_collection::CellCollection,
+        coords::Coord,
+        dimensions::Dimensions,
+        parse_error::{ParseError, ParseErrorKind},
+        square::Square,
+    },
+    solvers::validator,
+};
+
+use super::cell::SizedCell;
+
+/// A grid of any of the sizes described by [`Dimensions`], such as 4x4, 6x6, 12x12 and 16x16.
+///
+/// The grid is a [`Board`], so it is solved by the same solvers as the standard grid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SizedGrid<D: Dimensions> {
+    cells: Vec<SizedCell<D>>,
+}
+
+impl<D: Dimensions> SizedGrid<D> {
+    /// Creates a new grid where every value is possible in every cell
+    pub fn new() -> Self {
+        SizedGrid {
+            cells: vec![SizedCell::new(); D::CELL_COUNT],
+        }
+    }
+
+    /// Returns the amount of rows, columns, boxes and values
+    pub fn size(&self) -> usize {
+        D::SIZE
+    }
+
+    /// Returns the amount of cells in the grid
+    pub fn cell_count(&self) -> usize {
+        D::CELL_COUNT
+    }
+
+    /// Retrieves the cell at the given index
+    pub fn get_cell(&self, index: usize) -> &SizedCell<D> {
+        &self.cells[index]
+    }
+
+    /// Sets the cell at the given index
+    pub fn set_cell(&mut self, index: usize, cell: SizedCell<D>) {
+        self.cells[index] = cell;
+    }
+
+    /// Sets the value of the cell and removes the value from the candidates of its row, column and box
+    pub fn place_value(&mut self, index: usize, value: u16) {
+        self.cells[index] = SizedCell::new_with_value(value);
+        self.mark_off(Coord::at_index(index), value);
+    }
+
+    /// Returns true if no row, column or box contains the same value twice
+    pub fn is_valid(&self) -> bool {
+        validator::is_valid(self)
+    }
+
+    /// Returns true if every cell has a value and the grid is valid
+    pub fn is_solved(&self) -> bool {
+        self.count_determined_cells() == D::CELL_COUNT && self.is_valid()
+    }
+
+    /// Parses a grid.
+    ///
+    /// Values are written with the symbols `1` to `9` followed by `A` to `P`, `.` and `0` are empty
+    /// cells. Whitespace and the `|`, `-` and `+` separators are skipped.
+    pub fn parse(text: &str) -> Result<Self, ParseError> {
+        let cells = read_symbols(text)?;
+        let mut grid = SizedGrid::new();
+
+        for (index, (line, column, c)) in cells.iter().copied().enumerate() {
+            let value = match c {
+                '.' | '0' => None,
+                _ => match D::value_of(c) {
+                    Some(value) => Some(value),
+                    None => {
+                        let kind = ParseErrorKind::InvalidCharacter(c);
+                        return Err(ParseError::new(line, column, kind));
+                    }
+                },
+            };
+            if index >= D::CELL_COUNT {
+                return Err(ParseError::new(line, column, ParseErrorKind::TooManyCells));
+            }
+
+            if let Some(value) = value {
+                grid.place_value(index, value);
+            }
+        }
+
+        if cells.len() < D::CELL_COUNT {
+            let kind = ParseErrorKind::TooFewCells {
+                found: cells.len(),
+                expected: D::CELL_COUNT,
+            };
+            let line = text.lines().count().max(1);
+            let column = text.lines().last().map_or(0, |l| l.chars().count()) + 1;
+            return Err(ParseError::new(line, column, kind));
+        }
+
+        Ok(grid)
+    }
+
+    /// Returns the values of the grid as a single line, with `.` for the empty cells
+    pub fn to_line(&self) -> String {
+        self.cells
+            .iter()
+            .map(|cell| match cell.value() {
+                Some(v) => D::symbol(v),
+                None => '.',
+            })
+            .collect()
+    }
+}
+
+impl<D: Dimensions> Default for SizedGrid<D> {
+    fn default() -> Self {
+        SizedGrid::new()
+    }
+}
+
+impl<D: Dimensions> CellCollection for SizedGrid<D> {
+    type Dimensions = D;
+
+    fn get_coord(&self, index: usize) -> Coord<D> {
+        Coord::at_index(index)
+    }
+
+    fn iter(&self) -> std::ops::Range<usize> {
+        0..D::CELL_COUNT
+    }
+
+    fn max(&self) -> usize {
+        D::CELL_COUNT
+    }
+}
+
+impl<D: Dimensions> Board for SizedGrid<D> {
+    type Cell = SizedCell<D>;
+    type Box = Square<D>;
+
+    #[inline]
+    fn get_cell(&self, index: usize) -> &SizedCell<D> {
+        &self.cells[index]
+    }
+
+    #[inline]
+    fn set_cell(&mut self, index: usize, cell: &SizedCell<D>) {
+        self.cells[index] = *cell;
+    }
+
+    fn place_value_at(&mut self, coord: Coord<D>, value: u16) {
+        self.place_value(coord.get_index(), value);
+    }
+
+    fn mark_off(&mut self, coord: Coord<D>, value: u16) {
+        for peer in D::peers(coord.get_index()) {
+            self.cells[*peer].unset_possible(value);
+        }
+    }
+
+    fn boxes(&self) -> impl Iterator<Item = Square<D>> + use<D> {
+        (0..D::SIZE).map(Square::at)
+    }
+}
+
+/// Returns the characters that are cells with their line and column, skipping whitespace and separators
+fn read_symbols(text: &str) -> Result<Vec<(usize, usize, char)>, ParseError> {
+    let mut result = Vec::new();
+
+    for (line, content) in text.lines().enumerate() {
+        for (column, c) in content.chars().enumerate() {
+            if c.is_whitespace() || c == '|' || c == '-' || c == '+' {
+                continue;
+            }
+            if !c.is_ascii_alphanumeric() && c != '.' {
+                let kind = ParseErrorKind::InvalidCharacter(c);
+                return Err(ParseError::new(line + 1, column + 1, kind));
+            }
+
+            result.push((line + 1, column + 1, c));
+        }
+    }
+
+    Ok(result)
+}
+
+impl<D: Dimensions> Display for SizedGrid<D> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let size = D::SIZE;
+        // Every cell takes 2 characters and every separator between boxes another 2
+        let separator_width = size * 2 + (size / D::BOX_WIDTH - 1) * 2 - 1;
+
+        for row in 0..size {
+            if row > 0 && row % D::BOX_HEIGHT == 0 {
+                writeln!(f, "{}", "-".repeat(separator_width))?;
+            }
+
+            let mut line = String::with_capacity(separator_width);
+            for col in 0..size {
+                if col > 0 && col % D::BOX_WIDTH == 0 {
+                    line.push_str("| ");
+                }
+
+                match self.cells[Coord::<D>::at(row, col).get_index()].value() {
+                    Some(v) => line.push(D::symbol(v)),
+                    None => line.push('.'),
+                }
+                line.push(' ');
+            }
+            writeln!(f, "{}", line.trim_end())?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SizedGrid;
+    use crate::{
+        constraints::constraint_set::ConstraintSet,
+        grid::{
+            board::Board,
+            dimensions::{Dimensions, Four, Nine, Six, Sixteen, Twelve},
+            parse_error::ParseErrorKind,
+        },
+        sized::cell::SizedCell,
+        solvers::{
+            fast_solver::FastSolver, solution_counter::SolutionCounter, solver::SolveResult,
+            solver_manager::SolverManager,
+        },
+    };
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_parse_six() {
+        let grid = SizedGrid::<Six>::parse(
+            "1 . . | . . 6
+             . . 6 | 1 . .
+             ------|------
+             . 1 . | . 6 .
+             . 6 . | . 1 .
+             ------|------
+             . . 1 | 6 . .
+             6 . . | . . 1",
+        )
+        .unwrap();
+
+        assert_eq!(grid.count_determined_cells(), 12);
+        assert!(grid.is_valid());
+        // Row, column and box of the first cell all lose the 1
+        assert!(!grid.get_cell(1).is_possible(1));
+        assert!(!grid.get_cell(18).is_possible(1));
+        assert!(!grid.get_cell(7).is_possible(1));
+
+        let reparsed = SizedGrid::<Six>::parse(&grid.to_string()).unwrap();
+        assert_eq!(reparsed, grid);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = SizedGrid::<Four>::parse("12345").unwrap_err();
+        assert_eq!(error.kind, ParseErrorKind::InvalidCharacter('5'));
+
+        let error = SizedGrid::<Four>::parse("1234").unwrap_err();
+        assert_eq!(
+            error.kind,
+            ParseErrorKind::TooFewCells {
+                found: 4,
+                expected: 16
+            }
+        );
+
+        let error = SizedGrid::<Four>::parse(&".".repeat(50)).unwrap_err();
+        assert_eq!(error.kind, ParseErrorKind::TooManyCells);
+
+        let mut sixteen = String::from("G");
+        sixteen.push_str(&".".repeat(255));
+        let grid = SizedGrid::<Sixteen>::parse(&sixteen).unwrap();
+        assert_eq!(grid.get_cell(0).value(), Some(16));
+        assert_eq!(grid.to_line(), sixteen);
+    }
+
+    #[test]
+    fn test_is_valid() {
+        let mut grid = SizedGrid::<Four>::new();
+        grid.place_value(0, 1);
+        grid.place_value(5, 2);
+        assert!(grid.is_valid());
+
+        // Same box as the first cell
+        grid.set_cell(4, SizedCell::new_with_value(1));
+        assert!(!grid.is_valid());
+    }
+
+    fn solve_empty<D: Dimensions>() {
+        let grid = SizedGrid::<D>::new();
+        let mut solver = FastSolver::<_, SizedGrid<D>>::new(StdRng::seed_from_u64(3));
+        let solved = solver.solve(&grid);
+
+        assert!(solved.is_solved(), "{:?} should be solved", D::default());
+    }
+
+    #[test]
+    fn test_solve_all_sizes() {
+        solve_empty::<Four>();
+        solve_empty::<Six>();
+        solve_empty::<Nine>();
+        solve_empty::<Twelve>();
+        solve_empty::<Sixteen>();
+    }
+
+    #[test]
+    fn test_unique_puzzle() {
+        let mut solver = FastSolver::<_, SizedGrid<Sixteen>>::new(StdRng::seed_from_u64(3));
+        let solution = solver.solve(&SizedGrid::new());
+        let mut puzzle = solution.clone();
+        let size = puzzle.size();
+
+        // Clearing a single cell in each row keeps the solution unique
+        for row in 0..size {
+            let index = row * size + (row * 5) % size;
+            puzzle.set_cell(index, SizedCell::new());
+        }
+
+        assert!(SolutionCounter::is_unique(&puzzle));
+        let result = SolverManager::<SizedGrid<Sixteen>>::default().solve(puzzle);
+        assert_eq!(result.result, SolveResult::Solved);
+        assert_eq!(result.grid, solution);
+    }
+
+    #[test]
+    fn test_propagate_error() {
+        let mut grid = SizedGrid::<Four>::parse("12.. .... .... ...1").unwrap();
+        grid.set_cell(2, SizedCell::new_with_value(1));
+
+        let result = SolverManager::<SizedGrid<Four>>::default().solve(grid);
+        assert_eq!(result.result, SolveResult::Error);
+        assert_eq!(
+            SolutionCounter::count_with(&SizedGrid::<Four>::new(), &ConstraintSet::default(), 1000),
+            288
+        );
+    }
+}
@@ -1,22 +1,35 @@
 use crate::grid::{
-    cell_collection::CellCollection, column::Column, grid::Grid, mark::Mark, row::Row,
-    slice::Slice, square::Square,
+    board::{Board, BoardCell},
+    cell_collection::CellCollection,
+    column::Column,
+    dimensions::{Dimensions, MAX_SIZE},
+    grid::Grid,
+    mark::Mark,
+    row::Row,
 };
 
-use super::solver::{SolveResult, Solver};
+use super::{
+    explanation::{self, Change, Step},
+    solver::{SolveResult, Solver},
+};
 
 // The solver that turns solved cells into determined cells.
 // EC if only 1 possibility is left
 pub struct DeterminedSolver {}
 
-impl Solver for DeterminedSolver {
+impl<G: Board> Solver<G> for DeterminedSolver {
     fn name(&self) -> &'static str {
         "Determined Solver"
     }
 
-    fn solve(&self, grid: &mut Grid) -> SolveResult {
+    fn solve(&self, grid: &mut G) -> SolveResult {
         DeterminedSolver::solve(grid)
     }
+
+    /// Explains each placed value as a hidden single, looking at the boxes before the lines
+    fn explain(&self, before: &G, after: &G) -> Vec<Step> {
+        explanation::for_board(before, after, explain_hidden_singles)
+    }
 }
 
 impl DeterminedSolver {
@@ -28,7 +41,7 @@ impl DeterminedSolver {
         Box::new(Self::new())
     }
 
-    pub fn solve(grid: &mut Grid) -> SolveResult {
+    pub fn solve<G: Board>(grid: &mut G) -> SolveResult {
         let ch1 = DeterminedSolver::solve_rows(grid);
         let ch2 = DeterminedSolver::solve_columns(grid);
         let ch3 = DeterminedSolver::solve_squares(grid);
@@ -36,7 +49,7 @@ impl DeterminedSolver {
         SolveResult::from_changed(ch1 | ch2 | ch3)
     }
 
-    pub fn solve_for_mark(grid: &mut Grid, mark: Mark) -> SolveResult {
+    pub fn solve_for_mark<G: Board>(grid: &mut G, mark: Mark) -> SolveResult {
         let ch1 = DeterminedSolver::solve_rows_for_mark(grid, mark);
         let ch2 = DeterminedSolver::solve_columns_for_mark(grid, mark);
         let ch3 = DeterminedSolver::solve_squares_for_mark(grid, mark);
@@ -44,106 +57,129 @@ impl DeterminedSolver {
         SolveResult::from_changed(ch1 | ch2 | ch3)
     }
 
-    pub fn solve_rows(grid: &mut Grid) -> bool {
-        solve_area(grid, Row::iter_row())
+    pub fn solve_rows<G: Board>(grid: &mut G) -> bool {
+        solve_area(grid, rows::<G>(), u32::MAX)
     }
 
-    pub fn solve_rows_for_mark(grid: &mut Grid, mark: Mark) -> bool {
-        solve_area_for_mark(grid, Row::iter_row(), mark)
+    pub fn solve_rows_for_mark<G: Board>(grid: &mut G, mark: Mark) -> bool {
+        solve_area(grid, rows::<G>(), value_bit(mark))
     }
 
-    pub fn solve_columns(grid: &mut Grid) -> bool {
-        solve_area(grid, Column::iter_col())
+    pub fn solve_columns<G: Board>(grid: &mut G) -> bool {
+        solve_area(grid, columns::<G>(), u32::MAX)
     }
 
-    pub fn solve_columns_for_mark(grid: &mut Grid, mark: Mark) -> bool {
-        solve_area_for_mark(grid, Column::iter_col(), mark)
+    pub fn solve_columns_for_mark<G: Board>(grid: &mut G, mark: Mark) -> bool {
+        solve_area(grid, columns::<G>(), value_bit(mark))
     }
 
-    pub fn solve_squares(grid: &mut Grid) -> bool {
-        solve_area(grid, Square::iter_squares())
+    /// Solves the boxes, the squares or the regions when the grid is a jigsaw
+    pub fn solve_squares<G: Board>(grid: &mut G) -> bool {
+        let boxes = grid.boxes();
+        solve_area(grid, boxes, u32::MAX)
     }
 
-    pub fn solve_squares_for_mark(grid: &mut Grid, mark: Mark) -> bool {
-        solve_area_for_mark(grid, Square::iter_squares(), mark)
+    pub fn solve_squares_for_mark<G: Board>(grid: &mut G, mark: Mark) -> bool {
+        let boxes = grid.boxes();
+        solve_area(grid, boxes, value_bit(mark))
     }
 }
 
-#[inline(always)]
-fn solve_area<U: CellCollection, T: Iterator<Item = U>>(grid: &mut Grid, iter: T) -> bool {
-    let mut changed = false;
+fn rows<G: Board>() -> impl Iterator<Item = Row<G::Dimensions>> {
+    (0..G::Dimensions::SIZE).map(Row::at)
+}
 
-    for area in iter {
-        changed |= set_if_possible_area(grid, &area);
-    }
+fn columns<G: Board>() -> impl Iterator<Item = Column<G::Dimensions>> {
+    (0..G::Dimensions::SIZE).map(Column::at)
+}
 
-    return changed;
+fn value_bit(mark: Mark) -> u32 {
+    1 << (mark.to_value() - 1)
 }
 
 #[inline(always)]
-fn solve_area_for_mark<U: CellCollection, T: Iterator<Item = U>>(
-    grid: &mut Grid,
-    iter: T,
-    mark: Mark,
-) -> bool {
+fn solve_area<G, U, T>(grid: &mut G, iter: T, values: u32) -> bool
+where
+    G: Board,
+    U: CellCollection<Dimensions = G::Dimensions>,
+    T: Iterator<Item = U>,
+{
     let mut changed = false;
 
     for area in iter {
-        changed |= set_if_possible_area_for_mark(grid, &area, mark);
+        changed |= set_if_possible_area(grid, &area, values);
     }
 
     return changed;
 }
 
+/// Places the values, of the given bits, that have a single place left in the area
 #[inline(always)]
-fn set_if_possible_area<T: CellCollection>(grid: &mut Grid, area: &T) -> bool {
-    let data = Slice::from(grid, area);
-    let or_all = data.only_possible().or_all();
-    let mut changed = false;
+fn set_if_possible_area<G, T>(grid: &mut G, area: &T, values: u32) -> bool
+where
+    G: Board,
+    T: CellCollection<Dimensions = G::Dimensions>,
+{
+    // The candidates before anything is placed, a placement must not hide the place of another value
+    let mut data = [0u32; MAX_SIZE];
+    let mut once = 0;
+    let mut twice = 0;
+
+    for index in area.iter() {
+        let candidates = grid.get_cell_at(area.get_coord(index)).candidates();
+        data[index] = candidates;
+        twice |= once & candidates;
+        once |= candidates;
+    }
+
+    let mut singles = once & !twice & values;
+    let changed = singles != 0;
 
-    for mark in or_all.iter_possible() {
-        if data.count_possible(mark) == 1 {
-            let index = data.first_possible(mark);
-            let coord = area.get_coord(index);
-            let value = mark.to_value();
-            changed = true;
+    while singles != 0 {
+        let value = singles.trailing_zeros() as u16 + 1;
+        singles &= singles - 1;
 
-            grid.place_value_at(coord, value);
+        let bit = 1 << (value - 1);
+        if let Some(index) = area.iter().find(|i| data[*i] & bit != 0) {
+            grid.place_value_at(area.get_coord(index), value);
         }
     }
 
     return changed;
 }
 
-#[inline(always)]
-fn set_if_possible_area_for_mark<T: CellCollection>(grid: &mut Grid, area: &T, mark: Mark) -> bool {
-    let data = Slice::from(grid, area);
-    let mut changed = false;
-
-    if data.count_possible(mark) == 1 {
-        let index = data.first_possible(mark);
-        let coord = area.get_coord(index);
-        let value = mark.to_value();
-        changed = true;
-
-        grid.place_value_at(coord, value);
-    }
-
-    return changed;
+fn explain_hidden_singles(before: &Grid, after: &Grid) -> Vec<Step> {
+    let changes = explanation::placements(before, after);
+
+    explanation::explain_in_order(before, &changes, |grid, change| {
+        let (coord, value) = match change {
+            Change::Place(coord, value) => (coord, value),
+            Change::Remove(..) => return None,
+        };
+
+        explanation::lines_and_regions(grid)
+            .into_iter()
+            .find_map(|(unit, cells)| {
+                let candidates = explanation::candidates_in(grid, &cells, value)?;
+                (candidates == [coord]).then_some(Step::HiddenSingle { coord, value, unit })
+            })
+    })
 }
 
 #[cfg(test)]
 mod test {
     use crate::{
         grid::{
-            cell::Cell, cell_collection::CellCollection, coords::Coord, mark::Mark,
+            cell::Cell, cell_collection::CellCollection, coords::Coord, grid::Grid, mark::Mark,
             utility::utility,
         },
         solvers::{
             determined_solver::DeterminedSolver,
+            explanation::Step,
             mark_reset::MarkReset,
             mark_simple::MarkSimple,
             solver::{SolveResult, Solver},
+            validation_report::Unit,
         },
         test::util::general_tests,
     };
@@ -250,7 +286,8 @@ mod test {
                5 1 9 | 3 2 6 | . . 4
                2 4 8 | 9 5 7 | . . .
                7 6 3 | 4 1 8 | 2 5 9"#,
-        );
+        )
+        .unwrap();
         println!("{}", grid);
 
         MarkReset::solve(grid);
@@ -278,7 +315,8 @@ mod test {
                . 1 . | 3 . 6 | . . 4
                2 . 8 | . 5 . | . . .
                7 6 3 | 4 1 . | 2 5 ."#,
-        );
+        )
+        .unwrap();
         println!("{}", grid);
 
         MarkReset::solve(grid);
@@ -291,4 +329,35 @@ mod test {
         println!("{}", grid);
         assert_eq!(c.get_value(), 7);
     }
+
+    #[test]
+    fn test_explain_hidden_single() {
+        let grid = &mut Grid::new();
+        // The 7s around the fourth box leave it a single place, r4c2
+        for coord in [
+            Coord::new(4, 5),
+            Coord::new(5, 7),
+            Coord::new(0, 0),
+            Coord::new(7, 2),
+        ] {
+            grid.place_value_at(coord, 7);
+        }
+
+        let before = *grid;
+        let solver = DeterminedSolver::new();
+        assert_eq!(solver.solve(grid), SolveResult::Updated);
+
+        let steps = solver.explain(&before, grid);
+        let expected = Step::HiddenSingle {
+            coord: Coord::new(3, 1),
+            value: 7,
+            unit: Unit::Region(3),
+        };
+        assert!(steps.contains(&expected));
+        assert_eq!(
+            expected.to_string(),
+            "Hidden single: 7 can only go in r4c2 within box 4"
+        );
+        assert!(steps.iter().all(|s| matches!(s, Step::HiddenSingle { .. })));
+    }
 }
@@ -0,0 +1,480 @@
+use std::fmt::{Display, Formatter};
+
+use super::validation_report::Unit;
+use crate::grid::{
+    board::Board,
+    cell_collection::CellCollection,
+    column::Column,
+    constants::{GRID_HEIGHT, GRID_WIDTH},
+    coords::Coord,
+    dimensions::Nine,
+    grid::Grid,
+    mark::Mark,
+    row::Row,
+};
+
+/// A single solving step, with what was found and where
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind"))]
+pub enum Step {
+    /// The cell has only one candidate left
+    NakedSingle { coord: Coord, value: u16 },
+    /// The value has only one place left in the unit
+    HiddenSingle {
+        coord: Coord,
+        value: u16,
+        unit: Unit,
+    },
+    /// The value of the unit can only go where it meets the other unit, so the rest of the other unit can not have it
+    LockedCandidates {
+        value: u16,
+        unit: Unit,
+        other: Unit,
+        cells: Vec<Coord>,
+    },
+    /// Placing the value in the cell would leave no place for it in the unit
+    TrialAndError {
+        coord: Coord,
+        value: u16,
+        unit: Unit,
+    },
+    /// A value was placed for a reason that is not described
+    Placement { coord: Coord, value: u16 },
+    /// A candidate was removed for a reason that is not described
+    Elimination { value: u16, cells: Vec<Coord> },
+}
+
+/// The texts a locale provides, with named placeholders between braces such as `{value}`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Template {
+    NakedSingle,
+    HiddenSingle,
+    LockedCandidates,
+    TrialAndError,
+    Placement,
+    Elimination,
+    Row,
+    Column,
+    Region,
+    Diagonal,
+    AntiDiagonal,
+    Window,
+    KnightMove,
+    KingMove,
+    /// Placed between the cells of a list
+    Separator,
+}
+
+/// Provides the text of each template, implement it to explain the steps in another language
+pub trait Locale {
+    fn template(&self, template: Template) -> &str;
+}
+
+/// The built in english texts
+#[derive(Debug, Clone, Copy, Default)]
+pub struct English;
+
+impl Locale for English {
+    fn template(&self, template: Template) -> &str {
+        match template {
+            Template::NakedSingle => "Naked single: {cell} can only be {value}",
+            Template::HiddenSingle => "Hidden single: {value} can only go in {cell} within {unit}",
+            Template::LockedCandidates => {
+                "Locked candidates: {value} in {unit} can only go in {other}, so it is removed from {cells}"
+            }
+            Template::TrialAndError => {
+                "Trial and error: placing {value} in {cell} leaves no place for {value} in {unit}"
+            }
+            Template::Placement => "{value} is placed in {cell}",
+            Template::Elimination => "{value} is removed from {cells}",
+            Template::Row => "row {number}",
+            Template::Column => "column {number}",
+            Template::Region => "box {number}",
+            Template::Diagonal => "the diagonal",
+            Template::AntiDiagonal => "the anti diagonal",
+            Template::Window => "window {number}",
+            Template::KnightMove => "a knight move",
+            Template::KingMove => "a king move",
+            Template::Separator => ", ",
+        }
+    }
+}
+
+impl Step {
+    /// Returns the template that explains this step
+    pub fn template(&self) -> Template {
+        match self {
+            Step::NakedSingle { .. } => Template::NakedSingle,
+            Step::HiddenSingle { .. } => Template::HiddenSingle,
+            Step::LockedCandidates { .. } => Template::LockedCandidates,
+            Step::TrialAndError { .. } => Template::TrialAndError,
+            Step::Placement { .. } => Template::Placement,
+            Step::Elimination { .. } => Template::Elimination,
+        }
+    }
+
+    /// Returns the value that is placed or removed
+    pub fn value(&self) -> u16 {
+        match self {
+            Step::NakedSingle { value, .. }
+            | Step::HiddenSingle { value, .. }
+            | Step::LockedCandidates { value, .. }
+            | Step::TrialAndError { value, .. }
+            | Step::Placement { value, .. }
+            | Step::Elimination { value, .. } => *value,
+        }
+    }
+
+    /// Returns the cells that change, the placed cell or the cells that lose the value
+    pub fn coords(&self) -> Vec<Coord> {
+        match self {
+            Step::NakedSingle { coord, .. }
+            | Step::HiddenSingle { coord, .. }
+            | Step::TrialAndError { coord, .. }
+            | Step::Placement { coord, .. } => vec![*coord],
+            Step::LockedCandidates { cells, .. } | Step::Elimination { cells, .. } => cells.clone(),
+        }
+    }
+
+    /// Renders the explanation of the step with the texts of the locale
+    pub fn explain(&self, locale: &dyn Locale) -> String {
+        let mut args = vec![("value", self.value().to_string())];
+
+        match self {
+            Step::NakedSingle { coord, .. } | Step::Placement { coord, .. } => {
+                args.push(("cell", coord.notation()));
+            }
+            Step::HiddenSingle { coord, unit, .. } | Step::TrialAndError { coord, unit, .. } => {
+                args.push(("cell", coord.notation()));
+                args.push(("unit", explain_unit(*unit, locale)));
+            }
+            Step::LockedCandidates {
+                unit, other, cells, ..
+            } => {
+                args.push(("unit", explain_unit(*unit, locale)));
+                args.push(("other", explain_unit(*other, locale)));
+                args.push(("cells", explain_cells(cells, locale)));
+            }
+            Step::Elimination { cells, .. } => {
+                args.push(("cells", explain_cells(cells, locale)));
+            }
+        }
+
+        fill(locale.template(self.template()), &args)
+    }
+}
+
+impl Display for Step {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.explain(&English))
+    }
+}
+
+/// Renders the name of a unit, numbered from 1 like the cells
+pub fn explain_unit(unit: Unit, locale: &dyn Locale) -> String {
+    let (template, number) = match unit {
+        Unit::Row(row) => (Template::Row, row + 1),
+        Unit::Column(col) => (Template::Column, col + 1),
+        Unit::Region(index) => (Template::Region, index + 1),
+        Unit::Window(index) => (Template::Window, index + 1),
+        Unit::Diagonal => (Template::Diagonal, 0),
+        Unit::AntiDiagonal => (Template::AntiDiagonal, 0),
+        Unit::KnightMove => (Template::KnightMove, 0),
+        Unit::KingMove => (Template::KingMove, 0),
+    };
+
+    fill(locale.template(template), &[("number", number.to_string())])
+}
+
+fn explain_cells(cells: &[Coord], locale: &dyn Locale) -> String {
+    let names: Vec<String> = cells.iter().map(|c| c.notation()).collect();
+    names.join(locale.template(Template::Separator))
+}
+
+/// Replaces each `{name}` in the template with its argument, unknown placeholders are left as they are
+pub fn fill(template: &str, args: &[(&str, String)]) -> String {
+    let mut result = template.to_string();
+
+    for (name, value) in args {
+        result = result.replace(&format!("{{{}}}", name), value);
+    }
+
+    result
+}
+
+/// A single change between two grids
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Change {
+    Place(Coord, u16),
+    Remove(Coord, u16),
+}
+
+/// Returns the values placed from the before grid to the after grid
+pub fn placements(before: &Grid, after: &Grid) -> Vec<Change> {
+    after
+        .iter()
+        .filter(|i| !before.get_cell(*i).is_determined())
+        .filter_map(|i| {
+            let value = after.get_cell(i).value()?;
+            Some(Change::Place(Coord::from_index(i), value))
+        })
+        .collect()
+}
+
+/// Returns the candidates removed from the cells that are still open in the after grid
+pub fn removals(before: &Grid, after: &Grid) -> Vec<Change> {
+    let mut changes = Vec::new();
+
+    for index in after.iter().filter(|i| !after.get_cell(*i).is_determined()) {
+        let coord = Coord::from_index(index);
+        for mark in before.get_cell(index).iter_possible() {
+            if !after.is_possible_at(coord, mark) {
+                changes.push(Change::Remove(coord, mark.to_value()));
+            }
+        }
+    }
+
+    changes
+}
+
+/// Describes the placements and removals from the before grid to the after grid without a reason
+pub fn changes(before: &Grid, after: &Grid) -> Vec<Step> {
+    let mut changes = placements(before, after);
+    changes.extend(removals(before, after));
+
+    explain_in_order(before, &changes, |_, _| None)
+}
+
+/// Explains the changes of a board with the explain function, the steps are only written for the standard grid
+pub fn for_board<G: Board>(
+    before: &G,
+    after: &G,
+    explain: fn(&Grid, &Grid) -> Vec<Step>,
+) -> Vec<Step> {
+    match (before.as_grid(), after.as_grid()) {
+        (Some(before), Some(after)) => explain(before, after),
+        _ => Vec::new(),
+    }
+}
+
+/// Explains the changes with the find function, each against the grid with the changes explained so far applied.
+///
+/// A solver makes its changes one after another, so a change can depend on another one that comes later in the list.
+/// The changes are tried until none of the rest can be explained, which are then described without a reason.
+pub fn explain_in_order<F>(before: &Grid, changes: &[Change], find: F) -> Vec<Step>
+where
+    F: Fn(&Grid, Change) -> Option<Step>,
+{
+    let current = &mut before.clone();
+    let mut pending = changes.to_vec();
+    let mut steps = Vec::new();
+
+    loop {
+        let found = pending
+            .iter()
+            .enumerate()
+            .find_map(|(i, change)| find(current, *change).map(|step| (i, step)));
+
+        let (index, step) = match found {
+            Some(found) => found,
+            None => break,
+        };
+
+        match pending.remove(index) {
+            Change::Place(coord, value) => current.place_value_at(coord, value),
+            Change::Remove(coord, value) => {
+                current.unset_possible_at(coord, Mark::from_value(value))
+            }
+        }
+        push_step(&mut steps, step);
+    }
+
+    for change in pending {
+        let step = match change {
+            Change::Place(coord, value) => Step::Placement { coord, value },
+            Change::Remove(coord, value) => Step::Elimination {
+                value,
+                cells: vec![coord],
+            },
+        };
+        push_step(&mut steps, step);
+    }
+
+    steps
+}
+
+/// Adds the step, removals for the same reason are merged into one step
+fn push_step(steps: &mut Vec<Step>, step: Step) {
+    for existing in steps.iter_mut() {
+        match (existing, &step) {
+            (
+                Step::LockedCandidates {
+                    value,
+                    unit,
+                    other,
+                    cells,
+                },
+                Step::LockedCandidates {
+                    value: v,
+                    unit: u,
+                    other: o,
+                    cells: more,
+                },
+            ) if value == v && unit == u && other == o => {
+                cells.extend(more);
+                return;
+            }
+            (
+                Step::Elimination { value, cells },
+                Step::Elimination {
+                    value: v,
+                    cells: more,
+                },
+            ) if value == v => {
+                cells.extend(more);
+                return;
+            }
+            _ => {}
+        }
+    }
+
+    steps.push(step);
+}
+
+/// Returns the rows, columns and regions of the grid with their cells, the regions first
+pub fn lines_and_regions(grid: &Grid) -> Vec<(Unit, Vec<Coord>)> {
+    let mut units = Vec::new();
+
+    for (index, region) in grid.regions().into_iter().enumerate() {
+        units.push((Unit::Region(index), coords_of(&region)));
+    }
+    for row in 0..GRID_HEIGHT {
+        units.push((Unit::Row(row), coords_of(&Row::new(row))));
+    }
+    for col in 0..GRID_WIDTH {
+        units.push((Unit::Column(col), coords_of(&Column::new(col))));
+    }
+
+    units
+}
+
+fn coords_of<T: CellCollection<Dimensions = Nine>>(area: &T) -> Vec<Coord> {
+    area.iter().map(|i| area.get_coord(i)).collect()
+}
+
+/// Returns the open cells of the unit that can still hold the value, none if the value is placed in the unit
+pub fn candidates_in(grid: &Grid, cells: &[Coord], value: u16) -> Option<Vec<Coord>> {
+    if cells
+        .iter()
+        .any(|c| grid.get_cell_at(*c).value() == Some(value))
+    {
+        return None;
+    }
+
+    let mark = Mark::from_value(value);
+    Some(
+        cells
+            .iter()
+            .filter(|c| grid.is_possible_at(**c, mark))
+            .copied()
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::{explain_unit, fill, English, Locale, Step, Template};
+    use crate::{
+        grid::{coords::Coord, utility::utility},
+        solvers::{solver::SolveResult, solver_manager::SolverManager, validation_report::Unit},
+    };
+
+    /// A small dutch locale, to check that every text comes from the locale
+    struct Dutch;
+
+    impl Locale for Dutch {
+        fn template(&self, template: Template) -> &str {
+            match template {
+                Template::HiddenSingle => {
+                    "Verborgen enkele: {value} kan alleen in {cell} binnen {unit}"
+                }
+                Template::Region => "blok {number}",
+                Template::Separator => " en ",
+                Template::Elimination => "{value} valt af in {cells}",
+                _ => English.template(template),
+            }
+        }
+    }
+
+    #[test]
+    fn test_explain() {
+        let step = Step::HiddenSingle {
+            coord: Coord::new(3, 1),
+            value: 7,
+            unit: Unit::Region(3),
+        };
+
+        assert_eq!(
+            step.to_string(),
+            "Hidden single: 7 can only go in r4c2 within box 4"
+        );
+        assert_eq!(
+            step.explain(&Dutch),
+            "Verborgen enkele: 7 kan alleen in r4c2 binnen blok 4"
+        );
+
+        let step = Step::Elimination {
+            value: 3,
+            cells: vec![Coord::new(0, 0), Coord::new(8, 8)],
+        };
+        assert_eq!(step.to_string(), "3 is removed from r1c1, r9c9");
+        assert_eq!(step.explain(&Dutch), "3 valt af in r1c1 en r9c9");
+
+        let step = Step::LockedCandidates {
+            value: 5,
+            unit: Unit::Row(0),
+            other: Unit::Region(2),
+            cells: vec![Coord::new(1, 6)],
+        };
+        assert_eq!(
+            step.to_string(),
+            "Locked candidates: 5 in row 1 can only go in box 3, so it is removed from r2c7"
+        );
+    }
+
+    #[test]
+    fn test_fill() {
+        assert_eq!(
+            explain_unit(Unit::AntiDiagonal, &English),
+            "the anti diagonal"
+        );
+        assert_eq!(explain_unit(Unit::Window(0), &English), "window 1");
+        assert_eq!(
+            fill("{a} and {b}, {c}", &[("a", "1".into()), ("b", "2".into())]),
+            "1 and 2, {c}"
+        );
+    }
+
+    #[test]
+    fn test_solve_explained() {
+        let puzzle = utility::from_digit(
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300",
+        )
+        .unwrap();
+
+        let (result, steps) = SolverManager::new().solve_explained(puzzle);
+        assert_eq!(result.result, SolveResult::Solved);
+
+        // Every empty cell is filled by a step, with the value of the solution
+        let placed: Vec<&Step> = steps
+            .iter()
+            .filter(|s| matches!(s, Step::NakedSingle { .. } | Step::HiddenSingle { .. }))
+            .collect();
+        assert_eq!(placed.len(), 81 - puzzle.count_determined());
+        for step in placed {
+            let coord = step.coords()[0];
+            assert_eq!(result.grid.get_cell_at(coord).value(), Some(step.value()));
+        }
+    }
+}
@@ -1,6 +1,10 @@
 use rand::{rngs::StdRng, seq::IteratorRandom, RngCore, SeedableRng};
 
-use crate::grid::{cell_collection::CellCollection, grid::Grid, square::Square};
+use crate::grid::{
+    board::{Board, BoardCell},
+    cell_collection::CellCollection,
+    grid::Grid,
+};
 
 use super::{
     determined_solver::DeterminedSolver, is_solved::IsSolved, mark_occupy::MarkOccupy,
@@ -9,25 +13,25 @@ use super::{
 };
 
 /// A solver that uses a random number generator to solve the puzzle, forcefully goes through each square
-pub struct FastSolver<T: RngCore> {
+pub struct FastSolver<T: RngCore, G: Board = Grid> {
     pub rng: T,
-    pub solvers: SolverManager,
+    pub solvers: SolverManager<G>,
 }
 
-impl<T: RngCore> FastSolver<T> {
+impl<T: RngCore, G: Board> FastSolver<T, G> {
     /// Creates a new generator
     pub fn new(rng: T) -> Self {
-        let mut solvers = SolverManager::new();
+        let mut solvers = SolverManager::default();
         solvers.config.max_iterations = 10;
 
         Self { solvers, rng }
     }
 
-    pub fn solve(&mut self, source: &Grid) -> Grid {
+    pub fn solve(&mut self, source: &G) -> G {
         let source = &mut source.clone();
 
         if self.solvers.pre_solve(source).is_done() {
-            return *source;
+            return source.clone();
         }
 
         loop {
@@ -38,14 +42,14 @@ impl<T: RngCore> FastSolver<T> {
         }
     }
 
-    fn solve_round(&mut self, source: &Grid) -> Option<Grid> {
+    fn solve_round(&mut self, source: &G) -> Option<G> {
         let grid = &mut source.clone();
 
-        for sq in Square::iter_squares() {
+        for sq in grid.boxes() {
             let mut count = 3;
             loop {
                 let buf = &mut grid.clone();
-                let result = self.determine_area(buf, sq);
+                let result = self.determine_area(buf, &sq);
                 if result == SolveResult::Solved {
                     return Some(buf.clone());
                 }
@@ -71,18 +75,24 @@ impl<T: RngCore> FastSolver<T> {
         };
     }
 
-    fn determine_area<U: CellCollection>(&mut self, grid: &mut Grid, area: U) -> SolveResult {
+    fn determine_area<U>(&mut self, grid: &mut G, area: &U) -> SolveResult
+    where
+        U: CellCollection<Dimensions = G::Dimensions>,
+    {
         for index in area.iter() {
             let coord = area.get_coord(index);
-            let cell = &grid.get_cell_at(coord);
+            let cell = *grid.get_cell_at(coord);
             if cell.is_determined() {
                 continue;
             }
 
-            let iter = cell.iter_possible();
+            let iter = cell.iter_possible_values();
 
             match iter.choose(&mut self.rng) {
-                Some(value) => grid.place_value_at(coord, value.to_value()),
+                Some(value) => {
+                    let constraints = &self.solvers.constraints;
+                    constraints.place_value_at(grid, coord, value)
+                }
                 None => return SolveResult::Error,
             }
         }
@@ -91,7 +101,7 @@ impl<T: RngCore> FastSolver<T> {
     }
 
     #[inline(always)]
-    fn check_solve(&self, grid: &mut Grid) -> SolveResult {
+    fn check_solve(&self, grid: &mut G) -> SolveResult {
         let mut result = MarkReset::solve(grid);
         if result.is_done() {
             return result;
@@ -100,6 +110,10 @@ impl<T: RngCore> FastSolver<T> {
         if result.is_done() {
             return result;
         }
+        result |= self.solvers.constraints.propagate(grid);
+        if result.is_done() {
+            return result;
+        }
         result |= MarkOccupy::solve(grid);
         if result.is_done() {
             return result;
@@ -114,7 +128,10 @@ impl<T: RngCore> FastSolver<T> {
         }
 
         //Finalizers
-        result | IsSolved::solve(grid)
+        match result | IsSolved::solve(grid) {
+            SolveResult::Solved if !self.solvers.constraints.is_valid(grid) => SolveResult::Error,
+            result => result,
+        }
     }
 }
 
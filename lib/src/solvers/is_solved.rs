@@ -1,4 +1,4 @@
-use crate::grid::{cell::Cell, cell_collection::CellCollection, grid::Grid};
+use crate::grid::board::{Board, BoardCell};
 
 use super::solver::{SolveResult, Solver};
 
@@ -13,18 +13,17 @@ impl IsSolved {
         Box::new(Self::new())
     }
 
-    pub fn solve(grid: &Grid) -> SolveResult {
-        let mut check_possible = Cell::new_empty();
+    pub fn solve<G: Board>(grid: &G) -> SolveResult {
+        let mut check_possible = 0;
         let mut check_filled = true;
 
         for i in grid.iter() {
             let c = grid.get_cell(i);
-            check_possible = check_possible | *c;
-            check_filled &= c.get_value() > 0;
+            check_possible |= c.candidates();
+            check_filled &= c.is_determined();
         }
 
-        if check_possible.only_possible().is_empty() {
-            // If there is a cell with no possible values, then the puzzle is unsolvable
+        if check_possible == 0 {
             if !check_filled {
                 return SolveResult::Error;
             }
@@ -35,12 +34,12 @@ impl IsSolved {
     }
 }
 
-impl Solver for IsSolved {
+impl<G: Board> Solver<G> for IsSolved {
     fn name(&self) -> &'static str {
         "Is Solved"
     }
 
-    fn solve(&self, grid: &mut Grid) -> SolveResult {
+    fn solve(&self, grid: &mut G) -> SolveResult {
         IsSolved::solve(grid)
     }
 }
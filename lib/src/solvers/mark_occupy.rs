@@ -1,9 +1,18 @@
 use crate::grid::{
-    cell::Cell, cell_collection::CellCollection, column::Column, constants::GRID_HEIGHT_RANGE,
-    grid::Grid, mark::Mark, row::Row,
+    board::{Board, BoardCell},
+    cell_collection::CellCollection,
+    column::Column,
+    coords::Coord,
+    dimensions::{Dimensions, MAX_SIZE},
+    grid::Grid,
+    row::Row,
 };
 
-use super::solver::{SolveResult, Solver};
+use super::{
+    explanation::{self, Change, Step},
+    solver::{SolveResult, Solver},
+    validation_report::Unit,
+};
 
 /** Checks rows and columns, and determines if a mark value is occupied by a certain area:
  *
@@ -26,92 +35,197 @@ impl MarkOccupy {
         Box::new(Self::new())
     }
 
-    pub fn solve(grid: &mut Grid) -> SolveResult {
-        let mut changed = false;
+    pub fn solve<G: Board>(grid: &mut G) -> SolveResult {
+        if !grid.has_aligned_boxes() {
+            return SolveResult::from_changed(solve_regions(grid));
+        }
 
-        for index in GRID_HEIGHT_RANGE.step_by(3) {
-            let r1 = &Row::new(index + 0);
-            let r2 = &Row::new(index + 1);
-            let r3 = &Row::new(index + 2);
+        let size = G::Dimensions::SIZE;
+        let height = G::Dimensions::BOX_HEIGHT;
+        let width = G::Dimensions::BOX_WIDTH;
+        let mut changed = false;
 
-            changed |= solve_set(grid, r1, r2, r3);
-            changed |= solve_set(grid, r2, r1, r3);
-            changed |= solve_set(grid, r3, r1, r2);
+        // The rows of a band of boxes, split into the segments that lie in each box
+        for band in (0..size).step_by(height) {
+            for check in band..band + height {
+                let others = (band..band + height).filter(|row| *row != check);
+                changed |= solve_set(grid, &Row::at(check), others.map(Row::at), width);
+            }
         }
 
-        for index in GRID_HEIGHT_RANGE.step_by(3) {
-            let c1 = &Column::new(index + 0);
-            let c2 = &Column::new(index + 1);
-            let c3 = &Column::new(index + 2);
-
-            changed |= solve_set(grid, c1, c2, c3);
-            changed |= solve_set(grid, c2, c1, c3);
-            changed |= solve_set(grid, c3, c1, c2);
+        for band in (0..size).step_by(width) {
+            for check in band..band + width {
+                let others = (band..band + width).filter(|col| *col != check);
+                changed |= solve_set(grid, &Column::at(check), others.map(Column::at), height);
+            }
         }
 
         SolveResult::from_changed(changed)
     }
 }
 
-impl Solver for MarkOccupy {
+impl<G: Board> Solver<G> for MarkOccupy {
     fn name(&self) -> &'static str {
         "Mark Occupy"
     }
 
-    fn solve(&self, grid: &mut Grid) -> SolveResult {
+    fn solve(&self, grid: &mut G) -> SolveResult {
         MarkOccupy::solve(grid)
     }
+
+    /// Explains each removed candidate by a unit that only has the value where it meets a unit of the cell
+    fn explain(&self, before: &G, after: &G) -> Vec<Step> {
+        explanation::for_board(before, after, explain_locked_candidates)
+    }
 }
 
+/// Unsets the values that are only possible in one segment of the checked line from that segment of the other lines
 #[inline(always)]
-fn solve_set<T: CellCollection>(grid: &mut Grid, check: &T, other1: &T, other2: &T) -> bool {
-    fn get_cell<T: CellCollection>(grid: &Grid, marked: &T, index: usize) -> Cell {
-        let coord = marked.get_coord(index);
-        *grid.get_cell_at(coord)
+fn solve_set<G, T>(grid: &mut G, check: &T, others: impl Iterator<Item = T>, segment: usize) -> bool
+where
+    G: Board,
+    T: CellCollection<Dimensions = G::Dimensions>,
+{
+    let count = check.max() / segment;
+    let mut segments = [0u32; MAX_SIZE];
+    for index in check.iter() {
+        segments[index / segment] |= grid.get_cell_at(check.get_coord(index)).candidates();
     }
 
-    let c1 = get_cell(grid, check, 0) | get_cell(grid, check, 1) | get_cell(grid, check, 2);
-    let c2 = get_cell(grid, check, 3) | get_cell(grid, check, 4) | get_cell(grid, check, 5);
-    let c3 = get_cell(grid, check, 6) | get_cell(grid, check, 7) | get_cell(grid, check, 8);
-
-    fn which_square(c1: Cell, c2: Cell, c3: Cell, mark: Mark) -> Option<usize> {
-        // This mark is only possible in square one?
-        return match (
-            c1.is_possible(mark),
-            c2.is_possible(mark),
-            c3.is_possible(mark),
-        ) {
-            (true, false, false) => Some(0),
-            (false, true, false) => Some(3),
-            (false, false, true) => Some(6),
-            _ => None,
-        };
+    // Check if only possible in one square
+    let mut once = 0;
+    let mut twice = 0;
+    for candidates in segments[..count].iter() {
+        twice |= once & candidates;
+        once |= candidates;
+    }
+    let single = once & !twice;
+    if single == 0 {
+        return false;
     }
 
-    // Check if only possible in one square
     let mut changed = false;
-    let possibles: Cell = (c1 | c2 | c3).only_possible();
-
-    for mark in possibles.iter_possible() {
-        // Isolate only the cells that have this mark
-        if let Some(square) = which_square(c1, c2, c3, mark) {
-            // We found a square that can only be in one place
-            changed |= unset_three(grid, square, other1, mark);
-            changed |= unset_three(grid, square, other2, mark);
+    for other in others {
+        for (number, candidates) in segments[..count].iter().enumerate() {
+            let values = candidates & single;
+            if values == 0 {
+                continue;
+            }
+
+            for index in number * segment..(number + 1) * segment {
+                let coord = other.get_coord(index);
+                changed |= unset_values(grid, coord, values);
+            }
         }
     }
 
     changed
 }
 
-pub fn unset_three<T: CellCollection>(grid: &mut Grid, start: usize, set: &T, mark: Mark) -> bool {
-    let c1 = *grid.get_cell_at(set.get_coord(start + 0));
-    let c2 = *grid.get_cell_at(set.get_coord(start + 1));
-    let c3 = *grid.get_cell_at(set.get_coord(start + 2));
+/// Unsets the values, of the given bits, from the cell and returns true if any of them were possible
+fn unset_values<G: Board>(grid: &mut G, coord: Coord<G::Dimensions>, values: u32) -> bool {
+    let mut remove = grid.get_cell_at(coord).candidates() & values;
+    let changed = remove != 0;
+
+    while remove != 0 {
+        let value = remove.trailing_zeros() as u16 + 1;
+        remove &= remove - 1;
+        grid.unset_possible_value_at(coord, value);
+    }
+
+    changed
+}
+
+/// The same reasoning for jigsaw regions, which do not line up with the rows and columns.
+/// If a mark of a line is only possible within one region, the rest of the region can not have it, and the other way around
+fn solve_regions<G: Board>(grid: &mut G) -> bool {
+    let size = G::Dimensions::SIZE;
+    let rows = (0..size).map(|row| (0..size).map(|col| Coord::at(row, col)).collect());
+    let columns = (0..size).map(|col| (0..size).map(|row| Coord::at(row, col)).collect());
+    let lines: Vec<Vec<Coord<G::Dimensions>>> = rows.chain(columns).collect();
+    let mut changed = false;
 
-    grid.unset_possible_at(set.get_coord(start + 0), mark);
-    grid.unset_possible_at(set.get_coord(start + 1), mark);
-    grid.unset_possible_at(set.get_coord(start + 2), mark);
+    for region in grid.boxes() {
+        let region: Vec<Coord<G::Dimensions>> =
+            region.iter().map(|i| region.get_coord(i)).collect();
 
-    return (c1 | c2 | c3).is_possible(mark);
+        for line in lines.iter() {
+            if !line.iter().any(|c| region.contains(c)) {
+                continue;
+            }
+
+            for value in 1..=size as u16 {
+                changed |= claim(grid, line, &region, value);
+                changed |= claim(grid, &region, line, value);
+            }
+        }
+    }
+
+    changed
+}
+
+/// If the value is only possible where `from` overlaps `other`, it is unset from the rest of `other`
+fn claim<G: Board>(
+    grid: &mut G,
+    from: &[Coord<G::Dimensions>],
+    other: &[Coord<G::Dimensions>],
+    value: u16,
+) -> bool {
+    let mut any = false;
+    for coord in from.iter() {
+        if !grid.get_cell_at(*coord).is_possible_value(value) {
+            continue;
+        }
+        if !other.contains(coord) {
+            return false;
+        }
+        any = true;
+    }
+    if !any {
+        return false;
+    }
+
+    let mut changed = false;
+    for coord in other.iter().filter(|c| !from.contains(c)) {
+        if grid.get_cell_at(*coord).is_possible_value(value) {
+            grid.unset_possible_value_at(*coord, value);
+            changed = true;
+        }
+    }
+
+    changed
+}
+
+fn explain_locked_candidates(before: &Grid, after: &Grid) -> Vec<Step> {
+    let changes = explanation::removals(before, after);
+
+    explanation::explain_in_order(before, &changes, |grid, change| {
+        let (coord, value) = match change {
+            Change::Remove(coord, value) => (coord, value),
+            Change::Place(..) => return None,
+        };
+        let units = explanation::lines_and_regions(grid);
+
+        let mut others = units.iter().filter(|(_, cells)| cells.contains(&coord));
+        others.find_map(|(other, other_cells)| {
+            units.iter().find_map(|(unit, cells)| {
+                // A box and a line, two lines meet in no more than a single cell
+                let is_region = |u: &Unit| matches!(u, Unit::Region(_));
+                if cells.contains(&coord) || is_region(unit) == is_region(other) {
+                    return None;
+                }
+
+                let candidates = explanation::candidates_in(grid, cells, value)?;
+                let locked =
+                    !candidates.is_empty() && candidates.iter().all(|c| other_cells.contains(c));
+
+                locked.then(|| Step::LockedCandidates {
+                    value,
+                    unit: *unit,
+                    other: *other,
+                    cells: vec![coord],
+                })
+            })
+        })
+    })
 }
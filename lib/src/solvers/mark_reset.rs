@@ -1,5 +1,5 @@
 use super::solver::{SolveResult, Solver};
-use crate::grid::{cell::Cell, cell_collection::CellCollection, grid::Grid};
+use crate::grid::board::{Board, BoardCell};
 
 pub struct MarkReset {}
 
@@ -12,15 +12,14 @@ impl MarkReset {
         Box::new(Self::new())
     }
 
-    pub fn solve(grid: &mut Grid) -> SolveResult {
+    pub fn solve<G: Board>(grid: &mut G) -> SolveResult {
         for i in grid.iter() {
             let cell = *grid.get_cell(i);
 
-            // If the cell is not determined, then we need to reset the marks
             let c = if cell.is_determined() {
                 cell
             } else {
-                Cell::new()
+                G::Cell::new()
             };
 
             grid.set_cell(i, &c);
@@ -30,12 +29,12 @@ impl MarkReset {
     }
 }
 
-impl Solver for MarkReset {
+impl<G: Board> Solver<G> for MarkReset {
     fn name(&self) -> &'static str {
         "Mark Resetter"
     }
 
-    fn solve(&self, grid: &mut Grid) -> SolveResult {
+    fn solve(&self, grid: &mut G) -> SolveResult {
         MarkReset::solve(grid)
     }
 }
@@ -43,7 +42,7 @@ impl Solver for MarkReset {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::{solvers::mark_reset::MarkReset, test::util::general_tests};
+    use crate::{grid::cell::Cell, solvers::mark_reset::MarkReset, test::util::general_tests};
 
     #[test]
     fn test_mark_reset() {
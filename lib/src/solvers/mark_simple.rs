@@ -1,4 +1,4 @@
-use crate::grid::{cell_collection::CellCollection, coords::Coord, grid::Grid};
+use crate::grid::board::{Board, BoardCell};
 
 use super::solver::{SolveResult, Solver};
 
@@ -14,13 +14,12 @@ impl MarkSimple {
         Box::new(Self::new())
     }
 
-    pub fn solve(grid: &mut Grid) -> SolveResult {
+    pub fn solve<G: Board>(grid: &mut G) -> SolveResult {
         for i in grid.iter() {
             let cell = grid.get_cell(i);
 
-            //If the cell is determined, mark off that square, row and column
-            if cell.is_determined() {
-                grid.mark_off(Coord::from_index(i), cell.get_value());
+            if let Some(value) = cell.value() {
+                grid.mark_off(grid.get_coord(i), value);
             }
         }
 
@@ -28,12 +27,12 @@ impl MarkSimple {
     }
 }
 
-impl Solver for MarkSimple {
+impl<G: Board> Solver<G> for MarkSimple {
     fn name(&self) -> &'static str {
         "Mark Simple"
     }
 
-    fn solve(&self, grid: &mut Grid) -> SolveResult {
+    fn solve(&self, grid: &mut G) -> SolveResult {
         MarkSimple::solve(grid)
     }
 }
@@ -42,11 +41,11 @@ impl Solver for MarkSimple {
 mod test {
     use crate::{
         grid::{
-            cell::Cell, cell_collection::CellCollection, coords::Coord, grid::Grid, mark::Mark,
-            utility::utility,
+            board::Board, cell::Cell, cell_collection::CellCollection, coords::Coord, grid::Grid,
+            jigsaw::Jigsaw, jigsaw_grid::JigsawGrid, mark::Mark, utility::utility,
         },
         solvers::solver::Solver,
-        test::util::general_tests::get_url,
+        test::util::general_tests::{self, get_url},
     };
 
     #[test]
@@ -103,6 +102,20 @@ mod test {
         test_at_coord(coord, Mark::N9);
     }
 
+    #[test]
+    fn test_solve_jigsaw() {
+        let layout: Jigsaw = general_tests::JIGSAW_LAYOUT.parse().unwrap();
+        let grid = &mut JigsawGrid::new(layout);
+        grid.set_cell_at(Coord::new(2, 2), &Cell::new_with_value(5));
+
+        super::MarkSimple::solve(grid);
+
+        assert_eq!(layout.region(3).count_possible(grid, Mark::N5), 0);
+        // The square is no longer a region, only the row and column are marked off
+        assert!(grid.get_cell_at(Coord::new(0, 0)).is_possible(Mark::N5));
+        assert!(!grid.get_cell_at(Coord::new(2, 0)).is_possible(Mark::N5));
+    }
+
     fn test_at_coord(coord: Coord, mark: Mark) {
         let grid = &mut Grid::new();
 
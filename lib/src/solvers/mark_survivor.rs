@@ -1,18 +1,29 @@
-use crate::grid::{cell_collection::CellCollection, grid::Grid};
+use crate::grid::{
+    board::{Board, BoardCell},
+    grid::Grid,
+    mark::Mark,
+};
 
-use super::solver::{SolveResult, Solver};
+use super::{
+    explanation::{self, Change, Step},
+    solver::{SolveResult, Solver},
+};
 
 /** MarkSurvivor checks if a there is only one possibility left and turns that into a determined value */
 pub struct MarkSurvivor {}
 
-impl Solver for MarkSurvivor {
+impl<G: Board> Solver<G> for MarkSurvivor {
     fn name(&self) -> &'static str {
-        "Mark Shapes"
+        "Mark Survivor"
     }
 
-    fn solve(&self, grid: &mut Grid) -> SolveResult {
+    fn solve(&self, grid: &mut G) -> SolveResult {
         MarkSurvivor::solve(grid)
     }
+
+    fn explain(&self, before: &G, after: &G) -> Vec<Step> {
+        explanation::for_board(before, after, explain_naked_singles)
+    }
 }
 
 impl MarkSurvivor {
@@ -24,13 +35,12 @@ impl MarkSurvivor {
         Box::new(Self::new())
     }
 
-    pub fn solve(grid: &mut Grid) -> SolveResult {
+    pub fn solve<G: Board>(grid: &mut G) -> SolveResult {
         let mut result = SolveResult::Nothing;
 
-        //Loop through all the cells
         for i in grid.iter() {
             let coord = grid.get_coord(i);
-            let cell = grid.get_cell_at(coord);
+            let cell = *grid.get_cell_at(coord);
             if cell.is_determined() {
                 continue;
             }
@@ -39,15 +49,27 @@ impl MarkSurvivor {
             }
 
             //If there is only one possible value, set it
-            match cell.iter_possible().next() {
-                Some(mark) => {
-                    grid.place_value_at(coord, mark.to_value());
-                    result = SolveResult::Updated;
-                }
-                None => {}
+            let value = cell.iter_possible_values().next();
+            if let Some(value) = value {
+                grid.place_value_at(coord, value);
+                result = SolveResult::Updated;
             }
         }
 
         result
     }
 }
+
+fn explain_naked_singles(before: &Grid, after: &Grid) -> Vec<Step> {
+    let changes = explanation::placements(before, after);
+
+    explanation::explain_in_order(before, &changes, |grid, change| match change {
+        Change::Place(coord, value) => {
+            let cell = grid.get_cell_at(coord);
+            let single = cell.possible_count() == 1 && cell.is_possible(Mark::from_value(value));
+
+            single.then_some(Step::NakedSingle { coord, value })
+        }
+        Change::Remove(..) => None,
+    })
+}
@@ -1,8 +1,16 @@
 use crate::grid::{
-    cell_collection::CellCollection, grid::Grid, mark::Mark, slice::Slice, square::Square,
+    board::{Board, BoardCell},
+    cell_collection::CellCollection,
+    dimensions::{Dimensions, MAX_SIZE},
+    grid::Grid,
+    mark::Mark,
 };
 
-use super::solver::{SolveResult, Solver};
+use super::{
+    explanation::{self, Change, Step},
+    solver::{SolveResult, Solver},
+    validation_report::Unit,
+};
 
 /**
  * MarkTrailAndError takes a number, and checks if any of the possible cell left to fill in,
@@ -11,14 +19,19 @@ use super::solver::{SolveResult, Solver};
 
 pub struct MarkTrailAndError {}
 
-impl Solver for MarkTrailAndError {
+impl<G: Board> Solver<G> for MarkTrailAndError {
     fn name(&self) -> &'static str {
         "Mark Trail And Error"
     }
 
-    fn solve(&self, grid: &mut Grid) -> SolveResult {
+    fn solve(&self, grid: &mut G) -> SolveResult {
         MarkTrailAndError::solve(grid)
     }
+
+    /// Explains each removed candidate by the region that would have no place left for the value
+    fn explain(&self, before: &G, after: &G) -> Vec<Step> {
+        explanation::for_board(before, after, explain_trial_and_error)
+    }
 }
 
 impl MarkTrailAndError {
@@ -30,68 +43,72 @@ impl MarkTrailAndError {
         Box::new(Self::new())
     }
 
-    pub fn solve(grid: &mut Grid) -> SolveResult {
+    pub fn solve<G: Board>(grid: &mut G) -> SolveResult {
         let mut changed = false;
 
-        for mark in Mark::iter() {
-            changed = changed | MarkTrailAndError::solve_for_mark(grid, mark);
+        for value in 1..=G::Dimensions::SIZE as u16 {
+            changed |= MarkTrailAndError::solve_for_value(grid, value);
         }
 
         SolveResult::from_changed(changed)
     }
 
-    pub fn solve_for_mark(grid: &mut Grid, mark: Mark) -> bool {
-        let buffer = &mut Grid::empty();
-        let mut squares_determined = [false; 9];
-        let mut changed = false;
+    pub fn solve_for_mark<G: Board>(grid: &mut G, mark: Mark) -> bool {
+        MarkTrailAndError::solve_for_value(grid, mark.to_value())
+    }
 
-        for sq_index in Square::iter() {
-            let sq = Square::from_square_index(sq_index);
+    pub fn solve_for_value<G: Board>(grid: &mut G, value: u16) -> bool {
+        let size = G::Dimensions::SIZE;
+        let mut squares_determined = [false; MAX_SIZE];
+        let mut changed = false;
 
-            squares_determined[sq_index] = Slice::from(grid, &sq).is_determined(mark.to_value());
+        for (sq_index, sq) in grid.boxes().enumerate() {
+            squares_determined[sq_index] = sq.count_determined_value(grid, value) > 0;
         }
 
         let squares_count = squares_determined.iter().filter(|x| **x).count();
-        if squares_count < 3 || squares_count > 7 || grid.count_determined() < 25 {
+        // For a 9x9 grid, 3 to 7 determined squares and at least 25 determined cells
+        if squares_count < size / 3
+            || squares_count > size - 2
+            || grid.count_determined_cells() < G::Dimensions::CELL_COUNT * 25 / 81
+        {
             // 8 and 9 amount statistics are almost 100% never hitting
             // Under determined 25 is the most in-effective
             return false;
         }
 
-        for sq_index in Square::iter() {
-            let sq = Square::from_square_index(sq_index);
+        // The squares, or the regions of a jigsaw
+        let squares: Vec<G::Box> = grid.boxes().collect();
+        let buffer = &mut grid.clone();
+
+        for (sq_index, sq) in squares.iter().enumerate() {
             if squares_determined[sq_index] {
                 continue;
             }
 
             for c in sq.iter().map(|s| sq.get_coord(s)) {
                 let original_cell = *grid.get_cell_at(c);
-                if !original_cell.is_possible(mark) {
+                if !original_cell.is_possible_value(value) {
                     continue;
                 }
 
                 buffer.clone_from(grid);
                 // Place the mark
-                buffer.place_value_at(c, mark.to_value());
+                buffer.place_value_at(c, value);
 
                 // Check all the square were possible are now determined, or have at least one possible left
                 // If there is any square not determined or any possible left, this cell is causing errors and needs to be marked off
-                for old_sq_index in Square::iter() {
+                for (old_sq_index, sq) in squares.iter().enumerate() {
                     if squares_determined[sq_index] || old_sq_index == sq_index {
                         continue;
                     }
-                    let sq = Square::from_square_index(old_sq_index);
-                    let a = Slice::from(buffer, &sq);
                     //Any possible then continue
-                    if a.is_determined(mark.to_value()) {
-                        continue;
-                    }
-                    if a.any_possible(mark) {
+                    if has_value(buffer, sq, value) {
                         continue;
                     }
 
                     // This square is not determined, and has no possible left, unset this cell
-                    grid.unset_possible_at(c, mark);
+                    grid.unset_possible_value_at(c, value);
                     changed = true;
                     break;
                 }
@@ -101,3 +118,40 @@ impl MarkTrailAndError {
         changed
     }
 }
+
+/// Returns true if the value is placed in the square or still a candidate of one of its cells.
+///
+/// Goes over every cell without branching, which is faster than stopping at the first match.
+fn has_value<G: Board>(grid: &G, sq: &G::Box, value: u16) -> bool {
+    let mut found = false;
+
+    for i in sq.iter() {
+        let cell = grid.get_cell_at(sq.get_coord(i));
+        found |= (cell.value() == Some(value)) | cell.is_possible_value(value);
+    }
+
+    found
+}
+
+fn explain_trial_and_error(before: &Grid, after: &Grid) -> Vec<Step> {
+    let changes = explanation::removals(before, after);
+
+    explanation::explain_in_order(before, &changes, |grid, change| {
+        let (coord, value) = match change {
+            Change::Remove(coord, value) => (coord, value),
+            Change::Place(..) => return None,
+        };
+        let attempt = &mut grid.clone();
+        attempt.place_value_at(coord, value);
+
+        let mut regions = grid.regions().into_iter().enumerate();
+        regions.find_map(|(index, region)| {
+            let candidates = explanation::candidates_in(attempt, &region.coords(), value)?;
+            candidates.is_empty().then_some(Step::TrialAndError {
+                coord,
+                value,
+                unit: Unit::Region(index),
+            })
+        })
+    })
+}
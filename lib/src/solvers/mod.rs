@@ -1,12 +1,16 @@
 pub mod determined_solver;
 pub mod is_solved;
+pub mod explanation;
 pub mod fast_solver;
 pub mod mark_occupy;
 pub mod mark_reset;
 pub mod mark_simple;
 pub mod mark_survivor;
 pub mod mark_trail_and_error;
+pub mod samurai_solver;
+pub mod solution_counter;
 pub mod solver;
 pub mod solver_manager;
 pub mod util;
+pub mod validation_report;
 pub mod validator;
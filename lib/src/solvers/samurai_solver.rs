@@ -0,0 +1,228 @@
+use crate::grid::{cell_collection::CellCollection, coords::Coord, samurai::Samurai};
+
+use super::{solver::SolveResult, solver_manager::SolverManager};
+
+/// The result of solving a samurai sudoku
+#[derive(Debug, Clone, Copy)]
+pub struct SamuraiSolverResult {
+    pub result: SolveResult,
+    pub samurai: Samurai,
+    pub iterations: usize,
+}
+
+/// Solves a samurai sudoku by solving the grids one at a time with the solver manager,
+/// and sharing the values and candidates of the shared squares until no grid changes.
+///
+/// If that gets stuck, a value is tried for the cell with the fewest candidates.
+pub struct SamuraiSolver {
+    pub manager: SolverManager,
+}
+
+impl SamuraiSolver {
+    pub fn new() -> Self {
+        Self::new_with_manager(SolverManager::new())
+    }
+
+    pub fn new_with_manager(manager: SolverManager) -> Self {
+        Self { manager }
+    }
+
+    pub fn solve(&self, samurai: Samurai) -> SamuraiSolverResult {
+        let mut current = samurai;
+        let mut result = SolveResult::Nothing;
+
+        for index in 0..5 {
+            result |= self.manager.pre_solve(current.grid_mut(index));
+        }
+
+        let mut iterations = 0;
+        if result != SolveResult::Error {
+            result = self.search(&mut current, &mut iterations);
+        }
+
+        SamuraiSolverResult {
+            result,
+            samurai: current,
+            iterations,
+        }
+    }
+
+    /// Runs the solvers on every grid and syncs the shared squares until nothing changes
+    pub fn solve_simple(&self, samurai: &mut Samurai, iterations: &mut usize) -> SolveResult {
+        loop {
+            let before = *samurai;
+
+            if samurai.sync() == SolveResult::Error {
+                return SolveResult::Error;
+            }
+            for index in 0..5 {
+                if self.solve_grid(samurai, index, iterations) == SolveResult::Error {
+                    return SolveResult::Error;
+                }
+            }
+
+            if *samurai == before {
+                break;
+            }
+        }
+
+        match (samurai.is_filled(), samurai.is_valid()) {
+            (_, false) => SolveResult::Error,
+            (true, true) => SolveResult::Solved,
+            (false, true) => SolveResult::Nothing,
+        }
+    }
+
+    /// Runs the solver rounds on a single grid until it stops changing
+    fn solve_grid(
+        &self,
+        samurai: &mut Samurai,
+        index: usize,
+        iterations: &mut usize,
+    ) -> SolveResult {
+        let grid = samurai.grid_mut(index);
+
+        for _ in 0..self.manager.config.max_iterations {
+            *iterations += 1;
+
+            match self.manager.solve_round(grid) {
+                SolveResult::Updated => continue,
+                result => return result,
+            }
+        }
+
+        SolveResult::Nothing
+    }
+
+    fn search(&self, samurai: &mut Samurai, iterations: &mut usize) -> SolveResult {
+        let result = self.solve_simple(samurai, iterations);
+        if result != SolveResult::Nothing {
+            return result;
+        }
+
+        let (grid, coord) = match SamuraiSolver::fewest_candidates(samurai) {
+            Some(found) => found,
+            None => return SolveResult::Error,
+        };
+
+        let cell = *samurai.grid(grid).get_cell_at(coord);
+        for mark in cell.iter_possible() {
+            let attempt = &mut samurai.clone();
+            attempt.place_value_at(grid, coord, mark.to_value());
+
+            if self.search(attempt, iterations) == SolveResult::Solved {
+                *samurai = *attempt;
+                return SolveResult::Solved;
+            }
+        }
+
+        SolveResult::Error
+    }
+
+    /// Returns the open cell with the fewest candidates over all grids
+    fn fewest_candidates(samurai: &Samurai) -> Option<(usize, Coord)> {
+        let mut best: Option<(usize, Coord, u32)> = None;
+
+        for (index, grid) in samurai.grids().iter().enumerate() {
+            for i in grid.iter() {
+                let cell = grid.get_cell(i);
+                let count = cell.possible_count();
+
+                if !cell.is_determined() && best.is_none_or(|(_, _, b)| count < b) {
+                    best = Some((index, Coord::from_index(i), count));
+                }
+            }
+        }
+
+        best.map(|(index, coord, _)| (index, coord))
+    }
+}
+
+impl Default for SamuraiSolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SamuraiSolver;
+    use crate::{
+        constraints::constraint_set::ConstraintSet,
+        grid::{
+            cell::Cell,
+            coords::Coord,
+            samurai::{Samurai, CENTER},
+            square::Square,
+        },
+        solvers::{solution_counter::SolutionCounter, solver::SolveResult},
+        test::util::general_tests,
+    };
+
+    /// Builds a solved samurai around the filled sudoku, by solving each outer grid from its shared square
+    fn solved_samurai() -> Samurai {
+        let samurai = &mut Samurai::new();
+        let center = general_tests::filled_sudoku();
+
+        for coord in (0..81).map(Coord::from_index) {
+            let value = center.get_cell_at(coord).get_value();
+            samurai.place_value_at(CENTER, coord, value);
+        }
+        for outer in [0, 1, 3, 4] {
+            let solution =
+                SolutionCounter::find_with(samurai.grid(outer), &ConstraintSet::new(), 1);
+            *samurai.grid_mut(outer) = solution[0];
+        }
+
+        assert!(samurai.is_filled() && samurai.is_valid());
+        *samurai
+    }
+
+    #[test]
+    fn test_solve_through_shared_squares() {
+        let solution = solved_samurai();
+        let puzzle = &mut solution.clone();
+
+        // The outer grids lose their shared squares and more, the central grid its corners
+        for (index, grid) in [0, 1, 3, 4].into_iter().zip([8, 6, 2, 0]) {
+            let square = Square::from_square_index(grid);
+            for coord in (0..9).map(|i| square.get_coord_at(i / 3, i % 3)) {
+                puzzle.grid_mut(index).set_cell_at(coord, &Cell::new());
+            }
+            for col in [0, 4, 8] {
+                puzzle
+                    .grid_mut(index)
+                    .set_cell_at(Coord::new(4, col), &Cell::new());
+            }
+        }
+        for coord in [
+            Coord::new(0, 0),
+            Coord::new(0, 8),
+            Coord::new(8, 0),
+            Coord::new(8, 8),
+        ] {
+            puzzle.grid_mut(CENTER).set_cell_at(coord, &Cell::new());
+        }
+
+        let result = SamuraiSolver::new().solve(*puzzle);
+
+        assert_eq!(result.result, SolveResult::Solved);
+        assert_eq!(result.samurai, solution);
+    }
+
+    #[test]
+    fn test_solve_empty() {
+        let result = SamuraiSolver::new().solve(Samurai::new());
+
+        assert_eq!(result.result, SolveResult::Solved);
+        assert!(result.samurai.is_valid());
+
+        let broken = &mut Samurai::new();
+        broken.grid_mut(0).place_value_at(Coord::new(8, 8), 1);
+        broken.grid_mut(CENTER).place_value_at(Coord::new(2, 2), 2);
+        assert_eq!(
+            SamuraiSolver::new().solve(*broken).result,
+            SolveResult::Error
+        );
+    }
+}
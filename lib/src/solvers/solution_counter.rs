@@ -0,0 +1,238 @@
+use std::{error::Error, time::Instant};
+
+use rand::{seq::SliceRandom, RngCore};
+
+use crate::{
+    constraints::constraint_set::ConstraintSet,
+    grid::board::{Board, BoardCell},
+};
+
+use super::{
+    determined_solver::DeterminedSolver, mark_reset::MarkReset, mark_simple::MarkSimple,
+    mark_survivor::MarkSurvivor, solver::SolveResult, validator::is_valid,
+};
+
+/// An exhaustive solver that searches every branch, used to check if a puzzle has a unique solution.
+///
+/// The variant of the grid and the given constraints are followed, so it works for all supported puzzles
+/// and for a [`Board`] of any size.
+pub struct SolutionCounter {}
+
+impl SolutionCounter {
+    /// Counts the solutions of the grid, stops once the limit has been reached
+    pub fn count<G: Board>(grid: &G, limit: usize) -> usize {
+        SolutionCounter::count_with(grid, &ConstraintSet::default(), limit)
+    }
+
+    /// Counts the solutions of the grid under the constraints, stops once the limit has been reached
+    pub fn count_with<G: Board>(grid: &G, constraints: &ConstraintSet<G>, limit: usize) -> usize {
+        SolutionCounter::find_with(grid, constraints, limit).len()
+    }
+
+    /// Returns true if the grid has exactly one solution
+    pub fn is_unique<G: Board>(grid: &G) -> bool {
+        SolutionCounter::count(grid, 2) == 1
+    }
+
+    /// Returns true if the grid has exactly one solution under the constraints
+    pub fn is_unique_with<G: Board>(grid: &G, constraints: &ConstraintSet<G>) -> bool {
+        SolutionCounter::count_with(grid, constraints, 2) == 1
+    }
+
+    /// Returns the solutions of the grid under the constraints, at most the given limit
+    pub fn find_with<G: Board>(grid: &G, constraints: &ConstraintSet<G>, limit: usize) -> Vec<G> {
+        let mut search = Search::new(constraints, limit);
+        search.run(grid);
+
+        search.solutions
+    }
+
+    /// Returns the solutions of the grid under the constraints, at most the given limit.
+    ///
+    /// Returns an error if the deadline passes before the search is done, without a deadline the search always finishes.
+    pub fn find_within<G: Board>(
+        grid: &G,
+        constraints: &ConstraintSet<G>,
+        limit: usize,
+        deadline: Option<Instant>,
+    ) -> Result<Vec<G>, Box<dyn Error>> {
+        let mut search = Search::new(constraints, limit);
+        search.deadline = deadline;
+        search.run(grid);
+
+        search.finish()
+    }
+
+    /// Returns a random solution of the grid under the constraints, or none if there is no solution.
+    ///
+    /// Returns an error if the deadline passes before a solution is found, without a deadline the search always finishes.
+    pub fn find_random_within<G: Board>(
+        grid: &G,
+        constraints: &ConstraintSet<G>,
+        rng: &mut dyn RngCore,
+        deadline: Option<Instant>,
+    ) -> Result<Option<G>, Box<dyn Error>> {
+        let mut search = Search::new(constraints, 1);
+        search.deadline = deadline;
+        search.rng = Some(rng);
+        search.run(grid);
+
+        Ok(search.finish()?.pop())
+    }
+}
+
+/// A depth first search over the candidates, branching on the cell with the fewest candidates
+struct Search<'a, G: Board> {
+    constraints: &'a ConstraintSet<G>,
+    limit: usize,
+    solutions: Vec<G>,
+    /// The search stops once the deadline has passed
+    deadline: Option<Instant>,
+    timed_out: bool,
+    /// Shuffles the candidates of every branch, so the first solution is a random one
+    rng: Option<&'a mut dyn RngCore>,
+}
+
+impl<'a, G: Board> Search<'a, G> {
+    fn new(constraints: &'a ConstraintSet<G>, limit: usize) -> Self {
+        Self {
+            constraints,
+            limit,
+            solutions: Vec::new(),
+            deadline: None,
+            timed_out: false,
+            rng: None,
+        }
+    }
+
+    fn run(&mut self, grid: &G) {
+        if self.limit == 0 {
+            return;
+        }
+
+        let start = &mut grid.clone();
+        MarkReset::solve(start);
+        MarkSimple::solve(start);
+        self.constraints.mark_off_all(start);
+
+        self.search(start);
+    }
+
+    fn finish(self) -> Result<Vec<G>, Box<dyn Error>> {
+        match self.timed_out {
+            true => Err("The search for solutions ran out of time")?,
+            false => Ok(self.solutions),
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        self.timed_out || self.solutions.len() >= self.limit
+    }
+
+    fn search(&mut self, grid: &mut G) {
+        if let Some(deadline) = self.deadline {
+            if Instant::now() >= deadline {
+                self.timed_out = true;
+                return;
+            }
+        }
+        if propagate(grid, self.constraints) == SolveResult::Error {
+            return;
+        }
+
+        // Branch on the cell with the fewest candidates
+        let mut best: Option<(usize, u32)> = None;
+        for index in grid.iter() {
+            let cell = grid.get_cell(index);
+            if cell.is_determined() {
+                continue;
+            }
+
+            let count = cell.possible_count();
+            if count == 0 {
+                return;
+            }
+            if best.is_none_or(|(_, c)| count < c) {
+                best = Some((index, count));
+            }
+        }
+
+        let index = match best {
+            Some((index, _)) => index,
+            None => {
+                if is_valid(grid) && self.constraints.is_valid(grid) {
+                    self.solutions.push(grid.clone());
+                }
+                return;
+            }
+        };
+
+        let coord = grid.get_coord(index);
+        let mut values: Vec<u16> = grid.get_cell(index).iter_possible_values().collect();
+        if let Some(rng) = self.rng.as_mut() {
+            values.shuffle(rng);
+        }
+
+        let branch = &mut grid.clone();
+        for value in values {
+            branch.clone_from(grid);
+            self.constraints.place_value_at(branch, coord, value);
+
+            self.search(branch);
+            if self.is_done() {
+                return;
+            }
+        }
+    }
+}
+
+/// Places the singles until nothing changes, returns an error once a cell has no candidates left
+fn propagate<G: Board>(grid: &mut G, constraints: &ConstraintSet<G>) -> SolveResult {
+    loop {
+        let mut result = MarkSurvivor::solve(grid);
+        result |= DeterminedSolver::solve(grid);
+        result |= constraints.propagate(grid);
+
+        let stuck = grid.iter().any(|index| {
+            let cell = grid.get_cell(index);
+            !cell.is_determined() && cell.possible_count() == 0
+        });
+        if stuck || result == SolveResult::Error {
+            return SolveResult::Error;
+        }
+        if result != SolveResult::Updated {
+            return result;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SolutionCounter;
+    use crate::{grid::utility::utility, test::util::general_tests};
+
+    #[test]
+    fn test_unique() {
+        let grid = utility::from_digit(
+            "003020600900305001001806400008102900700000008006708200002609500800203009005010300",
+        )
+        .unwrap();
+
+        assert!(SolutionCounter::is_unique(&grid));
+        assert_eq!(
+            SolutionCounter::count(&general_tests::filled_sudoku(), 10),
+            1
+        );
+    }
+
+    #[test]
+    fn test_multiple() {
+        let mut grid = general_tests::filled_sudoku();
+        general_tests::remove_number(&mut grid, 1);
+        general_tests::remove_number(&mut grid, 2);
+
+        // Swapping the 1s and 2s always gives a second solution
+        assert_eq!(SolutionCounter::count(&grid, 10), 2);
+        assert_eq!(SolutionCounter::count(&grid, 1), 1);
+    }
+}
@@ -3,12 +3,18 @@ use std::{
     ops::{BitOr, BitOrAssign},
 };
 
-use crate::grid::grid::Grid;
+use super::explanation::{self, Step};
+use crate::grid::{board::Board, grid::Grid};
 
-pub trait Solver {
+/// A solving technique that works on any [`Board`], the standard grid unless specified
+pub trait Solver<G: Board = Grid> {
     fn name(&self) -> &'static str;
     /// Solves the given grid and returns the result.
-    fn solve(&self, grid: &mut Grid) -> SolveResult;
+    fn solve(&self, grid: &mut G) -> SolveResult;
+    /// Explains the changes this solver made, from the grid before solving to the grid after.
+    fn explain(&self, before: &G, after: &G) -> Vec<Step> {
+        explanation::for_board(before, after, explanation::changes)
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -46,13 +52,14 @@ impl SolverResult {
     }
 }
 
-pub struct AnnotatedSolverResult {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AnnotatedSolverResult<G = Grid> {
     pub result: SolveResult,
-    pub grid: Grid,
+    pub grid: G,
     pub iterations: usize,
 }
 
-impl Display for AnnotatedSolverResult {
+impl<G: Display> Display for AnnotatedSolverResult<G> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
@@ -63,6 +70,7 @@ impl Display for AnnotatedSolverResult {
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u8)]
 pub enum SolveResult {
     Nothing = 0,
@@ -1,15 +1,22 @@
 use super::{
     determined_solver::DeterminedSolver,
+    explanation::{self, Step},
     is_solved::IsSolved,
     mark_occupy::MarkOccupy,
     mark_reset::MarkReset,
     mark_simple::MarkSimple,
     mark_survivor::MarkSurvivor,
     mark_trail_and_error::MarkTrailAndError,
-    solver::{AnnotatedSolverResult, SolveResult},
+    solver::{AnnotatedSolverResult, SolveResult, Solver},
     validator::is_valid,
 };
-use crate::grid::{cell_collection::CellCollection, grid::Grid};
+use crate::{
+    constraints::constraint_set::ConstraintSet,
+    grid::{
+        board::{Board, BoardCell},
+        grid::Grid,
+    },
+};
 
 pub struct SolverManagerConfig {
     pub max_iterations: usize,
@@ -23,8 +30,11 @@ impl SolverManagerConfig {
     }
 }
 
-pub struct SolverManager {
+/// Runs the solvers in rounds until the grid is solved, works on any [`Board`], the standard grid unless specified
+pub struct SolverManager<G: Board = Grid> {
     pub config: SolverManagerConfig,
+    /// The rules that apply in addition to the rows, columns and squares
+    pub constraints: ConstraintSet<G>,
 }
 
 impl SolverManager {
@@ -37,10 +47,34 @@ impl SolverManager {
 
     /// Creates a new solver manager with the given config
     pub fn new_with_config(config: SolverManagerConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            constraints: ConstraintSet::new(),
+        }
+    }
+}
+
+impl<G: Board> Default for SolverManager<G> {
+    fn default() -> Self {
+        SolverManager::new_with_constraints(ConstraintSet::default())
+    }
+}
+
+impl<G: Board> SolverManager<G> {
+    /// Creates a new solver manager with default settings that also follows the given constraints
+    pub fn new_with_constraints(constraints: ConstraintSet<G>) -> Self {
+        Self {
+            config: SolverManagerConfig::new(),
+            constraints,
+        }
     }
 
-    pub fn pre_solve(&self, grid: &mut Grid) -> SolveResult {
+    /// Returns true if the grid breaks neither the standard rules nor any of the constraints
+    pub fn is_valid(&self, grid: &G) -> bool {
+        is_valid(grid) && self.constraints.is_valid(grid)
+    }
+
+    pub fn pre_solve(&self, grid: &mut G) -> SolveResult {
         let mut result = MarkReset::solve(grid);
         if result.is_done() {
             return result;
@@ -50,34 +84,93 @@ impl SolverManager {
         if result.is_done() {
             return result;
         }
+        result |= self.constraints.propagate(grid);
+        if result.is_done() {
+            return result;
+        }
 
         MarkOccupy::solve(grid)
     }
 
-    pub fn solve_round(&self, grid: &mut Grid) -> SolveResult {
-        //Markers
-        let mut result = MarkOccupy::solve(grid);
-        if result.is_done() {
-            return result;
+    pub fn solve_round(&self, grid: &mut G) -> SolveResult {
+        self.solve_round_with(grid, None)
+    }
+
+    /// Runs a single round like [`SolverManager::solve_round`], and adds a step for every change to the list
+    pub fn solve_round_explained(&self, grid: &mut G, steps: &mut Vec<Step>) -> SolveResult {
+        self.solve_round_with(grid, Some(steps))
+    }
+
+    /// Solves the grid as far as the solvers get without guessing, and explains every step on the way
+    pub fn solve_explained(&self, grid: G) -> (AnnotatedSolverResult<G>, Vec<Step>) {
+        let current = &mut grid.clone();
+        let mut steps = Vec::new();
+
+        // Resetting and marking off the placed values is bookkeeping, not a step
+        let mut result = MarkReset::solve(current) | MarkSimple::solve(current);
+        let before = current.clone();
+        result |= self.constraints.propagate(current);
+        steps.extend(explanation::for_board(
+            &before,
+            current,
+            explanation::changes,
+        ));
+        if !result.is_done() {
+            result = run(&MarkOccupy {}, current, Some(&mut steps)) | SolveResult::Updated;
         }
-        result |= MarkTrailAndError::solve(grid);
-        if result.is_done() {
-            return result;
+
+        let mut iterations = 0;
+        while result == SolveResult::Updated && iterations < self.config.max_iterations {
+            result = self.solve_round_explained(current, &mut steps);
+            iterations += 1;
         }
-        result |= MarkSurvivor::solve(grid);
+
+        let result = AnnotatedSolverResult {
+            result,
+            grid: current.clone(),
+            iterations,
+        };
+        (result, steps)
+    }
+
+    fn solve_round_with(&self, grid: &mut G, mut steps: Option<&mut Vec<Step>>) -> SolveResult {
+        //Constraints
+        let mut result = match steps.as_deref_mut() {
+            Some(steps) => {
+                let before = grid.clone();
+                let result = self.constraints.propagate(grid);
+                steps.extend(explanation::for_board(&before, grid, explanation::changes));
+                result
+            }
+            None => self.constraints.propagate(grid),
+        };
         if result.is_done() {
             return result;
         }
-        result |= DeterminedSolver::solve(grid);
-        if result.is_done() {
-            return result;
+
+        //Markers
+        let markers: [&dyn Solver<G>; 4] = [
+            &MarkOccupy {},
+            &MarkTrailAndError {},
+            &MarkSurvivor {},
+            &DeterminedSolver {},
+        ];
+        for solver in markers {
+            result |= run(solver, grid, steps.as_deref_mut());
+            if result.is_done() {
+                return result;
+            }
         }
 
         //Finalizers
-        result | IsSolved::solve(grid)
+        match result | IsSolved::solve(grid) {
+            // Values placed this round have not been checked against the constraints yet
+            SolveResult::Solved if !self.constraints.is_valid(grid) => SolveResult::Error,
+            result => result,
+        }
     }
 
-    pub fn solve(&self, grid: Grid) -> AnnotatedSolverResult {
+    pub fn solve(&self, grid: G) -> AnnotatedSolverResult<G> {
         let mut current = &mut grid.clone();
         let mut result = self.solve_simple(current);
         current = &mut result.grid;
@@ -100,11 +193,11 @@ impl SolverManager {
         result
     }
 
-    pub fn solve_simple(&self, grid: &mut Grid) -> AnnotatedSolverResult {
+    pub fn solve_simple(&self, grid: &mut G) -> AnnotatedSolverResult<G> {
         self.solve_internal(grid, 0)
     }
 
-    fn solve_internal(&self, grid: &mut Grid, start_iteration: usize) -> AnnotatedSolverResult {
+    fn solve_internal(&self, grid: &mut G, start_iteration: usize) -> AnnotatedSolverResult<G> {
         let mut iteration = start_iteration;
         // Pre solvers can do a lot of work, but not mark it as solved or updated
         let mut current = self.pre_solve(grid) | SolveResult::Updated;
@@ -135,29 +228,30 @@ impl SolverManager {
         }
     }
 
-    fn try_some_stuff(&self, grid: &mut Grid, start_iteration: usize) -> AnnotatedSolverResult {
+    fn try_some_stuff(&self, grid: &mut G, start_iteration: usize) -> AnnotatedSolverResult<G> {
         let best_result = &mut grid.clone();
-        let mut solved_amount = grid.count_determined();
+        let mut solved_amount = grid.count_determined_cells();
         let mut iterations = start_iteration + 1;
         let mut errors = 0;
         let mut tries: usize = 0;
 
         //Used as a buffer
-        let new_grid = &mut Grid::empty();
+        let new_grid = &mut grid.clone();
 
         //Just set some cells to see if it works
         for index in grid.iter() {
-            let cell = grid.get_cell(index);
+            let cell = *grid.get_cell(index);
 
-            for mark in cell.only_possible().iter_possible() {
+            for value in cell.iter_possible_values() {
                 tries += 1;
-                grid.clone_to(new_grid);
-                new_grid.place_value(index, mark.to_value());
+                new_grid.clone_from(grid);
+                let coord = grid.get_coord(index);
+                self.constraints.place_value_at(new_grid, coord, value);
 
                 let result = self.solve_internal(new_grid, start_iteration);
-                if result.result == SolveResult::Error || !is_valid(&result.grid) {
+                if result.result == SolveResult::Error || !self.is_valid(&result.grid) {
                     // Because we are trying random stuff, we can get into invalid states
-                    grid.unset_possible(index, mark);
+                    grid.unset_possible_value_at(coord, value);
 
                     errors += 1;
                     continue;
@@ -166,9 +260,9 @@ impl SolverManager {
                     return result;
                 }
 
-                if result.grid.count_determined() > solved_amount {
-                    result.grid.clone_to(best_result);
-                    solved_amount = best_result.count_determined();
+                if result.grid.count_determined_cells() > solved_amount {
+                    best_result.clone_from(&result.grid);
+                    solved_amount = best_result.count_determined_cells();
                     iterations = result.iterations;
                 }
             }
@@ -187,3 +281,21 @@ impl SolverManager {
         }
     }
 }
+
+/// Runs the solver, and adds its explanation of the changes when there is a list of steps
+fn run<G: Board>(
+    solver: &dyn Solver<G>,
+    grid: &mut G,
+    steps: Option<&mut Vec<Step>>,
+) -> SolveResult {
+    let steps = match steps {
+        Some(steps) => steps,
+        None => return solver.solve(grid),
+    };
+
+    let before = grid.clone();
+    let result = solver.solve(grid);
+    steps.extend(solver.explain(&before, grid));
+
+    result
+}
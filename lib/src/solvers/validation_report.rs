@@ -0,0 +1,182 @@
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+};
+
+use crate::grid::{
+    coords::Coord,
+    dimensions::{Dimensions, Nine},
+};
+
+/// The unit in which a conflict was found
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Unit {
+    Row(usize),
+    Column(usize),
+    /// A square, or an irregular region of a jigsaw
+    Region(usize),
+    Diagonal,
+    AntiDiagonal,
+    Window(usize),
+    /// Two cells a knight move apart, in an anti-knight sudoku
+    KnightMove,
+    /// Two cells a king move apart, in an anti-king sudoku
+    KingMove,
+}
+
+impl Display for Unit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Unit::Row(row) => write!(f, "[Row {}]", row),
+            Unit::Column(col) => write!(f, "[Column {}]", col),
+            Unit::Region(index) => write!(f, "[Region {}]", index),
+            Unit::Diagonal => write!(f, "[Diagonal]"),
+            Unit::AntiDiagonal => write!(f, "[Anti Diagonal]"),
+            Unit::Window(index) => write!(f, "[Window {}]", index),
+            Unit::KnightMove => write!(f, "[Knight move]"),
+            Unit::KingMove => write!(f, "[King move]"),
+        }
+    }
+}
+
+/// A single reason a grid is not valid, in a grid of the standard size unless specified
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", bound = ""))]
+pub enum Conflict<D: Dimensions = Nine> {
+    /// The same value is placed twice in a unit
+    Duplicate {
+        value: u16,
+        first: Coord<D>,
+        second: Coord<D>,
+        unit: Unit,
+    },
+    /// The cell has no value and no candidates left
+    NoCandidates { coord: Coord<D> },
+    /// The value is not placed in the unit and no cell of the unit can hold it
+    NoPlace { value: u16, unit: Unit },
+    /// The value is placed in the unit, but another cell of the unit still has it as a candidate
+    PlacedCandidate {
+        value: u16,
+        placed: Coord<D>,
+        candidate: Coord<D>,
+        unit: Unit,
+    },
+    /// The cell holds a value outside of the values of the grid
+    InvalidValue { value: u16, coord: Coord<D> },
+    /// One of the extra constraints is broken
+    Constraint { name: String, message: String },
+}
+
+impl<D: Dimensions> Conflict<D> {
+    /// Returns the cells that cause the conflict, a conflict over a whole unit or constraint has none
+    pub fn coords(&self) -> Vec<Coord<D>> {
+        match self {
+            Conflict::Duplicate { first, second, .. } => vec![*first, *second],
+            Conflict::NoCandidates { coord } => vec![*coord],
+            Conflict::PlacedCandidate {
+                placed, candidate, ..
+            } => vec![*placed, *candidate],
+            Conflict::InvalidValue { coord, .. } => vec![*coord],
+            Conflict::NoPlace { .. } | Conflict::Constraint { .. } => Vec::new(),
+        }
+    }
+}
+
+impl<D: Dimensions> Display for Conflict<D> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Conflict::Duplicate {
+                value,
+                first,
+                second,
+                unit,
+            } => write!(
+                f,
+                "Same value {} at {} and {} in {}",
+                value, first, second, unit
+            ),
+            Conflict::NoCandidates { coord } => write!(f, "No possible values at {}", coord),
+            Conflict::NoPlace { value, unit } => {
+                write!(f, "No possible place for {} in {}", value, unit)
+            }
+            Conflict::PlacedCandidate {
+                value,
+                placed,
+                candidate,
+                unit,
+            } => write!(
+                f,
+                "{} is placed at {} but still possible at {} in {}",
+                value, placed, candidate, unit
+            ),
+            Conflict::InvalidValue { value, coord } => {
+                write!(f, "Invalid value {} at {}", value, coord)
+            }
+            Conflict::Constraint { name, message } => write!(f, "{}: {}", name, message),
+        }
+    }
+}
+
+/// All the conflicts found in a grid, an empty report means the grid is valid
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct ValidationReport<D: Dimensions = Nine> {
+    conflicts: Vec<Conflict<D>>,
+}
+
+impl<D: Dimensions> ValidationReport<D> {
+    pub fn new(conflicts: Vec<Conflict<D>>) -> Self {
+        Self { conflicts }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+
+    pub fn conflicts(&self) -> &[Conflict<D>] {
+        &self.conflicts
+    }
+
+    pub fn add(&mut self, conflict: Conflict<D>) {
+        self.conflicts.push(conflict);
+    }
+
+    /// Returns every cell involved in a conflict, once each
+    pub fn coords(&self) -> Vec<Coord<D>> {
+        let mut result: Vec<Coord<D>> = Vec::new();
+
+        for coord in self.conflicts.iter().flat_map(|c| c.coords()) {
+            if !result.contains(&coord) {
+                result.push(coord);
+            }
+        }
+
+        result
+    }
+
+    /// Returns the report as an error if there are any conflicts
+    pub fn into_result(self) -> Result<(), Box<dyn Error>> {
+        match self.is_valid() {
+            true => Ok(()),
+            false => Err(Box::new(self)),
+        }
+    }
+}
+
+impl<D: Dimensions> Display for ValidationReport<D> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (index, conflict) in self.conflicts.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", conflict)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<D: Dimensions> Error for ValidationReport<D> {}
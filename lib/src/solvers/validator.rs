@@ -1,51 +1,158 @@
-use core::panic;
 use std::error::Error;
 
-use crate::grid::{
-    cell::Cell, cell_collection::CellCollection, column::Column, coords::Coord, grid::Grid,
-    mark::Mark, queries::count_determine_value, row::Row, slice::Slice,
+use super::validation_report::{Conflict, Unit, ValidationReport};
+use crate::{
+    constraints::constraint_set::ConstraintSet,
+    grid::{
+        board::{Board, BoardCell},
+        cell::Cell,
+        cell_collection::CellCollection,
+        column::Column,
+        coords::Coord,
+        dimensions::{Dimensions, Nine},
+        grid::Grid,
+        mark::Mark,
+        row::Row,
+        slice::Slice,
+    },
 };
 
-pub fn validate_grid(grid: &Grid) -> Result<(), Box<dyn Error>> {
+/// Validates the grid, the error is a [`ValidationReport`] of every conflict
+pub fn validate_grid<G: Board>(grid: &G) -> Result<(), Box<dyn Error>> {
+    validation_report(grid).into_result()
+}
+
+/// Validates the grid against the standard rules and then against every constraint
+pub fn validate_grid_with<G: Board>(
+    grid: &G,
+    constraints: &ConstraintSet<G>,
+) -> Result<(), Box<dyn Error>> {
+    validation_report_with(grid, constraints).into_result()
+}
+
+/// Lists every conflict in the grid: invalid cells, repeated values and values without a place in a unit.
+///
+/// Works on any [`Board`], the boxes are numbered as the board returns them.
+pub fn validation_report<G: Board>(grid: &G) -> ValidationReport<G::Dimensions> {
+    let size = G::Dimensions::SIZE;
+    let mut report = ValidationReport::default();
+
     for index in grid.iter() {
         let coord = grid.get_coord(index);
         let cell = grid.get_cell_at(coord);
-        validate_cell(cell, coord)?;
+
+        match cell.value() {
+            Some(value) if value == 0 || value as usize > size => {
+                report.add(Conflict::InvalidValue { value, coord })
+            }
+            None if cell.possible_count() == 0 => report.add(Conflict::NoCandidates { coord }),
+            _ => {}
+        }
     }
 
-    for r in grid.iter_rows() {
-        validate_area(grid, r)?;
+    for row in 0..size {
+        report_area(grid, Row::at(row), Unit::Row(row), &mut report);
     }
-    for c in grid.iter_columns() {
-        validate_area(grid, c)?;
+    for col in 0..size {
+        report_area(grid, Column::at(col), Unit::Column(col), &mut report);
     }
-    for s in grid.iter_squares() {
-        validate_area(grid, s)?;
+    for (index, area) in grid.boxes().enumerate() {
+        report_area(grid, area, Unit::Region(index), &mut report);
     }
 
-    Ok(())
+    report
 }
 
-pub fn is_valid(grid: &Grid) -> bool {
+/// Lists every conflict in the grid, and the first broken rule of every constraint
+pub fn validation_report_with<G: Board>(
+    grid: &G,
+    constraints: &ConstraintSet<G>,
+) -> ValidationReport<G::Dimensions> {
+    let mut report = validation_report(grid);
+
+    for constraint in constraints.iter() {
+        constraint.report(grid, &mut report);
+    }
+
+    report
+}
+
+/// Adds the repeated values, the values without a place and the placed values that are still candidates in the area
+pub fn report_area<G, T>(
+    grid: &G,
+    area: T,
+    unit: Unit,
+    report: &mut ValidationReport<G::Dimensions>,
+) where
+    G: Board,
+    T: CellCollection<Dimensions = G::Dimensions>,
+{
+    let coords: Vec<Coord<G::Dimensions>> = area.iter().map(|i| area.get_coord(i)).collect();
+
+    for value in 1..=G::Dimensions::SIZE as u16 {
+        let placed: Vec<Coord<G::Dimensions>> = coords
+            .iter()
+            .filter(|c| grid.get_cell_at(**c).value() == Some(value))
+            .copied()
+            .collect();
+        let possible: Vec<Coord<G::Dimensions>> = coords
+            .iter()
+            .filter(|c| grid.get_cell_at(**c).is_possible_value(value))
+            .copied()
+            .collect();
+
+        match placed.first() {
+            None if possible.is_empty() => report.add(Conflict::NoPlace { value, unit }),
+            None => {}
+            Some(first) => {
+                for second in placed.iter().skip(1) {
+                    report.add(Conflict::Duplicate {
+                        value,
+                        first: *first,
+                        second: *second,
+                        unit,
+                    });
+                }
+                for candidate in possible {
+                    report.add(Conflict::PlacedCandidate {
+                        value,
+                        placed: *first,
+                        candidate,
+                        unit,
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Returns true if every cell has a value or a candidate, and no row, column or box repeats a value or
+/// has no place left for one
+pub fn is_valid<G: Board>(grid: &G) -> bool {
+    let size = G::Dimensions::SIZE;
+
     for index in grid.iter() {
-        let coord = grid.get_coord(index);
-        let cell = grid.get_cell_at(coord);
-        if is_valid_cell(cell) == false {
+        let cell = grid.get_cell(index);
+        let valid = match cell.value() {
+            Some(v) => v >= 1 && v as usize <= size,
+            None => cell.candidates() != 0,
+        };
+        if !valid {
             return false;
         }
     }
 
-    for r in grid.iter_rows() {
+    for r in (0..size).map(Row::at) {
         if is_valid_area(grid, r) == false {
             return false;
         }
     }
-    for c in grid.iter_columns() {
+    for c in (0..size).map(Column::at) {
         if is_valid_area(grid, c) == false {
             return false;
         }
     }
-    for s in grid.iter_squares() {
+    for s in grid.boxes() {
         if is_valid_area(grid, s) == false {
             return false;
         }
@@ -54,34 +161,38 @@ pub fn is_valid(grid: &Grid) -> bool {
     true
 }
 
-pub fn validate_placement(grid: &Grid, coord: Coord) -> Result<(), Box<dyn Error>> {
-    //println!("{}", utility::ascii_grid(&grid));
-
-    let cell = grid.get_cell_at(coord);
-    if !cell.is_determined() {
-        panic!("Cell at {} is not determined", coord);
-    }
-    let v = cell.get_value();
+/// Returns true if the grid breaks neither the standard rules nor any of the constraints
+pub fn is_valid_with(grid: &Grid, constraints: &ConstraintSet) -> bool {
+    is_valid(grid) && constraints.is_valid(grid)
+}
 
-    // Row should only be 1
-    let determined = count_determine_value(grid, Row::new(coord.get_row()), v);
-    if determined != 1 {
-        panic!("Row {} should only be 1", coord.get_row());
-    }
+/// Validates the value placed at the coord, the error is a [`ValidationReport`] of the values it repeats
+pub fn validate_placement<G: Board>(
+    grid: &G,
+    coord: Coord<G::Dimensions>,
+) -> Result<(), Box<dyn Error>> {
+    validate_placement_with(grid, &ConstraintSet::default(), coord)
+}
 
-    // Column should only be 1
-    let determined = count_determine_value(grid, Column::new(coord.get_col()), v);
-    if determined != 1 {
-        panic!("Column {} should only be 1", coord.get_col());
+/// Validates the value placed at the coord against the standard rules and the constraints
+pub fn validate_placement_with<G: Board>(
+    grid: &G,
+    constraints: &ConstraintSet<G>,
+    coord: Coord<G::Dimensions>,
+) -> Result<(), Box<dyn Error>> {
+    if !grid.get_cell_at(coord).is_determined() {
+        let msg = format!("Cell at {} is not determined", coord);
+        return Err(msg.into());
     }
 
-    // Square should only be 1
-    let determined = count_determine_value(grid, grid.get_square_at(coord), v);
-    if determined != 1 {
-        panic!("Square should only be 1");
-    }
+    let conflicts = validation_report_with(grid, constraints)
+        .conflicts()
+        .iter()
+        .filter(|c| matches!(c, Conflict::Duplicate { first, second, .. } if *first == coord || *second == coord))
+        .cloned()
+        .collect();
 
-    Ok(())
+    ValidationReport::new(conflicts).into_result()
 }
 
 pub fn validate_cell(cell: &Cell, coord: Coord) -> Result<(), Box<dyn Error>> {
@@ -118,7 +229,10 @@ pub fn is_valid_cell(cell: &Cell) -> bool {
     };
 }
 
-pub fn validate_area<T: CellCollection>(grid: &Grid, area: T) -> Result<(), Box<dyn Error>> {
+pub fn validate_area<T: CellCollection<Dimensions = Nine>>(
+    grid: &Grid,
+    area: T,
+) -> Result<(), Box<dyn Error>> {
     let first: Coord = area.get_coord(0);
     let last = area.get_coord(area.max() - 1);
     let slice = Slice::from(grid, &area);
@@ -153,37 +267,47 @@ pub fn validate_area<T: CellCollection>(grid: &Grid, area: T) -> Result<(), Box<
     Ok(())
 }
 
-pub fn is_valid_area<T: CellCollection>(grid: &Grid, area: T) -> bool {
-    let slice = Slice::from(grid, &area);
+pub fn is_valid_area<G, T>(grid: &G, area: T) -> bool
+where
+    G: Board,
+    T: CellCollection<Dimensions = G::Dimensions>,
+{
+    let values = (1u32 << G::Dimensions::SIZE) - 1;
+    let mut placed = 0;
+    let mut repeated = 0;
+    let mut possible = 0;
 
-    for mark in Mark::iter() {
-        let determined = slice.count_determined_value(mark.to_value());
-        let possible = slice.any_possible(mark);
+    for index in area.iter() {
+        let cell = grid.get_cell_at(area.get_coord(index));
+        possible |= cell.candidates();
 
-        // If value is placed more than once, Then it's invalid
-        match determined {
-            // If value is not placed then it must be possible somewhere
-            0 => {
-                if !possible {
-                    return false;
-                }
-            }
-            // If value is placed then it can't be possible
-            1 => {
-                if possible {
-                    return false;
-                }
-            }
-            _ => return false,
+        if let Some(value) = cell.value() {
+            let bit = 1 << (value - 1);
+            repeated |= placed & bit;
+            placed |= bit;
         }
     }
 
-    true
+    // A value can be placed only once, and is either placed or still possible somewhere
+    repeated == 0 && placed & possible == 0 && (placed | possible) & values == values
 }
 
 #[cfg(test)]
 mod test {
-    use crate::grid::utility::utility::parse_from_ascii;
+    use super::validation_report;
+    use crate::{
+        constraints::{chess_move::AntiKing, constraint_set::ConstraintSet, diagonal::sudoku_x},
+        grid::{
+            board::Board, cell::Cell, coords::Coord, dimensions::Four, grid::Grid, jigsaw::Jigsaw,
+            jigsaw_grid::JigsawGrid, mark::Mark, utility::utility::parse_from_ascii,
+        },
+        sized::grid::SizedGrid,
+        solvers::{
+            mark_simple::MarkSimple,
+            validation_report::{Conflict, Unit, ValidationReport},
+        },
+        test::util::general_tests,
+    };
 
     #[test]
     pub fn test_specific_case() {
@@ -199,8 +323,184 @@ mod test {
              . . . | . . . | . . .
              . . . | . 5 . | . . .
              . . . | . . . | . . .",
-        );
+        )
+        .unwrap();
+
+        assert!(super::is_valid(&grid));
+    }
 
+    #[test]
+    pub fn test_diagonal_conflict() {
+        let grid = parse_from_ascii(
+            "1 . . | . . . | . . .
+             . . . | . . . | . . .
+             . . . | . . . | . . .
+             ------|-------|------
+             . . . | . . . | . . .
+             . . . | . 1 . | . . .
+             . . . | . . . | . . .
+             ------|-------|------
+             . . . | . . . | . . .
+             . . . | . . . | . . .
+             . . . | . . . | . . .",
+        )
+        .unwrap();
         assert!(super::is_valid(&grid));
+
+        let constraints = sudoku_x();
+        assert!(!super::is_valid_with(&grid, &constraints));
+        assert!(super::validate_grid_with(&grid, &constraints).is_err());
+    }
+
+    #[test]
+    pub fn test_report_lists_every_conflict() {
+        let mut grid = parse_from_ascii(
+            "1 . . | . . 1 | . . .
+             . . . | . . . | . . .
+             . . . | . . . | . . .
+             ------|-------|------
+             . . . | . . . | . . .
+             . . . | . . . | . . .
+             . . . | . . . | . . .
+             ------|-------|------
+             . . . | . . . | . . 2
+             . . . | . . . | . . .
+             . . . | . . . | . . 2",
+        )
+        .unwrap();
+        MarkSimple::solve(&mut grid);
+        grid.set_cell_at(Coord::new(4, 4), &Cell::new_empty());
+
+        let report = validation_report(&grid);
+        let conflicts = report.conflicts();
+
+        assert!(conflicts.contains(&Conflict::Duplicate {
+            value: 1,
+            first: Coord::new(0, 0),
+            second: Coord::new(0, 5),
+            unit: Unit::Row(0),
+        }));
+        assert!(conflicts.contains(&Conflict::Duplicate {
+            value: 2,
+            first: Coord::new(6, 8),
+            second: Coord::new(8, 8),
+            unit: Unit::Column(8),
+        }));
+        assert!(conflicts.contains(&Conflict::Duplicate {
+            value: 2,
+            first: Coord::new(6, 8),
+            second: Coord::new(8, 8),
+            unit: Unit::Region(8),
+        }));
+        assert!(conflicts.contains(&Conflict::NoCandidates {
+            coord: Coord::new(4, 4)
+        }));
+        assert!(!report.is_valid());
+        assert_eq!(report.coords().len(), 5);
+        assert!(super::validate_grid(&grid).is_err());
+    }
+
+    #[test]
+    pub fn test_report_no_place() {
+        let mut grid = Grid::new();
+        for col in 0..9 {
+            grid.unset_possible_at(Coord::new(3, col), Mark::N7);
+        }
+
+        let report = validation_report(&grid);
+        assert_eq!(
+            report.conflicts(),
+            &[Conflict::NoPlace {
+                value: 7,
+                unit: Unit::Row(3)
+            }]
+        );
+        assert_eq!(report.to_string(), "No possible place for 7 in [Row 3]");
+    }
+
+    #[test]
+    pub fn test_validate_placement() {
+        let constraints = ConstraintSet::new().with(AntiKing);
+        let mut grid = Grid::new();
+        grid.place_value_at(Coord::new(0, 0), 5);
+        assert!(super::validate_placement(&grid, Coord::new(0, 0)).is_ok());
+        assert!(super::validate_placement(&grid, Coord::new(0, 1)).is_err());
+
+        grid.set_cell_at(Coord::new(1, 1), &Cell::new_with_value(5));
+        grid.set_cell_at(Coord::new(0, 7), &Cell::new_with_value(5));
+        assert_eq!(
+            super::validate_placement(&grid, Coord::new(0, 0))
+                .unwrap_err()
+                .downcast_ref::<ValidationReport>()
+                .unwrap()
+                .conflicts()
+                .len(),
+            2
+        );
+        let error =
+            super::validate_placement_with(&grid, &constraints, Coord::new(0, 0)).unwrap_err();
+        let report = error.downcast_ref::<ValidationReport>().unwrap();
+
+        let units: Vec<Unit> = report
+            .conflicts()
+            .iter()
+            .filter_map(|c| match c {
+                Conflict::Duplicate { unit, .. } => Some(*unit),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(units, vec![Unit::Row(0), Unit::Region(0), Unit::KingMove]);
+    }
+
+    #[test]
+    pub fn test_report_jigsaw() {
+        let layout: Jigsaw = general_tests::JIGSAW_LAYOUT.parse().unwrap();
+        let mut grid = JigsawGrid::new(layout);
+        grid.set_cell_at(Coord::new(2, 2), &Cell::new_with_value(5));
+        grid.set_cell_at(Coord::new(3, 1), &Cell::new_with_value(5));
+
+        // The cells share a region of the layout, but not a square
+        let report = validation_report(&grid);
+        assert!(report.conflicts().contains(&Conflict::Duplicate {
+            value: 5,
+            first: Coord::new(2, 2),
+            second: Coord::new(3, 1),
+            unit: Unit::Region(3),
+        }));
+        assert!(validation_report(grid.grid())
+            .conflicts()
+            .iter()
+            .all(|c| !matches!(c, Conflict::Duplicate { .. })));
+    }
+
+    #[test]
+    pub fn test_report_sized() {
+        let grid = SizedGrid::<Four>::parse("11.. .... .... ....").unwrap();
+        let report = validation_report(&grid);
+        let duplicate = |unit| Conflict::Duplicate {
+            value: 1,
+            first: Coord::<Four>::at(0, 0),
+            second: Coord::at(0, 1),
+            unit,
+        };
+
+        assert!(report.conflicts().contains(&duplicate(Unit::Row(0))));
+        assert!(report.conflicts().contains(&duplicate(Unit::Region(0))));
+        assert_eq!(report.coords(), vec![Coord::at(0, 0), Coord::at(0, 1)]);
+        assert!(super::validate_grid(&grid).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    pub fn test_report_serialize() {
+        let report = ValidationReport::new(vec![Conflict::NoCandidates {
+            coord: Coord::new(1, 2),
+        }]);
+        let json = serde_json::to_string(&report).unwrap();
+
+        assert_eq!(
+            json,
+            r#"{"conflicts":[{"kind":"NoCandidates","coord":{"row":1,"col":2}}]}"#
+        );
     }
 }
@@ -19,7 +19,8 @@ mod test {
              . . . | . . . | . . .\n\
              . . . | . . . | . . .\n\
              . . . | . . . | . . .",
-        );
+        )
+        .unwrap();
 
         let solver = SolverManager::new();
         let result = solver.solve_simple(grid);
@@ -16,7 +16,8 @@ mod tests {
                . 1 . | 3 . 6 | . . 4
                2 . 8 | . 5 . | . . .
                7 6 3 | 4 1 . | 2 5 ."#,
-        );
+        )
+        .unwrap();
 
         general_tests::test_should_solve(grid);
     }
@@ -35,7 +36,8 @@ mod tests {
                5 1 9 | 3 2 6 | . . 4
                2 4 8 | 9 5 7 | 1 3 6
                7 6 3 | 4 1 8 | 2 5 9"#,
-        );
+        )
+        .unwrap();
 
         general_tests::test_should_solve(grid);
     }
@@ -68,7 +68,11 @@ pub mod general_tests {
 
     /// Removes a number from the grid
     pub fn remove_number(grid: &mut Grid, number: u16) {
-        let mark = Mark::from_value(number);
+        // Only values 1 to 9 can be placed, so there is nothing to remove for other numbers
+        let mark = match Mark::try_from(number) {
+            Ok(mark) => mark,
+            Err(_) => return,
+        };
 
         //Reset all cells with nr 5 to empty
         for i in grid.iter() {
@@ -82,6 +86,17 @@ pub mod general_tests {
         }
     }
 
+    /// A jigsaw layout where a few cells of each square are swapped with a neighboring square
+    pub const JIGSAW_LAYOUT: &str = "111222233
+                                     111222333
+                                     114223333
+                                     144556666
+                                     444555666
+                                     444555566
+                                     778888999
+                                     777888999
+                                     777788999";
+
     /// Returns a filled sudoku grid
     pub fn filled_sudoku() -> Grid {
         parse_from_ascii(
@@ -97,6 +112,7 @@ pub mod general_tests {
              2 4 8 | 9 5 7 | 1 3 6\n\
              7 6 3 | 4 1 8 | 2 5 9",
         )
+        .unwrap()
     }
 
     /// Returns a localhost url with the grid as a query parameter
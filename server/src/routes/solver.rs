@@ -1,39 +1,38 @@
 use actix_web::{get, post, web::Json, HttpResponse};
 use sudoku_solver_lib::{
-    grid::utility::utility::filled_sudoku,
+    grid::{grid::Grid, utility::utility::filled_sudoku},
     solvers::{
+        mark_reset::MarkReset,
+        mark_simple::MarkSimple,
         solver::{AnnotatedSolverResult, SolveResult},
         solver_manager::SolverManager,
+        validator::validation_report,
     },
 };
 
-use crate::data::grid::{GridInput, GridOutput};
+use crate::data::grid::GridInput;
 
 #[post("/api/v1/solve")]
 pub async fn solve(input: Json<GridInput>) -> HttpResponse {
-    if input.is_valid() == false {
-        return HttpResponse::BadRequest().body("body is not valid");
-    }
-
     println!("Solve full");
-    let grid = input.to_grid();
+    if let Err(response) = check_grid(&input.grid) {
+        return response;
+    }
     let solver = SolverManager::new();
-    let result = solver.solve(grid);
-    let output = GridOutput::from_grid(result);
+    let result = solver.solve(input.grid);
 
     HttpResponse::Ok()
         .content_type("application/json")
-        .json(output)
+        .json(result)
 }
 
 #[post("/api/v1/solve/once")]
 pub async fn solve_once(input: Json<GridInput>) -> HttpResponse {
-    if input.is_valid() == false {
-        return HttpResponse::BadRequest().body("body is not valid");
-    }
-
     println!("Solve once");
-    let grid = &mut input.to_grid();
+    if let Err(response) = check_grid(&input.grid) {
+        return response;
+    }
+    let grid = &mut input.grid.clone();
     let solver = SolverManager::new();
     let result = solver.solve_round(grid);
     let annotated = AnnotatedSolverResult {
@@ -41,11 +40,10 @@ pub async fn solve_once(input: Json<GridInput>) -> HttpResponse {
         grid: grid.clone(),
         iterations: 1,
     };
-    let output = GridOutput::from_grid(annotated);
 
     HttpResponse::Ok()
         .content_type("application/json")
-        .json(output)
+        .json(annotated)
 }
 
 #[get("/api/v1/filled")]
@@ -55,9 +53,24 @@ pub async fn filled() -> HttpResponse {
         grid: filled_sudoku(),
         iterations: 1,
     };
-    let output = GridOutput::from_grid(annotated);
 
     HttpResponse::Ok()
         .content_type("application/json")
-        .json(output)
+        .json(annotated)
+}
+
+/// Returns a bad request with the conflicts of the grid as json, if it has any
+fn check_grid(grid: &Grid) -> Result<(), HttpResponse> {
+    let checked = &mut grid.clone();
+    MarkReset::solve(checked);
+    MarkSimple::solve(checked);
+
+    let report = validation_report(checked);
+    if report.is_valid() {
+        return Ok(());
+    }
+
+    Err(HttpResponse::BadRequest()
+        .content_type("application/json")
+        .json(report))
 }
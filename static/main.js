@@ -45,15 +45,82 @@ function setMessage(text, classes) {
 
 //Check if grid was enter by query string, which is a string of 81 characters representing the grid
 var urlParams = new URLSearchParams(window.location.search);
+
+//Variant markers, parity is a string of 81 characters: e for even, o for odd and . for neither
+//greater is a comma separated list of signs between neighboring cells, such as r1c1>r1c2
+set_parity(urlParams.get("parity") || "");
+set_signs(urlParams.get("greater") || "");
+
+function set_parity(parity) {
+  for (var i = 0; i < parity.length && i < 81; i++) {
+    var c = parity.charAt(i).toLowerCase();
+    var td = document.getElementById(`c_${Math.floor(i / 9)}_${i % 9}`);
+
+    td.classList.remove("even", "odd");
+    if (c == "e") {
+      td.classList.add("even");
+    } else if (c == "o") {
+      td.classList.add("odd");
+    }
+  }
+}
+
+function parse_coord(text) {
+  var match = /^r([1-9])c([1-9])$/i.exec(text.trim());
+  if (match == null) {
+    return null;
+  }
+
+  return { row: parseInt(match[1]) - 1, col: parseInt(match[2]) - 1 };
+}
+
+function set_signs(signs) {
+  var old = document.getElementsByClassName("sign");
+  while (old.length > 0) {
+    old[0].remove();
+  }
+
+  signs.split(",").forEach(function (sign) {
+    var parts = sign.split(">");
+    if (parts.length != 2) {
+      return;
+    }
+
+    var greater = parse_coord(parts[0]);
+    var smaller = parse_coord(parts[1]);
+    if (greater == null || smaller == null) {
+      return;
+    }
+
+    //The sign is drawn on the edge after the first of the two cells
+    var first = greater.row * 9 + greater.col < smaller.row * 9 + smaller.col ? greater : smaller;
+    var horizontal = greater.row == smaller.row && Math.abs(greater.col - smaller.col) == 1;
+    var vertical = greater.col == smaller.col && Math.abs(greater.row - smaller.row) == 1;
+    if (!horizontal && !vertical) {
+      return;
+    }
+
+    var span = document.createElement("span");
+    if (horizontal) {
+      span.setAttribute("class", "sign sign_right");
+      span.innerHTML = first == greater ? "&gt;" : "&lt;";
+    } else {
+      span.setAttribute("class", "sign sign_down");
+      span.innerHTML = first == greater ? "&or;" : "&and;";
+    }
+
+    document.getElementById(`c_${first.row}_${first.col}`).appendChild(span);
+  });
+}
 var grid = urlParams.get("grid");
 if (grid != null) {
-  var data = { cells: [] };
+  var data = { grid: [] };
   for (var i = 0; i < grid.length; i++) {
     var c = grid.charAt(i);
     c = c == "." ? 0 : parseInt(c);
-    data.cells.push({
+    data.grid.push({
       value: c,
-      possible: {},
+      candidates: [],
     });
   }
   set_sudoku(data);
@@ -86,7 +153,7 @@ solve.onclick = function () {
 
   const start = Date.now();
   fetch(req)
-    .then((response) => response.json())
+    .then(read_response)
     .then((data) => annotate(data, Date.now() - start))
     .then(set_sudoku)
     .catch((err) => setMessage(err, "error"));
@@ -107,7 +174,7 @@ solve_once.onclick = function () {
 
   const start = Date.now();
   fetch(req)
-    .then((response) => response.json())
+    .then(read_response)
     .then((data) => annotate(data, Date.now() - start))
     .then(set_sudoku)
     .catch((err) => setMessage(err, "error"));
@@ -121,27 +188,88 @@ filled.onclick = function () {
 
   const start = Date.now();
   fetch(req)
-    .then((response) => response.json())
+    .then(read_response)
     .then((data) => annotate(data, Date.now() - start))
     .then(set_sudoku)
     .catch((err) => setMessage(err, "error"));
 };
 
+//Reads the json of the response, a bad request holds the conflicts of the grid
+function read_response(response) {
+  clear_conflicts();
+
+  return response.json().then((data) => {
+    if (response.ok) {
+      return data;
+    }
+    if (data.conflicts == undefined) {
+      return Promise.reject(`Request failed with status ${response.status}`);
+    }
+
+    show_conflicts(data.conflicts);
+    return Promise.reject(data.conflicts.map(conflict_message).join("<br>"));
+  });
+}
+
+function show_conflicts(conflicts) {
+  conflicts.forEach(function (conflict) {
+    [conflict.first, conflict.second, conflict.coord, conflict.placed, conflict.candidate]
+      .filter((coord) => coord != undefined)
+      .forEach(function (coord) {
+        var cell = document.getElementById(`cell_${coord.row * 9 + coord.col}`);
+        cell.classList.add("conflict");
+      });
+  });
+}
+
+function clear_conflicts() {
+  var cells = document.getElementsByClassName("cell");
+  for (var i = 0; i < cells.length; i++) {
+    cells[i].classList.remove("conflict");
+  }
+}
+
+function format_coord(coord) {
+  return `r${coord.row + 1}c${coord.col + 1}`;
+}
+
+function format_unit(unit) {
+  if (typeof unit == "string") {
+    return unit;
+  }
+
+  var kind = Object.keys(unit)[0];
+  return `${kind} ${unit[kind] + 1}`;
+}
+
+function conflict_message(conflict) {
+  switch (conflict.kind) {
+    case "Duplicate":
+      return `Same ${conflict.value} at ${format_coord(conflict.first)} and ${format_coord(conflict.second)} in ${format_unit(conflict.unit)}`;
+    case "NoCandidates":
+      return `No possible values at ${format_coord(conflict.coord)}`;
+    case "NoPlace":
+      return `No place for ${conflict.value} in ${format_unit(conflict.unit)}`;
+    case "PlacedCandidate":
+      return `${conflict.value} at ${format_coord(conflict.placed)} is still possible at ${format_coord(conflict.candidate)}`;
+    case "InvalidValue":
+      return `Invalid value ${conflict.value} at ${format_coord(conflict.coord)}`;
+    case "Constraint":
+      return `${conflict.name}: ${conflict.message}`;
+  }
+
+  return conflict.kind;
+}
+
 function get_sudoku() {
   var cells = document.getElementsByClassName("cell");
-  var data = [];
+  var data = "";
   for (var i = 0; i < cells.length; i++) {
     var value = cells[i].value;
-    if (value == "") {
-      value = 0;
-    } else {
-      value = parseInt(value);
-    }
-
-    data.push(value);
+    data += value == "" ? "0" : value;
   }
 
-  return { cells: data };
+  return { grid: data };
 }
 
 function annotate(data, time) {
@@ -158,13 +286,13 @@ function annotate(data, time) {
 
 function get_result(result) {
     switch (result) {
-        case 0:
+        case "Nothing":
             return 'nothing';
-        case 1:
+        case "Updated":
             return 'updated';
-        case 2:
+        case "Solved":
             return 'solved';
-        case 3:
+        case "Error":
             return 'invalid';
     }
 
@@ -182,7 +310,7 @@ function set_sudoku(data) {
     id = id.substring(5);
     //Convert to int
     id = parseInt(id);
-    var c = data.cells[id];
+    var c = data.grid[id];
     if (c == undefined) {
       continue;
     }
@@ -193,10 +321,8 @@ function set_sudoku(data) {
 
       //Use placeholder to show possible values
       var placeholder = "";
-      for (var j = 0; j < 10; j++) {
-        if (c.possible["p" + j] == true) {
-          placeholder += j;
-        }
+      for (var j = 0; j < c.candidates.length; j++) {
+        placeholder += c.candidates[j];
       }
     }
 
@@ -210,12 +336,16 @@ function set_sudoku(data) {
 function to_query() {
   //Takes the current grid and converts it to a query string
   var cells = document.getElementsByClassName("cell");
-  var query = "?grid=";
+  var grid = "";
   for (var i = 0; i < cells.length; i++) {
     var c = cells[i].value;
-    query += c == "" ? "." : c;
+    grid += c == "" ? "." : c;
   }
 
+  //Keep the variant markers in the url
+  var params = new URLSearchParams(window.location.search);
+  params.set("grid", grid);
+
   //Update url
-  window.history.pushState({}, "", query);
+  window.history.pushState({}, "", "?" + params.toString());
 }
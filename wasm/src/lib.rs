@@ -2,77 +2,96 @@ mod sudoku;
 
 use std::ops::BitXor;
 
-use sudoku::cells::Cell;
+use sudoku::cells;
 use sudoku_solver_lib::{
+    formats::binary,
     generators::generators::Generator,
-    solvers::{fast_solver::FastSolver, solver_manager},
+    grid::grid::Grid,
+    solvers::{fast_solver::FastSolver, solver_manager, validator},
 };
 use wasm_bindgen::prelude::*;
 
 /// Create a new empty grid.
 #[wasm_bindgen]
-pub fn new_grid() -> Vec<Cell> {
-    let mut cells = Vec::new();
-
-    for _ in 0..81 {
-        let c = Cell {
-            value: 0,
-            possibilities: sudoku::cells::Possibilities {
-                p1: true,
-                p2: true,
-                p3: true,
-                p4: true,
-                p5: true,
-                p6: true,
-                p7: true,
-                p8: true,
-                p9: true,
-            },
-        };
-
-        cells.push(c);
+pub fn new_grid() -> Result<JsValue, JsValue> {
+    cells::from_grid(&Grid::new())
+}
+
+/// Parse a grid from the ascii or digit format, returns an error message if the text is not a valid grid.
+#[wasm_bindgen]
+pub fn parse_grid(text: &str) -> Result<JsValue, JsValue> {
+    match text.parse::<Grid>() {
+        Ok(grid) => cells::from_grid(&grid),
+        Err(e) => Err(JsValue::from_str(&e.to_string())),
     }
+}
 
-    cells
+/// Returns a short URL-safe code for the values of the grid, returns an error message if the grid contains invalid values.
+#[wasm_bindgen]
+pub fn encode_grid(grid: Vec<i32>) -> Result<String, JsValue> {
+    let grid = cells::to_sudoku_grid(grid).map_err(|e| JsValue::from_str(&e))?;
+
+    Ok(binary::encode_string(&grid))
 }
 
-/// Solve a grid.
+/// Decodes a grid from a code made by `encode_grid`, returns an error message if the code is not valid.
 #[wasm_bindgen]
-pub fn solve_once(grid: Vec<i32>) -> Vec<Cell> {
-    let grid = &mut Cell::to_sudoku_grid(grid);
+pub fn decode_grid(code: &str) -> Result<JsValue, JsValue> {
+    match binary::decode_string(code) {
+        Ok(grid) => cells::from_grid(&grid),
+        Err(e) => Err(JsValue::from_str(&e.to_string())),
+    }
+}
 
-    let solver = solver_manager::SolverManager::new();
-    let result = solver.solve_simple(grid);
+/// Solve a grid, returns an error message if the grid contains invalid values.
+#[wasm_bindgen]
+pub fn solve_once(grid: Vec<i32>) -> Result<JsValue, JsValue> {
+    let grid = cells::to_sudoku_grid(grid).map_err(|e| JsValue::from_str(&e))?;
 
-    Cell::from_grid(result.grid)
+    cells::from_grid(&solve_grid_once(grid))
 }
 
-/// Solve a sudoku grid.
+/// Solve a sudoku grid, returns an error message if the grid contains invalid values.
 #[wasm_bindgen]
-pub fn solve(grid: Vec<i32>) -> Vec<Cell> {
-    let grid = Cell::to_sudoku_grid(grid);
+pub fn solve(grid: Vec<i32>) -> Result<JsValue, JsValue> {
+    let grid = cells::to_sudoku_grid(grid).map_err(|e| JsValue::from_str(&e))?;
 
-    let mut solver = FastSolver::new_random();
-    let result = solver.solve(&grid);
+    cells::from_grid(&solve_grid(&grid))
+}
 
-    Cell::from_grid(result)
+/// Validates a grid, returns a report with every conflict so they can be highlighted.
+#[wasm_bindgen]
+pub fn validate(grid: Vec<i32>) -> Result<JsValue, JsValue> {
+    let grid = cells::to_sudoku_grid(grid).map_err(|e| JsValue::from_str(&e))?;
+    let report = validator::validation_report(&grid);
+
+    serde_wasm_bindgen::to_value(&report).map_err(JsValue::from)
+}
+
+/// Solves a grid as far as it goes without guessing, returns the english explanation of every step.
+#[wasm_bindgen]
+pub fn explain(grid: Vec<i32>) -> Result<Vec<String>, JsValue> {
+    let grid = cells::to_sudoku_grid(grid).map_err(|e| JsValue::from_str(&e))?;
+    let (_, steps) = solver_manager::SolverManager::new().solve_explained(grid);
+
+    Ok(steps.iter().map(|step| step.to_string()).collect())
 }
 
 /// Generate a new grid with a random seed and difficulty.
 #[wasm_bindgen]
-pub fn generate() -> Vec<Cell> {
+pub fn generate() -> Result<JsValue, JsValue> {
     let mut generator = Generator::new_random();
 
     let grid = generator.generate();
     let mut g = grid.clone();
     generator.remove_cells(&mut g);
 
-    return Cell::from_grid(g);
+    cells::from_grid(&g)
 }
 
 /// Generate a new grid with a specific difficulty and seed. If the difficulty is 0, it will be a full grid.
 #[wasm_bindgen]
-pub fn generate_with(difficulty: i32, seed: i32) -> Vec<Cell> {
+pub fn generate_with(difficulty: i32, seed: i32) -> Result<JsValue, JsValue> {
     if seed == 0 {
         panic!("Seed cannot be 0");
     }
@@ -88,12 +107,26 @@ pub fn generate_with(difficulty: i32, seed: i32) -> Vec<Cell> {
         generator.remove_cells_amount(&mut g, difficulty as usize);
     }
 
-    return Cell::from_grid(g);
+    cells::from_grid(&g)
+}
+
+fn solve_grid_once(mut grid: Grid) -> Grid {
+    let solver = solver_manager::SolverManager::new();
+
+    solver.solve_simple(&mut grid).grid
+}
+
+fn solve_grid(grid: &Grid) -> Grid {
+    let mut solver = FastSolver::new_random();
+
+    solver.solve(grid)
 }
 
 #[cfg(test)]
 mod test {
-    use crate::solve;
+    use sudoku_solver_lib::grid::cell_collection::CellCollection;
+
+    use crate::{solve_grid, sudoku::cells};
 
     #[test]
     pub fn test_solve() {
@@ -103,11 +136,20 @@ mod test {
             0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
         ];
 
-        let output = solve(input);
+        let grid = cells::to_sudoku_grid(input).unwrap();
+        let output = solve_grid(&grid);
 
-        for c in output.iter() {
-            println!("{:?}", c);
-            assert_ne!(c.value, 0);
+        for index in output.iter() {
+            assert!(output.get_cell(index).is_determined());
         }
     }
+
+    #[test]
+    pub fn test_invalid_values() {
+        let mut input = vec![0; 81];
+        input[4] = 64;
+
+        assert!(cells::to_sudoku_grid(input).is_err());
+        assert!(cells::to_sudoku_grid(vec![0; 80]).is_err());
+    }
 }
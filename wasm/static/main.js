@@ -57,14 +57,27 @@ if (grid != null) {
     c = c == "." ? 0 : parseInt(c);
     data.cells.push({
       value: c,
-      possibilities: {},
+      candidates: [],
     });
   }
   set_sudoku(data);
 }
 
+var wasm_loaded = null;
+
 wasm.then(async (wasm_module) => {
   await wasm_module.default();
+  wasm_loaded = wasm_module;
+
+  //Check if grid was shared as a code, which is shorter than the grid string
+  var code = urlParams.get("code");
+  if (code != null) {
+    try {
+      set_sudoku({ cells: wasm_module.decode_grid(code) });
+    } catch (err) {
+      setMessage(err, "error");
+    }
+  }
 
   var solve = document.getElementById("solve");
   solve.onclick = function () {
@@ -144,7 +157,7 @@ function get_result(result) {
 
 /**
  *
- * @param {{cells:Pick<wasm.Cell, "possibilities" | "value">[] }} data
+ * @param {{cells:{value: number, candidates: number[]}[] }} data
  */
 function set_sudoku(data) {
   console.log("received", data);
@@ -169,11 +182,7 @@ function set_sudoku(data) {
       value = "";
 
       if (hints) {
-        for (var j = 0; j < 10; j++) {
-          if (c.possibilities[`p${j}`] === true) {
-            placeholder += j;
-          }
-        }
+        placeholder = c.candidates.join("");
       }
     }
 
@@ -208,6 +217,17 @@ function get_sudoku() {
 }
 
 function to_query() {
+  //Takes the current grid and converts it to a short code once the wasm module is loaded
+  if (wasm_loaded != null) {
+    try {
+      var code = wasm_loaded.encode_grid(get_sudoku());
+      window.history.pushState({}, "", "?code=" + code);
+      return;
+    } catch (err) {
+      console.log("unable to encode grid", err);
+    }
+  }
+
   //Takes the current grid and converts it to a query string
   var cells = document.getElementsByClassName("cell");
   var query = "?grid=";